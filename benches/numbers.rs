@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde::Serialize;
+use serde_rdf::{to_string, PropertyConfig, SerializerConfig, SubjectConfig};
+
+#[derive(Serialize)]
+struct Series {
+    id: String,
+    values: Vec<u64>,
+    readings: Vec<f64>,
+}
+
+fn config() -> SerializerConfig {
+    let property = |field: &str| PropertyConfig {
+        struct_field: field.to_string(),
+        rdf_property: format!("https://example.org/ns#{field}"),
+        ..Default::default()
+    };
+    SerializerConfig {
+        subjects: HashMap::from([(
+            "Series".to_string(),
+            SubjectConfig {
+                struct_name: "Series".to_string(),
+                rdf_type: "https://example.org/ns#Series".to_string(),
+                identifier_field: "id".to_string(),
+                identifier_prefix: "https://example.org/".to_string(),
+                properties: vec![property("values"), property("readings")],
+                ..Default::default()
+            },
+        )]),
+        ..Default::default()
+    }
+}
+
+fn numbers(c: &mut Criterion) {
+    let config = config();
+    let integers = Series {
+        id: "integers".to_string(),
+        values: (0..100_000).map(|n| n * 7_919).collect(),
+        readings: Vec::new(),
+    };
+    let floats = Series {
+        id: "floats".to_string(),
+        values: Vec::new(),
+        readings: (0..100_000).map(|n| f64::from(n) / 7.0).collect(),
+    };
+
+    let mut group = c.benchmark_group("serialize 100k");
+    group.sample_size(20);
+    group.bench_function("integers", |b| {
+        b.iter(|| to_string(black_box(&integers), &config).unwrap())
+    });
+    group.bench_function("floats", |b| {
+        b.iter(|| to_string(black_box(&floats), &config).unwrap())
+    });
+    group.finish();
+
+    // The lexical forms alone, as formatted before with `to_string` and now with itoa and ryu.
+    let mut group = c.benchmark_group("lexical forms of 100k");
+    group.bench_function("integers with std", |b| {
+        b.iter(|| {
+            let forms: Vec<String> = integers.values.iter().map(u64::to_string).collect();
+            black_box(forms)
+        })
+    });
+    group.bench_function("integers with itoa", |b| {
+        let mut buffer = itoa::Buffer::new();
+        b.iter(|| {
+            let forms: Vec<String> = integers
+                .values
+                .iter()
+                .map(|n| buffer.format(*n).to_owned())
+                .collect();
+            black_box(forms)
+        })
+    });
+    group.bench_function("floats with std", |b| {
+        b.iter(|| {
+            let forms: Vec<String> = floats.readings.iter().map(f64::to_string).collect();
+            black_box(forms)
+        })
+    });
+    group.bench_function("floats with ryu", |b| {
+        let mut buffer = ryu::Buffer::new();
+        b.iter(|| {
+            let forms: Vec<String> = floats
+                .readings
+                .iter()
+                .map(|n| buffer.format(*n).to_owned())
+                .collect();
+            black_box(forms)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, numbers);
+criterion_main!(benches);
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde::Serialize;
+use serde_rdf::{to_string, PropertyConfig, SerializerConfig, SubjectConfig};
+
+#[derive(Serialize)]
+struct Project {
+    id: String,
+    name: String,
+    shortcode: String,
+    keywords: Vec<String>,
+}
+
+fn config() -> SerializerConfig {
+    let property = |field: &str, property: &str| PropertyConfig {
+        struct_field: field.to_string(),
+        rdf_property: format!("dsp:{property}"),
+        ..Default::default()
+    };
+    SerializerConfig {
+        namespaces: HashMap::from([(
+            "dsp".to_string(),
+            "https://ns.dasch.swiss/repository#".to_string(),
+        )]),
+        subjects: HashMap::from([(
+            "Project".to_string(),
+            SubjectConfig {
+                struct_name: "Project".to_string(),
+                rdf_type: "dsp:Project".to_string(),
+                identifier_field: "id".to_string(),
+                identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                properties: vec![
+                    property("name", "hasName"),
+                    property("shortcode", "hasShortcode"),
+                    property("keywords", "hasKeyword"),
+                ],
+                ..Default::default()
+            },
+        )]),
+        ..Default::default()
+    }
+}
+
+fn records(c: &mut Criterion) {
+    let config = config();
+    let projects: Vec<Project> = (0..10_000)
+        .map(|n| Project {
+            id: format!("{n:04X}"),
+            name: format!("Project {n}"),
+            shortcode: format!("{n:04X}"),
+            keywords: vec!["history".to_string(), "archive".to_string()],
+        })
+        .collect();
+
+    // One document per record, e.g. one per request of a service, with the config shared by
+    // all of them.
+    let mut group = c.benchmark_group("serialize 10k records with a shared config");
+    group.sample_size(20);
+    group.bench_function("one call per record", |b| {
+        b.iter(|| {
+            for project in &projects {
+                black_box(to_string(black_box(project), &config).unwrap());
+            }
+        })
+    });
+    group.bench_function("one call for all records", |b| {
+        b.iter(|| to_string(black_box(&projects), &config).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, records);
+criterion_main!(benches);
@@ -0,0 +1,149 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde::Serialize;
+use serde_rdf::{to_string, to_triples, PropertyConfig, SerializerConfig, SubjectConfig};
+
+/// Counts the allocations of the benchmark, which are reported per record next to the timings.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const FIELDS: [&str; 20] = [
+    "f00", "f01", "f02", "f03", "f04", "f05", "f06", "f07", "f08", "f09", "f10", "f11", "f12",
+    "f13", "f14", "f15", "f16", "f17", "f18", "f19",
+];
+
+#[derive(Serialize)]
+struct Record {
+    id: String,
+    f00: String,
+    f01: String,
+    f02: String,
+    f03: String,
+    f04: String,
+    f05: String,
+    f06: String,
+    f07: String,
+    f08: String,
+    f09: String,
+    f10: String,
+    f11: String,
+    f12: String,
+    f13: String,
+    f14: String,
+    f15: String,
+    f16: String,
+    f17: String,
+    f18: String,
+    f19: String,
+}
+
+impl Record {
+    fn new(n: usize) -> Self {
+        let value = |field: usize| format!("value {field} of record {n}");
+        Record {
+            id: n.to_string(),
+            f00: value(0),
+            f01: value(1),
+            f02: value(2),
+            f03: value(3),
+            f04: value(4),
+            f05: value(5),
+            f06: value(6),
+            f07: value(7),
+            f08: value(8),
+            f09: value(9),
+            f10: value(10),
+            f11: value(11),
+            f12: value(12),
+            f13: value(13),
+            f14: value(14),
+            f15: value(15),
+            f16: value(16),
+            f17: value(17),
+            f18: value(18),
+            f19: value(19),
+        }
+    }
+}
+
+fn config() -> SerializerConfig {
+    let properties = FIELDS
+        .iter()
+        .map(|field| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("https://example.org/ns#{field}"),
+            ..Default::default()
+        })
+        .collect();
+    SerializerConfig {
+        subjects: HashMap::from([(
+            "Record".to_string(),
+            SubjectConfig {
+                struct_name: "Record".to_string(),
+                rdf_type: "https://example.org/ns#Record".to_string(),
+                identifier_field: "id".to_string(),
+                identifier_prefix: "https://example.org/".to_string(),
+                properties,
+                ..Default::default()
+            },
+        )]),
+        ..Default::default()
+    }
+}
+
+/// The allocations made by `f` per record.
+fn allocations_per_record(records: usize, f: impl FnOnce()) -> f64 {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) as f64 / records as f64
+}
+
+fn strings(c: &mut Criterion) {
+    let config = config();
+    let records: Vec<Record> = (0..10_000).map(Record::new).collect();
+
+    let turtle = allocations_per_record(records.len(), || {
+        black_box(to_string(&records, &config).unwrap());
+    });
+    let triples = allocations_per_record(records.len(), || {
+        black_box(to_triples(&records, &config).unwrap());
+    });
+    println!("allocations per record: {turtle:.1} as Turtle, {triples:.1} as triples");
+
+    let mut group = c.benchmark_group("serialize 10k records with 20 strings");
+    group.sample_size(20);
+    group.bench_function("to_string", |b| {
+        b.iter(|| to_string(black_box(&records), &config).unwrap())
+    });
+    group.bench_function("to_triples", |b| {
+        b.iter(|| to_triples(black_box(&records), &config).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, strings);
+criterion_main!(benches);
@@ -44,13 +44,22 @@ pub enum IsoCode {
 
 fn main() {
     let project_ttl = r#"
-       <https://ark.dasch.swiss/ark:/72163/1/081C> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Project> ;
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        <https://ark.dasch.swiss/ark:/72163/1/081C> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Project> ;
             <https://ns.dasch.swiss/repository#hasName> "Hôtel de Musique Bern"^^xsd:string ;
             <https://ns.dasch.swiss/repository#hasDescription> "The database documents the events that took place in the Hôtel de Musique in Bern between 1766 and 1905. The repertoire was constituted by different kinds of spectacles like theatre plays, operas, ballets, concerts, dance parties, acrobatic performances, conferences or magicians. The list reconstructs the lifely and colourful theatre culture of Bern in the 19th Century."@en ;
 
             <https://ns.dasch.swiss/repository#hasShortcode> "081C"^^xsd:string ;
-            <https://ns.dasch.swiss/repository#hasDataset> <dataset-0> ;
+            <https://ns.dasch.swiss/repository#hasDataset> <https://ark.dasch.swiss/ark:/72163/1/081C/dataset-0> .
+
+        <https://ark.dasch.swiss/ark:/72163/1/081C/dataset-0> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Dataset> ;
+            <https://ns.dasch.swiss/repository#hasName> "Hôtel de Musique Bern Events"^^xsd:string .
     "#;
 
-    let _project: Project = serde_rdf::from_str(project_ttl).unwrap();
+    let project: Project = serde_rdf::from_str(project_ttl).unwrap();
+    assert_eq!(project.shortcode, "081C");
+    assert_eq!(project.datasets.len(), 1);
+    assert!(project.description.0[&IsoCode::EN].starts_with("The database"));
+    println!("{:#?}", project);
 }
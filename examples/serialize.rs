@@ -61,11 +61,13 @@ fn main() {
                 identifier_field: "id".to_string(),
                 identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
                 properties: Vec::new(),
+                ..Default::default()
             },
         )]),
+        ..Default::default()
     };
 
-    let project_ttl = serde_rdf::to_string(&dataset, config).unwrap();
+    let project_ttl = serde_rdf::to_string(&dataset, &config).unwrap();
 
     dbg!(project_ttl);
 }
@@ -0,0 +1,194 @@
+//! `#[derive(RdfMapped)]`, which implements `serde_rdf::RdfMapped` from `#[rdf(...)]`
+//! attributes. It is re-exported by the `derive` feature of `serde_rdf`.
+//!
+//! On the struct:
+//! - `type = "dsp:Project"`: the `rdf_type`, which is required
+//! - `id(field = "id", prefix = "https://ark.dasch.swiss/ark:/72163/1/")`: the identifier
+//!   field and prefix, without which the subjects are blank nodes
+//! - `prefix(dsp = "https://ns.dasch.swiss/repository#")`: the namespaces of prefixed names
+//!
+//! On a field, which is otherwise not part of the RDF representation:
+//! - `property = "dsp:hasName"`: the `rdf_property`, which is required
+//! - `lang = "en"`: the language tag of its literals
+//! - `nested = Dataset`: the struct of its subjects, e.g. of a `Vec<Dataset>`, whose configs
+//!   are collected with the one of the struct
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Path, Result};
+
+#[proc_macro_derive(RdfMapped, attributes(rdf))]
+pub fn derive_rdf_mapped(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The `#[rdf(...)]` attributes of the struct.
+#[derive(Default)]
+struct Subject {
+    rdf_type: Option<LitStr>,
+    identifier_field: Option<LitStr>,
+    identifier_prefix: Option<LitStr>,
+    namespaces: Vec<(String, LitStr)>,
+}
+
+/// The `#[rdf(...)]` attributes of a field.
+#[derive(Default)]
+struct Property {
+    rdf_property: Option<LitStr>,
+    language: Option<LitStr>,
+    nested: Option<Path>,
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "RdfMapped can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "RdfMapped can only be derived for structs with named fields",
+        ));
+    };
+
+    let subject = subject(input)?;
+    let rdf_type = subject.rdf_type.ok_or_else(|| {
+        Error::new_spanned(&input.ident, "missing #[rdf(type = \"...\")] on the struct")
+    })?;
+    let string = |value: Option<&LitStr>| match value {
+        Some(value) => quote!(::std::string::String::from(#value)),
+        None => quote!(::std::string::String::new()),
+    };
+    let identifier_field = string(subject.identifier_field.as_ref());
+    let identifier_prefix = string(subject.identifier_prefix.as_ref());
+    let (prefixes, namespaces): (Vec<_>, Vec<_>) = subject.namespaces.into_iter().unzip();
+
+    let mut properties = Vec::new();
+    let mut nested = Vec::new();
+    for field in &fields.named {
+        let Some(property) = property(field)? else {
+            continue;
+        };
+        let ident = field.ident.as_ref().expect("named fields have an ident");
+        let struct_field = ident.to_string();
+        let rdf_property = property.rdf_property.ok_or_else(|| {
+            Error::new_spanned(ident, "missing #[rdf(property = \"...\")] on the field")
+        })?;
+        nested.extend(property.nested);
+        let language = match property.language {
+            Some(language) => {
+                quote!(::std::option::Option::Some(::std::string::String::from(#language)))
+            }
+            None => quote!(::std::option::Option::None),
+        };
+        properties.push(quote! {
+            ::serde_rdf::PropertyConfig {
+                struct_field: ::std::string::String::from(#struct_field),
+                rdf_property: ::std::string::String::from(#rdf_property),
+                language: #language,
+                ..::std::default::Default::default()
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let struct_name = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::serde_rdf::RdfMapped for #ident #ty_generics #where_clause {
+            fn subject_config() -> ::serde_rdf::SubjectConfig {
+                ::serde_rdf::SubjectConfig {
+                    struct_name: ::std::string::String::from(#struct_name),
+                    rdf_type: ::std::string::String::from(#rdf_type),
+                    identifier_field: #identifier_field,
+                    identifier_prefix: #identifier_prefix,
+                    properties: ::std::vec![#(#properties),*],
+                    ..::std::default::Default::default()
+                }
+            }
+
+            fn namespaces() -> ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                ::std::vec![#((
+                    ::std::string::String::from(#prefixes),
+                    ::std::string::String::from(#namespaces)
+                )),*]
+            }
+
+            #[allow(unused_variables)]
+            fn nested_configs(config: &mut ::serde_rdf::SerializerConfig) {
+                #(<#nested as ::serde_rdf::RdfMapped>::collect_configs(config);)*
+            }
+        }
+    })
+}
+
+fn subject(input: &DeriveInput) -> Result<Subject> {
+    let mut subject = Subject::default();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("rdf"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("type") {
+                subject.rdf_type = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("id") {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("field") {
+                        subject.identifier_field = Some(meta.value()?.parse()?);
+                    } else if meta.path.is_ident("prefix") {
+                        subject.identifier_prefix = Some(meta.value()?.parse()?);
+                    } else {
+                        return Err(meta.error("expected `field` or `prefix`"));
+                    }
+                    Ok(())
+                })?;
+            } else if meta.path.is_ident("prefix") {
+                meta.parse_nested_meta(|meta| {
+                    let prefix = meta
+                        .path
+                        .get_ident()
+                        .ok_or_else(|| meta.error("expected a prefix"))?
+                        .to_string();
+                    subject.namespaces.push((prefix, meta.value()?.parse()?));
+                    Ok(())
+                })?;
+            } else {
+                return Err(meta.error("expected `type`, `id` or `prefix`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(subject)
+}
+
+/// The attributes of a field, if it has any.
+fn property(field: &syn::Field) -> Result<Option<Property>> {
+    let mut property = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("rdf"))
+    {
+        let property = property.get_or_insert_with(Property::default);
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("property") {
+                property.rdf_property = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("lang") {
+                property.language = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("nested") {
+                property.nested = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `property`, `lang` or `nested`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(property)
+}
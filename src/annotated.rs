@@ -0,0 +1,64 @@
+//! Attach statement-level metadata to a single property using RDF-star.
+
+use std::collections::HashMap;
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// The name under which [`Annotated`] is recognized by the serializer.
+pub(crate) const NAME: &str = "$serde_rdf::private::Annotated";
+pub(crate) const VALUE: &str = "value";
+pub(crate) const ANNOTATIONS: &str = "annotations";
+
+/// A field value together with annotations about the statement it produces.
+///
+/// The wrapped value is serialized as if the wrapper was not there. In addition, every triple
+/// produced by the field is annotated using the Turtle-star syntax
+/// `<< s p o >> <annotation-predicate> "annotation value" .`, where the keys of `annotations`
+/// are the predicate IRIs and the values are written as `xsd:string` literals.
+///
+/// Example:
+/// ```
+/// use std::collections::HashMap;
+/// use serde_rdf::Annotated;
+/// let _name = Annotated {
+///     value: "Hôtel de Musique Bern".to_string(),
+///     annotations: HashMap::from([(
+///         "https://ns.dasch.swiss/repository#confidence".to_string(),
+///         "0.9".to_string(),
+///     )]),
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Annotated<T> {
+    pub value: T,
+    pub annotations: HashMap<String, String>,
+}
+
+impl<T> Annotated<T> {
+    /// Wrap a value without any annotations.
+    pub fn new(value: T) -> Self {
+        Annotated {
+            value,
+            annotations: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Serialize for Annotated<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Sort the annotations, so that the output does not depend on the order of the map.
+        let mut annotations: Vec<(&String, &String)> = self.annotations.iter().collect();
+        annotations.sort();
+
+        let mut state = serializer.serialize_struct(NAME, 2)?;
+        state.serialize_field(VALUE, &self.value)?;
+        state.serialize_field(ANNOTATIONS, &annotations)?;
+        state.end()
+    }
+}
@@ -0,0 +1,185 @@
+//! Convert between JSON records and RDF with a mapping config, to try out a config without
+//! writing Rust.
+//!
+//! ```text
+//! serde-rdf serialize --config mapping.yaml --input record.json --format turtle
+//! serde-rdf deserialize --config mapping.yaml --input data.ttl --subject <iri>
+//! ```
+
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use serde::Deserialize;
+use serde_rdf::{
+    to_string_with_format, Deserializer, DeserializerConfig, Format, SerializerConfig,
+};
+
+const USAGE: &str = "\
+Usage:
+    serde-rdf serialize --config <file> [--input <file>] [--format <format>] [--struct <name>]
+    serde-rdf deserialize --config <file> [--input <file>] [--format <format>] [--subject <iri>]
+
+Options:
+    --config <file>    The mapping config, in YAML, JSON or TOML (by extension)
+    --input <file>     The input, read from stdin if absent or `-`
+    --format <format>  The RDF format written or read, e.g. turtle, ntriples or a media type;
+                       by default the format of the input's extension, or Turtle
+    --struct <name>    The struct the JSON record maps to; by default the only subject of the
+                       config
+    --subject <iri>    The subject to read instead of the root of the document
+";
+
+/// The options of both commands.
+#[derive(Default)]
+struct Options {
+    config: Option<String>,
+    input: Option<String>,
+    format: Option<String>,
+    struct_name: Option<String>,
+    subject: Option<String>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((command, args)) = args.split_first() else {
+        eprint!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let options = match parse_options(args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprint!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let output = match command.as_str() {
+        "serialize" => serialize(&options),
+        "deserialize" => deserialize(&options),
+        "help" | "--help" | "-h" => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        command => {
+            eprint!("error: unknown command {command}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match output {
+        Ok(output) => {
+            let mut stdout = io::stdout().lock();
+            match stdout.write_all(output.as_bytes()) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let option = match flag.as_str() {
+            "--config" => &mut options.config,
+            "--input" => &mut options.input,
+            "--format" => &mut options.format,
+            "--struct" => &mut options.struct_name,
+            "--subject" => &mut options.subject,
+            flag => return Err(format!("unknown option {flag}")),
+        };
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value of {flag}"))?;
+        *option = Some(value.clone());
+    }
+    Ok(options)
+}
+
+/// Serialize the JSON record of the input as the struct of the options.
+fn serialize(options: &Options) -> serde_rdf::Result<String> {
+    let mut config = load_config(options)?;
+    let struct_name = match &options.struct_name {
+        Some(name) => name.clone(),
+        None => match config.subjects.keys().collect::<Vec<_>>()[..] {
+            [name] => name.clone(),
+            _ => {
+                return Err(serde_rdf::Error::Message(
+                    "serialize -> the config has several subjects, choose one with --struct"
+                        .to_string(),
+                ))
+            }
+        },
+    };
+    if !config.subjects.contains_key(&struct_name) {
+        return Err(serde_rdf::Error::MissingSubjectConfig { struct_name });
+    }
+    let record: serde_json::Value = serde_json::from_str(&read_input(options)?).map_err(|err| {
+        serde_rdf::Error::Message(format!("serialize -> invalid JSON input: {err}"))
+    })?;
+    config.root_subject = Some(struct_name);
+    to_string_with_format(&record, &config, format(options, Format::Turtle)?)
+}
+
+/// Deserialize the subject of the options from the RDF input and write it as JSON.
+fn deserialize(options: &Options) -> serde_rdf::Result<String> {
+    let config = DeserializerConfig::try_from(&load_config(options)?)?;
+    let input = read_input(options)?;
+    let extension = options
+        .input
+        .as_deref()
+        .and_then(|path| Path::new(path).extension())
+        .and_then(|extension| Format::from_extension(&extension.to_string_lossy()));
+    let format = format(options, extension.unwrap_or(Format::Turtle))?;
+    let mut deserializer = Deserializer::from_str_with_format(&input, format)?.with_config(&config);
+    if let Some(subject) = &options.subject {
+        deserializer = deserializer.with_subject(subject)?;
+    }
+    let record = serde_json::Value::deserialize(deserializer)?;
+    let mut json = serde_json::to_string_pretty(&record)
+        .map_err(|err| serde_rdf::Error::Message(err.to_string()))?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// The config of the options, loaded from TOML or YAML, which includes JSON.
+fn load_config(options: &Options) -> serde_rdf::Result<SerializerConfig> {
+    let Some(path) = &options.config else {
+        return Err(serde_rdf::Error::Message(
+            "missing --config <file>".to_string(),
+        ));
+    };
+    let file = std::fs::read_to_string(path)
+        .map_err(|err| serde_rdf::Error::Message(format!("cannot read config {path}: {err}")))?;
+    match Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some("toml") => SerializerConfig::from_toml_str(&file),
+        _ => SerializerConfig::from_reader(file.as_bytes()),
+    }
+}
+
+fn read_input(options: &Options) -> serde_rdf::Result<String> {
+    match options.input.as_deref() {
+        None | Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|err| serde_rdf::Error::Message(format!("cannot read input {path}: {err}"))),
+    }
+}
+
+/// The format of the options, or the given default.
+fn format(options: &Options, default: Format) -> serde_rdf::Result<Format> {
+    options.format.as_deref().map_or(Ok(default), str::parse)
+}
@@ -0,0 +1,72 @@
+//! Decoding of the `xsd:base64Binary` and `xsd:hexBinary` datatypes.
+
+use crate::error::{Error, Result};
+
+/// Decode the lexical form of an `xsd:base64Binary`, which uses the standard alphabet and
+/// requires padding. Spaces between the characters are ignored.
+pub(crate) fn decode_base64(value: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(value.len() / 4 * 3);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    let mut length = 0;
+    let mut padding = 0;
+    for (offset, c) in value.char_indices() {
+        let sextet = match c {
+            ' ' => continue,
+            '=' => {
+                padding += 1;
+                length += 1;
+                continue;
+            }
+            'A'..='Z' => c as u32 - 'A' as u32,
+            'a'..='z' => c as u32 - 'a' as u32 + 26,
+            '0'..='9' => c as u32 - '0' as u32 + 52,
+            '+' => 62,
+            '/' => 63,
+            _ => return Err(invalid(value, "xsd:base64Binary", c, offset)),
+        };
+        if padding > 0 {
+            return Err(invalid(value, "xsd:base64Binary", c, offset));
+        }
+        buffer = (buffer << 6) | sextet;
+        bits += 6;
+        length += 1;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    // The bits left over by the padding must be zero.
+    if length % 4 != 0 || padding > 2 || buffer != 0 {
+        return Err(Error::Message(format!(
+            "invalid padding in xsd:base64Binary {value:?}"
+        )));
+    }
+    Ok(bytes)
+}
+
+/// Decode the lexical form of an `xsd:hexBinary`, which may mix upper and lower case digits.
+pub(crate) fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return Err(Error::Message(format!(
+            "odd number of digits in xsd:hexBinary {value:?}"
+        )));
+    }
+    let digit = |offset: usize| {
+        let c = value[offset..].chars().next().unwrap_or_default();
+        c.to_digit(16)
+            .map(|digit| digit as u8)
+            .ok_or_else(|| invalid(value, "xsd:hexBinary", c, offset))
+    };
+    (0..value.len())
+        .step_by(2)
+        .map(|offset| Ok((digit(offset)? << 4) | digit(offset + 1)?))
+        .collect()
+}
+
+fn invalid(value: &str, datatype: &str, c: char, offset: usize) -> Error {
+    Error::Message(format!(
+        "invalid character {c:?} at byte offset {offset} in {datatype} {value:?}"
+    ))
+}
@@ -0,0 +1,390 @@
+//! [RDF Dataset Canonicalization](https://www.w3.org/TR/rdf-canon/) (RDFC-1.0) of a graph, with
+//! SHA-256 as the hash algorithm.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, Result};
+use crate::ns::XSD_STRING;
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+
+/// The number of permutations of related blank nodes tried before giving up, which only graphs
+/// with large sets of indistinguishable blank nodes reach.
+pub(crate) const MAX_PERMUTATIONS: usize = 100_000;
+
+/// The canonical N-Quads of the triples, with the blank nodes labeled `c14n0`, `c14n1`, ….
+pub(crate) fn canonicalize(triples: &[OwnedTriple]) -> Result<String> {
+    let mut state = State {
+        triples: HashMap::new(),
+        canonical: Issuer::new("c14n"),
+        permutations: 0,
+    };
+    for triple in triples {
+        if matches!(triple.subject, OwnedSubject::Triple(_))
+            || matches!(triple.object, OwnedObject::Triple(_))
+        {
+            return Err(Error::new(
+                "canonicalize -> quoted triples are not supported by RDFC-1.0",
+            ));
+        }
+        let (subject, object) = blank_nodes(triple);
+        for node in subject
+            .into_iter()
+            .chain(object.filter(|o| Some(*o) != subject))
+        {
+            state.triples.entry(node).or_default().push(triple);
+        }
+    }
+
+    // Blank nodes with a unique first degree hash are labeled in the order of their hashes.
+    let mut nodes: Vec<_> = state.triples.keys().copied().collect();
+    nodes.sort_unstable();
+    let mut by_hash: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for node in nodes {
+        by_hash
+            .entry(state.first_degree_hash(node))
+            .or_default()
+            .push(node);
+    }
+    for nodes in by_hash.values().filter(|nodes| nodes.len() == 1) {
+        state.canonical.issue(nodes[0]);
+    }
+    // The others in the order of their n-degree hashes, along with the blank nodes they reach.
+    for nodes in by_hash.values().filter(|nodes| nodes.len() > 1) {
+        let mut paths = Vec::new();
+        for node in nodes {
+            if state.canonical.get(node).is_some() {
+                continue;
+            }
+            let mut issuer = Issuer::new("b");
+            issuer.issue(node);
+            paths.push(state.n_degree_hash(node, issuer)?);
+        }
+        paths.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (_, issuer) in paths {
+            for node in &issuer.issued {
+                state.canonical.issue(node);
+            }
+        }
+    }
+
+    let canonical = &state.canonical;
+    let mut lines: Vec<_> = triples
+        .iter()
+        .map(|triple| nquad(triple, &|node| canonical.get(node).unwrap_or(node)))
+        .collect();
+    lines.sort_unstable();
+    // A simple literal and the same one typed as `xsd:string` are the same term.
+    lines.dedup();
+    Ok(lines.concat())
+}
+
+/// Issues identifiers with a prefix and a counter to blank nodes, remembering their order.
+#[derive(Debug, Clone)]
+struct Issuer {
+    prefix: &'static str,
+    /// The blank nodes in the order their identifiers were issued.
+    issued: Vec<String>,
+    identifiers: HashMap<String, String>,
+}
+
+impl Issuer {
+    fn new(prefix: &'static str) -> Self {
+        Issuer {
+            prefix,
+            issued: Vec::new(),
+            identifiers: HashMap::new(),
+        }
+    }
+
+    fn get(&self, node: &str) -> Option<&str> {
+        self.identifiers.get(node).map(String::as_str)
+    }
+
+    /// The identifier of the blank node, issuing a new one if it has none yet.
+    fn issue(&mut self, node: &str) -> String {
+        if let Some(identifier) = self.identifiers.get(node) {
+            return identifier.clone();
+        }
+        let identifier = format!("{}{}", self.prefix, self.issued.len());
+        self.issued.push(node.to_string());
+        self.identifiers
+            .insert(node.to_string(), identifier.clone());
+        identifier
+    }
+}
+
+struct State<'a> {
+    /// The triples mentioning each blank node.
+    triples: HashMap<&'a str, Vec<&'a OwnedTriple>>,
+    canonical: Issuer,
+    permutations: usize,
+}
+
+impl<'a> State<'a> {
+    /// The hash of the triples of the blank node, with it labeled `a` and others `z`.
+    fn first_degree_hash(&self, node: &str) -> String {
+        let mut lines: Vec<_> = self.triples[node]
+            .iter()
+            .map(|triple| nquad(triple, &|other| if other == node { "a" } else { "z" }))
+            .collect();
+        lines.sort_unstable();
+        sha256(&lines.concat())
+    }
+
+    /// The hash of a blank node related to another by the triple, in the given position.
+    fn related_hash(
+        &self,
+        related: &str,
+        triple: &OwnedTriple,
+        issuer: &Issuer,
+        position: char,
+    ) -> String {
+        let mut input = format!("{position}<{}>", triple.predicate);
+        match self.canonical.get(related).or_else(|| issuer.get(related)) {
+            Some(identifier) => {
+                input.push_str("_:");
+                input.push_str(identifier);
+            }
+            None => input.push_str(&self.first_degree_hash(related)),
+        }
+        sha256(&input)
+    }
+
+    /// The hash of the blank node and the blank nodes reachable from it, and the issuer that
+    /// labeled them along the lexicographically least path.
+    fn n_degree_hash(&mut self, node: &str, mut issuer: Issuer) -> Result<(String, Issuer)> {
+        let mut related: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
+        for triple in self.triples[node].clone() {
+            let (subject, object) = blank_nodes(triple);
+            for (position, other) in [('s', subject), ('o', object)] {
+                if let Some(other) = other.filter(|other| *other != node) {
+                    related
+                        .entry(self.related_hash(other, triple, &issuer, position))
+                        .or_default()
+                        .push(other);
+                }
+            }
+        }
+
+        let mut data = String::new();
+        for (hash, nodes) in related {
+            data.push_str(&hash);
+            let mut chosen: Option<(String, Issuer)> = None;
+            let mut order: Vec<_> = (0..nodes.len()).collect();
+            loop {
+                self.permutations += 1;
+                if self.permutations > MAX_PERMUTATIONS {
+                    return Err(Error::Message(format!(
+                        "canonicalize -> gave up after {MAX_PERMUTATIONS} permutations of \
+                         indistinguishable blank nodes"
+                    )));
+                }
+                let permutation: Vec<_> = order.iter().map(|i| nodes[*i]).collect();
+                let chosen_path = chosen.as_ref().map_or("", |(path, _)| path.as_str());
+                if let Some((path, candidate)) = self.path(&permutation, &issuer, chosen_path)? {
+                    if chosen_path.is_empty() || path.as_str() < chosen_path {
+                        chosen = Some((path, candidate));
+                    }
+                }
+                if !next_permutation(&mut order) {
+                    break;
+                }
+            }
+            if let Some((path, chosen)) = chosen {
+                data.push_str(&path);
+                issuer = chosen;
+            }
+        }
+        Ok((sha256(&data), issuer))
+    }
+
+    /// The path through the related blank nodes in the order of the permutation, unless it is
+    /// greater than the chosen path so far.
+    fn path(
+        &mut self,
+        permutation: &[&'a str],
+        issuer: &Issuer,
+        chosen_path: &str,
+    ) -> Result<Option<(String, Issuer)>> {
+        let worse = |path: &str| {
+            !chosen_path.is_empty() && path.len() >= chosen_path.len() && path > chosen_path
+        };
+        let mut issuer = issuer.clone();
+        let mut path = String::new();
+        let mut recursion = Vec::new();
+        for related in permutation {
+            path.push_str("_:");
+            match self.canonical.get(related) {
+                Some(identifier) => path.push_str(identifier),
+                None => {
+                    if issuer.get(related).is_none() {
+                        recursion.push(*related);
+                    }
+                    path.push_str(&issuer.issue(related));
+                }
+            }
+            if worse(&path) {
+                return Ok(None);
+            }
+        }
+        for related in recursion {
+            let (hash, result) = self.n_degree_hash(related, issuer.clone())?;
+            let _ = write!(path, "_:{}<{hash}>", issuer.issue(related));
+            issuer = result;
+            if worse(&path) {
+                return Ok(None);
+            }
+        }
+        Ok(Some((path, issuer)))
+    }
+}
+
+/// The labels of the blank nodes in the subject and object of the triple.
+fn blank_nodes(triple: &OwnedTriple) -> (Option<&str>, Option<&str>) {
+    let subject = match &triple.subject {
+        OwnedSubject::BlankNode(id) => Some(id.as_str()),
+        _ => None,
+    };
+    let object = match &triple.object {
+        OwnedObject::BlankNode(id) => Some(id.as_str()),
+        _ => None,
+    };
+    (subject, object)
+}
+
+/// Rearrange `order` into the next greater permutation, returning false after the greatest.
+fn next_permutation(order: &mut [usize]) -> bool {
+    let Some(i) = order.windows(2).rposition(|pair| pair[0] < pair[1]) else {
+        return false;
+    };
+    let j = order
+        .iter()
+        .rposition(|n| *n > order[i])
+        .expect("a greater element follows");
+    order.swap(i, j);
+    order[i + 1..].reverse();
+    true
+}
+
+fn sha256(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+/// The triple as a line of canonical N-Quads, with the blank nodes labeled by `label`.
+fn nquad<'a>(triple: &'a OwnedTriple, label: &dyn Fn(&'a str) -> &'a str) -> String {
+    let subject = match &triple.subject {
+        OwnedSubject::NamedNode(iri) => format!("<{iri}>"),
+        OwnedSubject::BlankNode(id) => format!("_:{}", label(id)),
+        OwnedSubject::Triple(_) => String::new(),
+    };
+    let object = match &triple.object {
+        OwnedObject::NamedNode(iri) => format!("<{iri}>"),
+        OwnedObject::BlankNode(id) => format!("_:{}", label(id)),
+        OwnedObject::Literal(literal) => quote_literal(literal),
+        OwnedObject::Triple(_) => String::new(),
+    };
+    format!("{subject} <{}> {object} .\n", triple.predicate)
+}
+
+/// The literal in canonical N-Quads form, which escapes only the characters it has to.
+fn quote_literal(literal: &OwnedLiteral) -> String {
+    let value = literal.lexical_form();
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\u{8}' => quoted.push_str("\\b"),
+            '\t' => quoted.push_str("\\t"),
+            '\n' => quoted.push_str("\\n"),
+            '\u{c}' => quoted.push_str("\\f"),
+            '\r' => quoted.push_str("\\r"),
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\u{0}'..='\u{1f}' | '\u{7f}' => {
+                let _ = write!(quoted, "\\u{:04X}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    match literal {
+        OwnedLiteral::LanguageTaggedString { language, .. } => format!("{quoted}@{language}"),
+        OwnedLiteral::Typed { datatype, .. } if datatype != XSD_STRING => {
+            format!("{quoted}^^<{datatype}>")
+        }
+        _ => quoted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MAX_PERMUTATIONS;
+    use crate::{Graph, OwnedObject, OwnedSubject, OwnedTriple};
+
+    #[test]
+    fn test_canonicalize() {
+        // Cases in the layout of the W3C rdf-canon test suite: the examples of the RDFC-1.0
+        // specification, with unique and shared first degree hashes, and graphs whose blank
+        // nodes take more and more permutations to tell apart.
+        let fixture = |name: &str| {
+            std::fs::read_to_string(format!("tests/fixtures/rdf-canon/{name}")).unwrap()
+        };
+        for name in [
+            "no-blank-nodes",
+            "unique-hashes",
+            "shared-hashes",
+            "chain",
+            "two-cycles",
+            "clique",
+        ] {
+            let graph = Graph::parse_turtle(&fixture(&format!("{name}-in.nq"))).unwrap();
+            let expected = fixture(&format!("{name}-rdfc10.nq"));
+            assert_eq!(graph.canonicalize().unwrap(), expected, "{name}");
+            // Other labels and another order give the same output.
+            let relabeled: Graph = graph
+                .triples()
+                .iter()
+                .rev()
+                .map(|triple| {
+                    let relabel = |label: &str| label.replace('e', "x");
+                    OwnedTriple {
+                        subject: match &triple.subject {
+                            OwnedSubject::BlankNode(id) => OwnedSubject::BlankNode(relabel(id)),
+                            subject => subject.clone(),
+                        },
+                        predicate: triple.predicate.clone(),
+                        object: match &triple.object {
+                            OwnedObject::BlankNode(id) => OwnedObject::BlankNode(relabel(id)),
+                            object => object.clone(),
+                        },
+                    }
+                })
+                .collect();
+            assert_eq!(relabeled.canonicalize().unwrap(), expected, "{name}");
+        }
+
+        // A clique of seven blank nodes takes more permutations than are tried.
+        let poison = Graph::parse_turtle(&fixture("poison-clique-in.nq")).unwrap();
+        let error = poison.canonicalize().unwrap_err().to_string();
+        assert!(
+            error.contains(&format!("{MAX_PERMUTATIONS} permutations")),
+            "{error}"
+        );
+
+        // Literals only escape what they have to, and `xsd:string` is implied.
+        let graph = Graph::parse_turtle(
+            r#"<https://example.org/p> <https://example.org/ns#name> "Hôtel \"de\"\tMusique" ;
+                <https://example.org/ns#title> "Events"@en , "Events"^^<http://www.w3.org/2001/XMLSchema#string> ."#,
+        )
+        .unwrap();
+        assert_eq!(
+            graph.canonicalize().unwrap(),
+            "<https://example.org/p> <https://example.org/ns#name> \"Hôtel \\\"de\\\"\\tMusique\" .\n\
+             <https://example.org/p> <https://example.org/ns#title> \"Events\" .\n\
+             <https://example.org/p> <https://example.org/ns#title> \"Events\"@en .\n"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! A [`SerializerConfig`] prepared for serializing: its property groups included, its prefixed
+//! names expanded and its IRIs checked once, rather than for every field of every value. The
+//! config itself is borrowed, only the IRIs that had to be expanded are owned.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::iri::check_iri;
+use crate::ns::RDF_TYPE;
+use crate::structure::{try_expand_prefixed_name, PropertyConfig, SerializerConfig, SubjectConfig};
+use crate::xsd::XsdType;
+
+/// The config a [`Serializer`](crate::Serializer) works with, built once per serialization.
+#[derive(Debug)]
+pub(crate) struct CompiledConfig<'c> {
+    /// The options of the config. Its IRIs are taken from the fields below, which are expanded.
+    pub(crate) config: &'c SerializerConfig,
+    /// The struct whose subject config applies to a map serialized at the top level.
+    pub(crate) root_subject: Option<&'c str>,
+    type_predicate: Cow<'c, str>,
+    reification_predicate: Option<Cow<'c, str>>,
+    skolemize: Option<Cow<'c, str>>,
+    individuals: HashMap<&'c str, Cow<'c, str>>,
+    subjects: HashMap<&'c str, CompiledSubject<'c>>,
+}
+
+impl<'c> CompiledConfig<'c> {
+    /// # Errors
+    ///
+    /// Fails if the config includes an unknown property group, uses an unknown prefix or has a
+    /// property or type predicate that is not an absolute IRI.
+    pub(crate) fn new(config: &'c SerializerConfig) -> Result<CompiledConfig<'c>> {
+        let expand = |value: &'c str| try_expand_prefixed_name(&config.namespaces, value);
+        let type_predicate = match &config.type_predicate {
+            Some(predicate) => expand(predicate)?,
+            None => Cow::Borrowed(RDF_TYPE),
+        };
+        check_iri(&type_predicate, || "type predicate".to_string())?;
+        let default_identifier_prefix = config
+            .default_identifier_prefix
+            .as_deref()
+            .map(expand)
+            .transpose()?;
+        let subjects = config
+            .subjects
+            .iter()
+            .map(|(name, subject)| {
+                let subject =
+                    CompiledSubject::new(config, name, subject, &default_identifier_prefix)?;
+                Ok((name.as_str(), subject))
+            })
+            .collect::<Result<_>>()?;
+        let individuals = config
+            .individuals
+            .iter()
+            .map(|(name, iri)| Ok((name.as_str(), expand(iri)?)))
+            .collect::<Result<_>>()?;
+        Ok(CompiledConfig {
+            config,
+            root_subject: config.root_subject.as_deref(),
+            type_predicate,
+            reification_predicate: config
+                .reification_predicate
+                .as_deref()
+                .map(expand)
+                .transpose()?,
+            skolemize: config.skolemize.as_deref().map(expand).transpose()?,
+            individuals,
+            subjects,
+        })
+    }
+
+    /// The subject config of the struct `name`.
+    pub(crate) fn subject(&self, name: &str) -> Option<&CompiledSubject<'c>> {
+        self.subjects.get(name)
+    }
+
+    /// The predicate typing subjects.
+    pub(crate) fn type_predicate(&self) -> &str {
+        &self.type_predicate
+    }
+
+    /// The predicate linking a subject to the statements of its reified properties.
+    pub(crate) fn reification_predicate(&self) -> Option<&str> {
+        self.reification_predicate.as_deref()
+    }
+
+    /// The base of the skolem IRIs standing in for blank nodes.
+    pub(crate) fn skolemize(&self) -> Option<&str> {
+        self.skolemize.as_deref()
+    }
+
+    /// The IRI of the individual represented by the unit struct `name`.
+    pub(crate) fn individual(&self, name: &str) -> Option<&str> {
+        self.individuals.get(name).map(|iri| &**iri)
+    }
+}
+
+/// What a field of a struct is mapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Field {
+    Identifier,
+    Type,
+    /// The index of its property in the `properties` of the compiled subject.
+    Property(usize),
+}
+
+#[derive(Debug)]
+pub(crate) struct CompiledSubject<'c> {
+    pub(crate) config: &'c SubjectConfig,
+    pub(crate) rdf_type: Cow<'c, str>,
+    /// Its own or the `default_identifier_prefix` of the config.
+    pub(crate) identifier_prefix: Cow<'c, str>,
+    /// Its own properties followed by those of the property groups it includes.
+    pub(crate) properties: Vec<CompiledProperty<'c>>,
+    /// The mapped fields by name, so that a field is looked up once rather than compared with
+    /// the identifier field, the type field and every property.
+    fields: HashMap<&'c str, Field>,
+}
+
+#[derive(Debug)]
+pub(crate) struct CompiledProperty<'c> {
+    pub(crate) config: &'c PropertyConfig,
+    pub(crate) rdf_property: Cow<'c, str>,
+    /// The datatype IRI, owned by the literals it is given to unless it is an XSD one.
+    pub(crate) datatype: Option<Cow<'static, str>>,
+}
+
+impl<'c> CompiledSubject<'c> {
+    fn new(
+        config: &'c SerializerConfig,
+        name: &str,
+        subject: &'c SubjectConfig,
+        default_identifier_prefix: &Option<Cow<'c, str>>,
+    ) -> Result<CompiledSubject<'c>> {
+        let namespaces = &config.namespaces;
+        let mut properties = Vec::with_capacity(subject.properties.len());
+        for property in &subject.properties {
+            properties.push(CompiledProperty::new(config, property)?);
+        }
+        for group in &subject.include_properties {
+            let included = config.property_groups.get(group).ok_or_else(|| {
+                Error::Message(format!(
+                    "validate -> unknown property group {group} included by subject: {name}"
+                ))
+            })?;
+            for property in included {
+                let field = property.struct_field.as_str();
+                if subject.maps_field(field)
+                    || properties.iter().any(|p| p.config.struct_field == field)
+                {
+                    return Err(Error::Message(format!(
+                        "validate -> field {field} of subject {name} is also in property group: \
+                         {group}"
+                    )));
+                }
+                properties.push(CompiledProperty::new(config, property)?);
+            }
+        }
+
+        let mut fields = HashMap::with_capacity(properties.len() + 2);
+        for (index, property) in properties.iter().enumerate() {
+            fields
+                .entry(property.config.struct_field.as_str())
+                .or_insert(Field::Property(index));
+        }
+        // The identifier and type fields are not serialized as properties, even if they have
+        // one.
+        if let Some(type_field) = &subject.type_field {
+            fields.insert(type_field.as_str(), Field::Type);
+        }
+        if !subject.identifier_field.is_empty() {
+            fields.insert(subject.identifier_field.as_str(), Field::Identifier);
+        }
+
+        let identifier_prefix = match default_identifier_prefix {
+            Some(prefix)
+                if !subject.identifier_field.is_empty() && subject.identifier_prefix.is_empty() =>
+            {
+                prefix.clone()
+            }
+            _ => try_expand_prefixed_name(namespaces, &subject.identifier_prefix)?,
+        };
+        Ok(CompiledSubject {
+            config: subject,
+            rdf_type: try_expand_prefixed_name(namespaces, &subject.rdf_type)?,
+            identifier_prefix,
+            properties,
+            fields,
+        })
+    }
+
+    pub(crate) fn field(&self, name: &str) -> Option<Field> {
+        self.fields.get(name).copied()
+    }
+}
+
+impl<'c> CompiledProperty<'c> {
+    fn new(config: &'c SerializerConfig, property: &'c PropertyConfig) -> Result<Self> {
+        let rdf_property = try_expand_prefixed_name(&config.namespaces, &property.rdf_property)?;
+        check_iri(&rdf_property, || {
+            format!("rdf_property of field {}", property.struct_field)
+        })?;
+        let datatype = match &property.datatype {
+            Some(XsdType::Custom(iri)) => Some(Cow::Owned(
+                try_expand_prefixed_name(&config.namespaces, iri)?.into_owned(),
+            )),
+            datatype => datatype.as_ref().map(XsdType::to_iri),
+        };
+        Ok(CompiledProperty {
+            config: property,
+            rdf_property,
+            datatype,
+        })
+    }
+}
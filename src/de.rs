@@ -2,273 +2,6064 @@
 
 //! Deserialize RDF data to a Rust data structure.
 
-use serde::de::{self, Visitor};
+use std::borrow::Cow;
+use std::cell::{OnceCell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufReader};
+use std::marker::PhantomData;
 
-use crate::{Error, Result};
+use rio_api::model::{GraphName, Triple};
+use rio_api::parser::{ParseError, QuadsParser, TriplesParser};
+use rio_turtle::{NQuadsParser, NTriplesParser, TriGParser, TurtleError, TurtleParser};
+#[cfg(feature = "xml")]
+use rio_xml::RdfXmlParser;
+use serde::de::value::{MapDeserializer, SeqDeserializer, StrDeserializer, StringDeserializer};
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
 
-/// A structure that deserializes RDF into Rust values.
-pub struct Deserializer {
-    statements: String,
+use crate::binary::{decode_base64, decode_hex};
+use crate::duration::XsdDuration;
+use crate::format::Format;
+#[cfg(feature = "xml")]
+use crate::ns::RDF_XML_LITERAL;
+use crate::ns::{RDF_FIRST, RDF_LANG_STRING, RDF_NIL, RDF_REST, RDF_TYPE, XSD};
+use crate::structure::{DeserializerConfig, PropertyConfig, SubjectConfig};
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+use crate::{value, with, Error, ParseIssue, Result};
+
+/// The local names of the XSD datatypes derived from `xsd:integer`.
+const XSD_INTEGER_TYPES: &[&str] = &[
+    "integer",
+    "long",
+    "int",
+    "short",
+    "byte",
+    "nonNegativeInteger",
+    "positiveInteger",
+    "nonPositiveInteger",
+    "negativeInteger",
+    "unsignedLong",
+    "unsignedInt",
+    "unsignedShort",
+    "unsignedByte",
+];
+
+/// The whitespace around the lexical forms of XSD datatypes other than strings.
+const XSD_WHITESPACE: [char; 4] = [' ', '\t', '\n', '\r'];
+
+/// The struct field populated with the IRI of the subject.
+const ID_FIELD: &str = "id";
+
+/// The key holding the class of a subject read as a map, unless configured otherwise.
+const TYPE_TAG: &str = "type";
+
+/// How deeply structs may be nested unless configured otherwise.
+const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// The parsed statements, indexed by subject.
+struct Graph<'de> {
+    /// The subjects in the order they first appear in the input.
+    subjects: Vec<OwnedSubject>,
+    statements: HashMap<OwnedSubject, Vec<(String, OwnedObject)>>,
+    /// The predicate / subject pairs of the statements referring to each subject, indexed when
+    /// an inverse property is first read.
+    referrers: OnceCell<HashMap<OwnedSubject, Vec<(String, OwnedObject)>>>,
+    /// The IRI of the named graph the statements were selected from, if any.
+    name: Option<String>,
+    /// The subjects of the structs being deserialized, from the root down.
+    path: RefCell<Vec<OwnedObject>>,
+    /// The text the statements were parsed from, to borrow strings from.
+    source: Option<&'de str>,
+    /// The terms that appear verbatim in the source, collected when a string is first
+    /// borrowed.
+    verbatim: OnceCell<HashSet<&'de str>>,
 }
 
-impl Deserializer {
-    /// Creates a RDF deserializer from a `&str`.
-    ///
-    /// # Errors
-    ///
-    /// An [`Error`][Error] is returned when the input is not valid HCL.
-    pub fn from_raw_str(input: &str) -> Result<Self> {
-        // FIXME: parse the input into a list of statements
-        // let statements = parser::parse(input)?;
-        Ok(Deserializer {
-            statements: input.to_string(),
+impl<'de> Graph<'de> {
+    fn new(triples: impl IntoIterator<Item = OwnedTriple>) -> Self {
+        let mut subjects = Vec::new();
+        let mut statements: HashMap<_, Vec<_>> = HashMap::new();
+        for triple in triples {
+            if !statements.contains_key(&triple.subject) {
+                subjects.push(triple.subject.clone());
+            }
+            statements
+                .entry(triple.subject)
+                .or_default()
+                .push((triple.predicate, triple.object));
+        }
+        Graph {
+            subjects,
+            statements,
+            referrers: OnceCell::new(),
+            name: None,
+            path: RefCell::new(Vec::new()),
+            source: None,
+            verbatim: OnceCell::new(),
+        }
+    }
+
+    /// The predicate / object pairs of the given subject.
+    fn statements(&self, subject: &OwnedSubject) -> &[(String, OwnedObject)] {
+        self.statements
+            .get(subject)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The predicates and subjects of the statements whose object is the given subject.
+    fn referrers(&self, subject: &OwnedSubject) -> &[(String, OwnedObject)] {
+        let referrers = self.referrers.get_or_init(|| {
+            let mut referrers: HashMap<_, Vec<_>> = HashMap::new();
+            // In document order, as the values of a field are.
+            for referrer in &self.subjects {
+                for (predicate, object) in self.statements(referrer) {
+                    if let Some(referenced) = as_subject(object) {
+                        referrers
+                            .entry(referenced)
+                            .or_default()
+                            .push((predicate.clone(), as_object(referrer)));
+                    }
+                }
+            }
+            referrers
+        });
+        referrers
+            .get(subject)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The elements of the [RDF collection](https://www.w3.org/TR/rdf11-mt/#rdf-collections)
+    /// starting at `head`, or `None` if `head` is neither `rdf:nil` nor has an `rdf:first`.
+    fn list<'a>(&'a self, head: &'a OwnedObject) -> Result<Option<Vec<&'a OwnedObject>>> {
+        let first = |node: &OwnedSubject| {
+            self.statements(node)
+                .iter()
+                .find(|(predicate, _)| predicate == RDF_FIRST)
+                .map(|(_, object)| object)
+        };
+        let error = |reason: &str| {
+            Error::Message(format!(
+                "deserialize_seq -> malformed rdf:List {}: {}",
+                label(head),
+                reason
+            ))
+        };
+        match head {
+            OwnedObject::NamedNode(iri) if iri == RDF_NIL => return Ok(Some(Vec::new())),
+            _ => match as_subject(head) {
+                Some(node) if first(&node).is_some() => {}
+                _ => return Ok(None),
+            },
+        }
+
+        let mut elements = Vec::new();
+        let mut visited = HashSet::new();
+        let mut node = head;
+        loop {
+            if matches!(node, OwnedObject::NamedNode(iri) if iri == RDF_NIL) {
+                return Ok(Some(elements));
+            }
+            let subject = as_subject(node)
+                .ok_or_else(|| error(&format!("rdf:rest is a literal: {}", label(node))))?;
+            if !visited.insert(subject.clone()) {
+                return Err(error(&format!("cycle at {}", label(node))));
+            }
+            let element = first(&subject)
+                .ok_or_else(|| error(&format!("missing rdf:first at {}", label(node))))?;
+            elements.push(element);
+            node = self
+                .statements(&subject)
+                .iter()
+                .find(|(predicate, _)| predicate == RDF_REST)
+                .map(|(_, object)| object)
+                .ok_or_else(|| error(&format!("missing rdf:rest at {}", label(node))))?;
+        }
+    }
+
+    /// The subject the document is about: the first one that is not referenced by another
+    /// subject, or simply the first one if all of them are. The subject of an inverse property
+    /// of the config is the one referenced.
+    fn root(&self, config: Option<&DeserializerConfig>) -> Result<OwnedObject> {
+        let inverse: HashSet<_> = config
+            .into_iter()
+            .flat_map(|config| {
+                config
+                    .subjects
+                    .values()
+                    .flat_map(|subject| &subject.properties)
+                    .filter(|property| property.inverse)
+                    .map(move |property| config.expand_prefixed_name(&property.rdf_property))
+            })
+            .collect();
+        let mut referenced = HashSet::new();
+        for (subject, statements) in &self.statements {
+            for (predicate, object) in statements {
+                if inverse.contains(predicate.as_str()) {
+                    referenced.insert(subject.clone());
+                } else if let Some(object) = as_subject(object) {
+                    referenced.insert(object);
+                }
+            }
+        }
+        let root = self
+            .subjects
+            .iter()
+            .find(|subject| !referenced.contains(subject))
+            .or_else(|| self.subjects.first())
+            .ok_or_else(|| Error::new("deserialize -> no subject found in the input"))?;
+        Ok(as_object(root))
+    }
+
+    /// The given term borrowed from the source, if it appears there verbatim, i.e. it needed
+    /// no unescaping. Any occurrence will do, as they are all equal.
+    fn borrow(&self, value: &str) -> Option<&'de str> {
+        let source = self.source?;
+        let verbatim = self.verbatim.get_or_init(|| verbatim_terms(source));
+        verbatim.get(value).copied()
+    }
+
+    /// Descend into the struct read from `object`, which must not be one of the structs it is
+    /// nested in.
+    fn enter(&self, object: &OwnedObject, max_depth: usize) -> Result<()> {
+        let mut path = self.path.borrow_mut();
+        if path.contains(object) {
+            let mut path: Vec<_> = path.iter().map(label).collect();
+            path.push(label(object));
+            return Err(Error::CyclicReference { path });
+        }
+        if path.len() >= max_depth {
+            return Err(Error::Message(format!(
+                "deserialize_struct -> structs nested deeper than {} at {}",
+                max_depth,
+                label(object)
+            )));
+        }
+        path.push(object.clone());
+        Ok(())
+    }
+
+    fn leave(&self) {
+        self.path.borrow_mut().pop();
+    }
+
+    /// The subjects typed with the given class by the given predicate, in the order they first
+    /// appear in the input.
+    fn instances<'a>(
+        &'a self,
+        class: &'a str,
+        type_predicate: &'a str,
+    ) -> impl Iterator<Item = &'a OwnedSubject> + use<'a, 'de> {
+        self.subjects.iter().filter(move |subject| {
+            self.statements(subject).iter().any(|(predicate, object)| {
+                predicate == type_predicate
+                    && matches!(object, OwnedObject::NamedNode(iri) if iri == class)
+            })
         })
     }
 }
 
-/// Deserialize an instance of type `T` from a string of RDF text.
-///
-/// By default, the deserialization will follow the [Turtle Specification][turtle-spec].
-///
-/// [turtle-spec]: https://www.w3.org/TR/turtle/
-///
-/// # Errors
+/// The spans of the terms of a Turtle, TriG, N-Triples or N-Quads document that contain no
+/// escape sequences: the IRIs and the lexical forms of string literals without their
+/// delimiters, and the other tokens, e.g. numbers, prefixed names and their local names.
 ///
-/// This functions fails with an error if the data does not match the structure of `T`.
-pub fn from_str<'de, T>(s: &'de str) -> Result<T>
-where
-    T: de::Deserialize<'de>,
-{
-    let deserializer = Deserializer::from_raw_str(s)?;
-    T::deserialize(deserializer)
+/// The document is scanned once, rather than searched for every borrowed string. The spans
+/// are only compared with the terms of the parsed statements, so an input the scan gets wrong
+/// costs borrowing, not correctness.
+fn verbatim_terms(source: &str) -> HashSet<&str> {
+    let bytes = source.as_bytes();
+    let find = |from: usize, byte: u8| {
+        bytes[from.min(bytes.len())..]
+            .iter()
+            .position(|b| *b == byte)
+            .map_or(bytes.len(), |offset| from + offset)
+    };
+    let mut terms = HashSet::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'#' => i = find(i, b'\n'),
+            // The start of a quoted triple.
+            b'<' if bytes.get(i + 1) == Some(&b'<') => i += 2,
+            b'<' => {
+                let end = find(i + 1, b'>');
+                let iri = &source[i + 1..end];
+                if !iri.contains('\\') {
+                    terms.insert(iri);
+                }
+                i = end + 1;
+            }
+            quote @ (b'"' | b'\'') => {
+                let delimiter = match bytes[i..].starts_with(&[quote; 3]) {
+                    true => 3,
+                    false => 1,
+                };
+                let start = i + delimiter;
+                let mut end = start;
+                let mut escaped = false;
+                while end < bytes.len() {
+                    match bytes[end] {
+                        b'\\' => {
+                            escaped = true;
+                            end += 2;
+                        }
+                        b if b == quote && bytes[end..].starts_with(&[quote; 3][..delimiter]) => {
+                            break
+                        }
+                        _ => end += 1,
+                    }
+                }
+                if !escaped {
+                    terms.insert(&source[start..end]);
+                }
+                i = end + delimiter;
+            }
+            b if b.is_ascii_whitespace() || b"()[];,>".contains(&b) => i += 1,
+            _ => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|b| b.is_ascii_whitespace() || b"()[];,<>\"'#".contains(b))
+                    .map_or(bytes.len(), |offset| i + offset);
+                let token = &source[i..end];
+                // A statement may end right after a token, e.g. `ex:size 42.`.
+                for token in [token, token.strip_suffix('.').unwrap_or(token)] {
+                    terms.insert(token);
+                    if let Some((_, local)) = token.split_once(':') {
+                        terms.insert(local);
+                    }
+                }
+                i = end;
+            }
+        }
+    }
+    terms
 }
 
-impl<'de> de::Deserializer<'de> for Deserializer {
-    type Error = Error;
-
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+/// The object referring to a subject.
+fn as_object(subject: &OwnedSubject) -> OwnedObject {
+    match subject {
+        OwnedSubject::NamedNode(iri) => OwnedObject::NamedNode(iri.clone()),
+        OwnedSubject::BlankNode(id) => OwnedObject::BlankNode(id.clone()),
+        OwnedSubject::Triple(triple) => OwnedObject::Triple(triple.clone()),
     }
+}
 
-    fn deserialize_bool<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+/// The subject an object refers to, if it can be the subject of other triples.
+fn as_subject(object: &OwnedObject) -> Option<OwnedSubject> {
+    match object {
+        OwnedObject::NamedNode(iri) => Some(OwnedSubject::NamedNode(iri.clone())),
+        OwnedObject::BlankNode(id) => Some(OwnedSubject::BlankNode(id.clone())),
+        OwnedObject::Triple(triple) => Some(OwnedSubject::Triple(triple.clone())),
+        OwnedObject::Literal(_) => None,
     }
+}
 
-    fn deserialize_i8<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
-    }
+/// The predicate typing subjects, `rdf:type` unless configured otherwise.
+fn type_predicate(config: Option<&DeserializerConfig>) -> Cow<'_, str> {
+    config.map_or(Cow::Borrowed(RDF_TYPE), DeserializerConfig::type_predicate)
+}
 
-    fn deserialize_i16<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+/// How a node is written in error messages: `<iri>` or `_:label`.
+fn label(object: &OwnedObject) -> String {
+    match object {
+        OwnedObject::NamedNode(iri) => format!("<{iri}>"),
+        OwnedObject::BlankNode(id) => format!("_:{id}"),
+        OwnedObject::Literal(literal) => format!("{literal:?}"),
+        OwnedObject::Triple(triple) => format!("{triple:?}"),
     }
+}
 
-    fn deserialize_i32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+/// Whether a predicate populates a field. Without a mapping, the local name of the predicate
+/// is compared with the field name, ignoring case and a leading `has`, so that `hasName`
+/// populates `name` and `hasDataset` populates `datasets`.
+fn guess_field(predicate: &str, field: &str) -> bool {
+    let local = local_name(predicate);
+    local.eq_ignore_ascii_case(field)
+        || field
+            .strip_suffix('s')
+            .is_some_and(|singular| local.eq_ignore_ascii_case(singular))
+}
+
+/// The local name of a predicate without a leading `has`, e.g. `Name` for `dsp:hasName`.
+fn local_name(predicate: &str) -> &str {
+    let local = predicate.rsplit(['#', '/']).next().unwrap_or(predicate);
+    local
+        .strip_prefix("has")
+        .filter(|rest| rest.starts_with(char::is_uppercase))
+        .unwrap_or(local)
+}
+
+/// Whether a value is in the lexical space of `xsd:decimal`, e.g. `-1.5`, `+.5` or `2.`.
+fn is_decimal(value: &str) -> bool {
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let (integral, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    !(integral.is_empty() && fraction.is_empty())
+        && integral
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+}
+
+/// A structure that deserializes RDF into Rust values. It borrows the config and, to hand out
+/// `&str` fields, the input text.
+pub struct Deserializer<'c, 'de> {
+    graph: Graph<'de>,
+    config: Option<&'c DeserializerConfig>,
+    /// The subject to start at instead of the root of the document.
+    subject: Option<OwnedObject>,
+}
+
+impl<'de> Deserializer<'_, 'de> {
+    /// Creates a RDF deserializer from a `&str`.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when the input is not valid Turtle.
+    pub fn from_raw_str(input: &'de str) -> Result<Self> {
+        Deserializer::from_parser(TurtleParser::new(input.as_bytes(), None))
+            .map(|deserializer| deserializer.with_source(input))
     }
 
-    fn deserialize_i64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from a `&str` of Turtle, skipping the statements the parser
+    /// rejects. Each error is reported as a [`ParseIssue`] and parsing resumes on the next line,
+    /// so that the statements on the other lines are read as usual.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when the parser reports an error without a line to skip,
+    /// e.g. a statement that is not terminated at the end of the input.
+    pub fn from_str_lenient(input: &'de str) -> Result<(Self, Vec<ParseIssue>)> {
+        let starts: Vec<usize> = std::iter::once(0)
+            .chain(input.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        let line = |index: usize| {
+            let end = starts.get(index + 1).map_or(input.len(), |next| next - 1);
+            starts.get(index).map(|start| &input[*start..end])
+        };
+        let mut triples = Vec::new();
+        let mut issues = Vec::new();
+        let mut prefixes = HashMap::new();
+        // The parser is restarted on the line after each one it rejects, with the prefixes
+        // declared so far on a line of their own before it.
+        let mut restart = 0;
+        while restart < starts.len() {
+            let header: String = prefixes
+                .iter()
+                .map(|(prefix, namespace)| format!("@prefix {prefix}: <{namespace}> . "))
+                .chain(std::iter::once("\n".to_string()))
+                .collect();
+            let rest = &input.as_bytes()[starts[restart]..];
+            let mut parser = TurtleParser::new(io::Read::chain(header.as_bytes(), rest), None);
+            // The triples of a statement are only kept once all of it is parsed.
+            let mut statement = Vec::new();
+            let err = loop {
+                if parser.is_end() {
+                    break None;
+                }
+                let step =
+                    parser.parse_step(&mut |triple| -> std::result::Result<(), TurtleError> {
+                        statement.push(OwnedTriple::from(&triple));
+                        Ok(())
+                    });
+                match step {
+                    Ok(()) => triples.append(&mut statement),
+                    Err(err) => break Some(err),
+                }
+            };
+            let Some(err) = err else {
+                break;
+            };
+            // The parser counts the lines from 1, with the header as the first.
+            let position = err.textual_position();
+            let rejected = position
+                .and_then(|position| usize::try_from(position.line_number()).ok())
+                .filter(|line| *line > 1)
+                .map(|line| restart + line - 2)
+                .filter(|rejected| line(*rejected).is_some_and(|line| !line.trim().is_empty()));
+            let (Some(position), Some(rejected)) = (position, rejected) else {
+                return Err(Error::from(err));
+            };
+            // The line is reported from the start of the input, separately from the message.
+            let message = err.to_string();
+            let message = message
+                .strip_suffix(&format!(
+                    " on line {} at position {}",
+                    position.line_number(),
+                    position.byte_number()
+                ))
+                .map_or(message.clone(), str::to_string);
+            issues.push(ParseIssue {
+                line: rejected as u64 + 1,
+                message,
+            });
+            prefixes = parser.prefixes().clone();
+            restart = rejected + 1;
+        }
+        Ok((
+            Deserializer::from_triples(triples).with_source(input),
+            issues,
+        ))
     }
 
-    fn deserialize_u8<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from an `io::Read`, which is parsed incrementally.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when reading fails or the input is not valid Turtle.
+    pub fn from_reader<R: io::Read>(reader: R) -> Result<Self> {
+        Deserializer::from_io(reader, |input| {
+            Deserializer::from_parser(TurtleParser::new(input, None))
+        })
     }
 
-    fn deserialize_u16<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from a `&str` of N-Triples.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when the input is not valid N-Triples.
+    pub fn from_ntriples_str(input: &'de str) -> Result<Self> {
+        Deserializer::from_parser(NTriplesParser::new(input.as_bytes()))
+            .map(|deserializer| deserializer.with_source(input))
     }
 
-    fn deserialize_u32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from an `io::Read` of N-Triples, which is parsed
+    /// incrementally.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when reading fails or the input is not valid N-Triples.
+    pub fn from_ntriples_reader<R: io::Read>(reader: R) -> Result<Self> {
+        Deserializer::from_io(reader, |input| {
+            Deserializer::from_parser(NTriplesParser::new(input))
+        })
     }
 
-    fn deserialize_u64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from a `&str` of TriG. Only the statements of the named graph
+    /// with the given IRI are read, or those of all graphs, including the default graph, when
+    /// it is `None`.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when the input is not valid TriG or the selected graph
+    /// has no statements.
+    pub fn from_trig_str(input: &'de str, graph: Option<&str>) -> Result<Self> {
+        Deserializer::from_quads_parser(TriGParser::new(input.as_bytes(), None), graph)
+            .map(|deserializer| deserializer.with_source(input))
     }
 
-    fn deserialize_f32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from a `&str` of N-Quads. Only the statements of the named
+    /// graph with the given IRI are read, or those of all graphs, including the default graph,
+    /// when it is `None`.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when the input is not valid N-Quads or the selected
+    /// graph has no statements.
+    pub fn from_nquads_str(input: &'de str, graph: Option<&str>) -> Result<Self> {
+        Deserializer::from_quads_parser(NQuadsParser::new(input.as_bytes()), graph)
+            .map(|deserializer| deserializer.with_source(input))
     }
 
-    fn deserialize_f64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from a `&str` of RDF/XML.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when the input is not valid RDF/XML or contains XML
+    /// literals (`rdf:parseType="Literal"`), which are not supported.
+    #[cfg(feature = "xml")]
+    pub fn from_rdfxml_str(input: &str) -> Result<Self> {
+        Deserializer::from_rdfxml(input.as_bytes())
     }
 
-    fn deserialize_char<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from an `io::Read` of RDF/XML, which is parsed incrementally.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when reading fails, the input is not valid RDF/XML or it
+    /// contains XML literals (`rdf:parseType="Literal"`), which are not supported.
+    #[cfg(feature = "xml")]
+    pub fn from_rdfxml_reader<R: io::Read>(reader: R) -> Result<Self> {
+        Deserializer::from_io(reader, |input| Deserializer::from_rdfxml(input))
     }
 
-    fn deserialize_str<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    #[cfg(feature = "xml")]
+    fn from_rdfxml(input: impl io::BufRead) -> Result<Self> {
+        let deserializer = Deserializer::from_parser(RdfXmlParser::new(input, None))?;
+        let xml_literal = deserializer
+            .graph
+            .statements
+            .values()
+            .flatten()
+            .any(|(_, object)| {
+                matches!(
+                    object,
+                    OwnedObject::Literal(OwnedLiteral::Typed { datatype, .. })
+                        if datatype == RDF_XML_LITERAL
+                )
+            });
+        if xml_literal {
+            return Err(Error::new(
+                "deserialize -> XML literals (rdf:parseType=\"Literal\") are not supported",
+            ));
+        }
+        Ok(deserializer)
     }
 
-    fn deserialize_string<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from a `&str` of expanded JSON-LD.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when the input is not expanded JSON-LD. Compacted
+    /// documents with a `@context` are not supported.
+    #[cfg(feature = "jsonld")]
+    pub fn from_jsonld_str(input: &str) -> Result<Self> {
+        Ok(Deserializer::from_triples(crate::jsonld::triples(input)?))
     }
 
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from a `&str` in the given format. Of the datasets of TriG
+    /// and N-Quads, all graphs are read.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error`][Error] is returned when the input is not valid in the format, or an
+    /// [`Error::UnsupportedFormat`] when the feature of the format is not enabled.
+    pub fn from_str_with_format(input: &'de str, format: Format) -> Result<Self> {
+        match format {
+            Format::Turtle => Deserializer::from_raw_str(input),
+            Format::NTriples => Deserializer::from_ntriples_str(input),
+            Format::NQuads => Deserializer::from_nquads_str(input, None),
+            Format::TriG => Deserializer::from_trig_str(input, None),
+            #[cfg(feature = "xml")]
+            Format::RdfXml => Deserializer::from_rdfxml_str(input),
+            #[cfg(feature = "jsonld")]
+            Format::JsonLd => Deserializer::from_jsonld_str(input),
+            #[allow(unreachable_patterns)]
+            format => Err(Error::UnsupportedFormat {
+                format,
+                operation: "reading",
+                feature: Some(if format == Format::RdfXml {
+                    "xml"
+                } else {
+                    "jsonld"
+                }),
+            }),
+        }
     }
 
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Parse the input of a reader, keeping its first IO error, which the parsers only report
+    /// as text.
+    fn from_io<R: io::Read>(
+        reader: R,
+        parse: impl FnOnce(BufReader<&mut Reader<R>>) -> Result<Self>,
+    ) -> Result<Self> {
+        let mut reader = Reader {
+            inner: reader,
+            error: None,
+        };
+        let result = parse(BufReader::new(&mut reader));
+        match reader.error {
+            Some(err) => Err(Error::Io(err)),
+            None => result,
+        }
     }
 
-    fn deserialize_option<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn from_parser<P: TriplesParser>(mut parser: P) -> Result<Self>
     where
-        V: Visitor<'de>,
+        Error: From<P::Error>,
     {
-        todo!()
+        let mut triples = Vec::new();
+        parser
+            .parse_all(&mut |triple| -> std::result::Result<(), P::Error> {
+                triples.push(OwnedTriple::from(&triple));
+                Ok(())
+            })
+            .map_err(Error::from)?;
+        Ok(Deserializer::from_triples(triples))
     }
 
-    fn deserialize_unit<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn from_quads_parser<P: QuadsParser>(mut parser: P, graph: Option<&str>) -> Result<Self>
     where
-        V: Visitor<'de>,
+        Error: From<P::Error>,
     {
-        todo!()
+        let mut triples = Vec::new();
+        parser
+            .parse_all(&mut |quad| -> std::result::Result<(), P::Error> {
+                let selected = match (graph, quad.graph_name) {
+                    (None, _) => true,
+                    (Some(graph), Some(GraphName::NamedNode(name))) => name.iri == graph,
+                    (Some(_), _) => false,
+                };
+                if selected {
+                    triples.push(OwnedTriple::from(&Triple {
+                        subject: quad.subject,
+                        predicate: quad.predicate,
+                        object: quad.object,
+                    }));
+                }
+                Ok(())
+            })
+            .map_err(Error::from)?;
+        let Some(graph) = graph else {
+            return Ok(Deserializer::from_triples(triples));
+        };
+        if triples.is_empty() {
+            return Err(Error::Message(format!(
+                "deserialize -> no statements found in the named graph <{graph}>"
+            )));
+        }
+        let mut deserializer = Deserializer::from_triples(triples);
+        deserializer.graph.name = Some(graph.to_string());
+        Ok(deserializer)
     }
 
-    fn deserialize_unit_struct<V>(
-        self,
-        _name: &'static str,
-        _visitor: V,
-    ) -> Result<V::Value, Self::Error>
+    /// Iterate over the subjects typed with the given class in a stream of N-Triples, without
+    /// building the graph of the whole input.
+    ///
+    /// The triples of each subject must be contiguous, as in sorted dumps: only those of the
+    /// current subject are kept, and it is deserialized when the triples of the next one start.
+    /// Objects referring to other subjects can thus only be read as IRIs, not as structs.
+    pub fn stream_by_type<R, T>(reader: R, class_iri: &str) -> SubjectStream<R, T>
     where
-        V: Visitor<'de>,
+        R: io::BufRead,
+        T: de::DeserializeOwned,
     {
-        todo!()
+        SubjectStream {
+            parser: NTriplesParser::new(reader),
+            class: class_iri.to_string(),
+            buffer: Vec::new(),
+            failed: false,
+            ty: PhantomData,
+        }
     }
 
-    fn deserialize_newtype_struct<V>(
-        self,
-        _name: &'static str,
-        _visitor: V,
-    ) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Borrow the strings that appear verbatim in the input from it.
+    fn with_source(mut self, input: &'de str) -> Self {
+        self.graph.source = Some(input);
+        self
     }
 
-    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Creates a RDF deserializer from triples that are already parsed.
+    pub fn from_triples(triples: impl IntoIterator<Item = OwnedTriple>) -> Self {
+        Deserializer {
+            graph: Graph::new(triples),
+            config: None,
+            subject: None,
+        }
     }
 
-    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Populate struct fields according to the given mapping instead of guessing them from
+    /// the predicate IRIs.
+    pub fn with_config(self, config: &DeserializerConfig) -> Deserializer<'_, 'de> {
+        Deserializer {
+            graph: self.graph,
+            config: Some(config),
+            subject: self.subject,
+        }
     }
 
-    fn deserialize_tuple_struct<V>(
-        self,
-        _name: &'static str,
-        _len: usize,
-        _visitor: V,
-    ) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Deserialize the subject with the given IRI instead of the root of the document.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error::SubjectNotFound`] is returned when the subject has no statements, even if
+    /// it is the object of some.
+    pub fn with_subject(self, iri: &str) -> Result<Self> {
+        let subject = OwnedSubject::NamedNode(iri.to_string());
+        if !self.graph.statements.contains_key(&subject) {
+            return Err(Error::SubjectNotFound(iri.to_string()));
+        }
+        Ok(Deserializer {
+            subject: Some(OwnedObject::NamedNode(iri.to_string())),
+            ..self
+        })
     }
 
-    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+    /// Deserialize the only subject typed with the given class instead of the root of the
+    /// document.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error::SubjectNotFound`] is returned when no subject has the class, and an
+    /// [`Error::AmbiguousSubject`] when several do.
+    pub fn with_type(self, class_iri: &str) -> Result<Self> {
+        let type_predicate = type_predicate(self.config);
+        let instances = self.graph.instances(class_iri, &type_predicate);
+        let subject = match instances.collect::<Vec<_>>()[..] {
+            [] => return Err(Error::SubjectNotFound(class_iri.to_string())),
+            [subject] => as_object(subject),
+            ref candidates => {
+                let candidates = candidates
+                    .iter()
+                    .map(|subject| match subject {
+                        OwnedSubject::NamedNode(iri) => iri.clone(),
+                        _ => label(&as_object(subject)),
+                    })
+                    .collect();
+                return Err(Error::AmbiguousSubject(candidates));
+            }
+        };
+        Ok(Deserializer {
+            subject: Some(subject),
+            ..self
+        })
     }
+}
 
-    fn deserialize_struct<V>(
-        self,
-        _name: &'static str,
-        _fields: &'static [&'static str],
-        _visitor: V,
-    ) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+/// Keeps the first IO error of the wrapped reader, which the parser only reports as text.
+struct Reader<R> {
+    inner: R,
+    error: Option<io::Error>,
+}
+
+impl<R: io::Read> io::Read for Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf).map_err(|err| {
+            let copy = io::Error::new(err.kind(), err.to_string());
+            self.error.get_or_insert(err);
+            copy
+        })
     }
+}
 
-    fn deserialize_enum<V>(
-        self,
-        _name: &'static str,
-        _variants: &'static [&'static str],
-        _visitor: V,
-    ) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+/// An iterator over the instances of a class in a stream of N-Triples, created by
+/// [`Deserializer::stream_by_type`].
+pub struct SubjectStream<R: io::BufRead, T> {
+    parser: NTriplesParser<R>,
+    class: String,
+    /// The triples of the current subject.
+    buffer: Vec<OwnedTriple>,
+    /// Whether parsing failed, after which the stream ends.
+    failed: bool,
+    ty: PhantomData<T>,
+}
+
+impl<R: io::BufRead, T: de::DeserializeOwned> SubjectStream<R, T> {
+    /// Deserialize the subject of the given triples, if it is an instance of the class.
+    fn instance(&self, triples: Vec<OwnedTriple>) -> Option<Result<T>> {
+        let subject = as_object(&triples.first()?.subject);
+        let typed = triples.iter().any(|triple| {
+            triple.predicate == RDF_TYPE
+                && matches!(&triple.object, OwnedObject::NamedNode(iri) if *iri == self.class)
+        });
+        if !typed {
+            return None;
+        }
+        let deserializer = Deserializer {
+            subject: Some(subject),
+            ..Deserializer::from_triples(triples)
+        };
+        Some(T::deserialize(deserializer))
     }
+}
 
-    fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+impl<R: io::BufRead, T: de::DeserializeOwned> Iterator for SubjectStream<R, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.failed {
+            if self.parser.is_end() {
+                let triples = std::mem::take(&mut self.buffer);
+                if triples.is_empty() {
+                    return None;
+                }
+                if let Some(instance) = self.instance(triples) {
+                    return Some(instance);
+                }
+                continue;
+            }
+            // A step parses one line, which holds at most one triple.
+            let mut parsed = None;
+            let step =
+                self.parser
+                    .parse_step(&mut |triple| -> std::result::Result<(), TurtleError> {
+                        parsed = Some(OwnedTriple::from(&triple));
+                        Ok(())
+                    });
+            if let Err(err) = step {
+                self.failed = true;
+                return Some(Err(Error::from(err)));
+            }
+            let Some(triple) = parsed else {
+                continue;
+            };
+            let next_subject = self
+                .buffer
+                .first()
+                .is_some_and(|first| first.subject != triple.subject);
+            if !next_subject {
+                self.buffer.push(triple);
+                continue;
+            }
+            let triples = std::mem::replace(&mut self.buffer, vec![triple]);
+            if let Some(instance) = self.instance(triples) {
+                return Some(instance);
+            }
+        }
+        None
     }
+}
 
-    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        todo!()
+/// Deserialize an instance of type `T` from a string of RDF text.
+///
+/// By default, the deserialization will follow the [Turtle Specification][turtle-spec].
+///
+/// [turtle-spec]: https://www.w3.org/TR/turtle/
+///
+/// Fields of type `&str`, or `Cow<str>` marked with `#[serde(borrow)]`, borrow from `s` instead
+/// of copying, as long as the literal is written without escapes.
+///
+/// # Errors
+///
+/// This functions fails with an error if the data does not match the structure of `T`.
+pub fn from_str<'de, T>(s: &'de str) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_raw_str(s)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from a string in the given format, e.g. one picked with
+/// [`Format::from_media_type`] from the `Content-Type` of a response.
+///
+/// Example:
+/// ```
+/// use serde::Deserialize;
+/// use serde_rdf::{from_str_with_format, Format};
+///
+/// #[derive(Deserialize)]
+/// struct Project {
+///     name: String,
+/// }
+///
+/// let input = "<https://example.org/p> <https://example.org/ns#name> \"Events\" .";
+/// let format = Format::from_extension("nt").unwrap();
+/// let project: Project = from_str_with_format(input, format).unwrap();
+/// assert_eq!(project.name, "Events");
+/// ```
+///
+/// # Errors
+///
+/// This functions fails with an [`Error::UnsupportedFormat`] if the feature of the format is
+/// not enabled, or with an error if the data does not match the structure of `T`.
+pub fn from_str_with_format<'de, T>(s: &'de str, format: Format) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_str_with_format(s, format)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from a string of Turtle that may contain malformed
+/// statements. The statements the parser rejects are skipped and returned as [`ParseIssue`]s
+/// along with the value read from the rest of the input.
+///
+/// # Errors
+///
+/// This functions fails with an error if a parse error cannot be attributed to a line, or if
+/// the data does not match the structure of `T`.
+pub fn from_str_lenient<'de, T>(s: &'de str) -> Result<(T, Vec<ParseIssue>)>
+where
+    T: de::Deserialize<'de>,
+{
+    let (deserializer, issues) = Deserializer::from_str_lenient(s)?;
+    Ok((T::deserialize(deserializer)?, issues))
+}
+
+/// Deserialize an instance of type `T` from the subject with the given IRI in a collection of
+/// triples, e.g. the output of another parser or of [`to_triples`](crate::to_triples).
+///
+/// # Errors
+///
+/// This functions fails with an [`Error::SubjectNotFound`] if the subject has no triples, or
+/// with an error if the data does not match the structure of `T`.
+pub fn from_triples<T>(triples: impl IntoIterator<Item = OwnedTriple>, subject: &str) -> Result<T>
+where
+    T: de::DeserializeOwned,
+{
+    let deserializer = Deserializer::from_triples(triples).with_subject(subject)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from the subject with the given IRI in a string of RDF
+/// text.
+///
+/// # Errors
+///
+/// This functions fails with an [`Error::SubjectNotFound`] if the subject has no statements,
+/// or with an error if the data does not match the structure of `T`.
+pub fn from_str_for_subject<'de, T>(s: &'de str, subject_iri: &str) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_raw_str(s)?.with_subject(subject_iri)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from the only subject typed with the given class, e.g.
+/// `https://ns.dasch.swiss/repository#Project`, in a string of RDF text.
+///
+/// # Errors
+///
+/// This functions fails with an [`Error::SubjectNotFound`] if no subject has the class, with an
+/// [`Error::AmbiguousSubject`] if several do, or with an error if the data does not match the
+/// structure of `T`.
+pub fn from_str_by_type<'de, T>(s: &'de str, class_iri: &str) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_raw_str(s)?.with_type(class_iri)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize every subject typed with the given class in a string of RDF text, in the order
+/// they first appear.
+///
+/// # Errors
+///
+/// This functions fails with an [`Error::Contextual`] naming the subject if one of them does not
+/// match the structure of `T`.
+pub fn from_str_all<'de, T>(s: &'de str, class_iri: &str) -> Result<Vec<T>>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_raw_str(s)?;
+    let graph = &deserializer.graph;
+    graph
+        .instances(class_iri, RDF_TYPE)
+        .map(|subject| {
+            let object = as_object(subject);
+            T::deserialize(Values {
+                graph,
+                config: deserializer.config,
+                field: None,
+                subject: None,
+                predicate: None,
+                objects: vec![&object],
+            })
+            .map_err(|err| err.context("deserializing", || label(&object), None))
+        })
+        .collect()
+}
+
+/// Deserialize an instance of type `T` from a string of RDF text, using the given mapping from
+/// predicates to struct fields.
+///
+/// In lenient mode, the `default_prefixes` of the config are declared before the input, so
+/// that documents using them without a `@prefix` still parse.
+///
+/// # Errors
+///
+/// This functions fails with an error if the data does not match the structure of `T`.
+pub fn from_str_with_config<'de, T>(s: &'de str, config: &DeserializerConfig) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = match config.lenient && !config.default_prefixes.is_empty() {
+        true => {
+            let input = with_default_prefixes(s, config);
+            Deserializer::from_parser(TurtleParser::new(input.as_bytes(), None))?.with_source(s)
+        }
+        false => Deserializer::from_raw_str(s)?,
+    };
+    T::deserialize(deserializer.with_config(config))
+}
+
+/// Prepend `@prefix` declarations for the default prefixes of the config. They share the first
+/// line of the input, to keep the line numbers of parse errors, and the declarations of the
+/// document itself take precedence as they come later.
+fn with_default_prefixes(input: &str, config: &DeserializerConfig) -> String {
+    let mut prefixed = String::new();
+    for (prefix, namespace) in &config.default_prefixes {
+        prefixed.push_str(&format!("@prefix {prefix}: <{namespace}> . "));
+    }
+    prefixed.push_str(input);
+    prefixed
+}
+
+/// Parse the `default_literal` of a field, a Turtle literal that may use the prefixes of the
+/// config.
+fn default_literal(config: &DeserializerConfig, field: &str, literal: &str) -> Result<OwnedObject> {
+    let mut input = String::new();
+    for (prefix, namespace) in &config.namespaces {
+        input.push_str(&format!("@prefix {prefix}: <{namespace}> .\n"));
+    }
+    input.push_str(&format!("<urn:default> <urn:default> {literal} ."));
+    let invalid = || {
+        Error::Message(format!(
+            "deserialize_struct -> invalid default_literal of field {field}: {literal}"
+        ))
+    };
+    let mut objects = Vec::new();
+    TurtleParser::new(input.as_bytes(), None)
+        .parse_all(&mut |triple| -> std::result::Result<(), TurtleError> {
+            objects.push(OwnedTriple::from(&triple).object);
+            Ok(())
+        })
+        .map_err(|_| invalid())?;
+    match objects.pop() {
+        Some(object @ OwnedObject::Literal(_)) if objects.is_empty() => Ok(object),
+        _ => Err(invalid()),
+    }
+}
+
+/// Check the typed literals read into a field against the datatype configured for it.
+/// Simple and language-tagged literals are left to the target type.
+fn check_datatype(
+    config: &DeserializerConfig,
+    property: &PropertyConfig,
+    subject: &OwnedObject,
+    statements: &[&(String, OwnedObject)],
+) -> Result<()> {
+    let Some(datatype) = &property.datatype else {
+        return Ok(());
+    };
+    let expected = config.expand_prefixed_name(datatype.as_iri());
+    for (predicate, object) in statements {
+        if let OwnedObject::Literal(OwnedLiteral::Typed { datatype, .. }) = object {
+            if *datatype != expected {
+                return Err(Error::UnexpectedDatatype {
+                    expected: expected.into_owned(),
+                    found: datatype.clone(),
+                    subject: label(subject),
+                    predicate: predicate.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Deserialize an instance of type `T` from a string of [N-Triples][n-triples].
+///
+/// [n-triples]: https://www.w3.org/TR/n-triples/
+///
+/// # Errors
+///
+/// This functions fails with an error if the data does not match the structure of `T`.
+pub fn from_ntriples_str<'de, T>(s: &'de str) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_ntriples_str(s)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from a string of [TriG][trig], reading only the named
+/// graph with the IRI `graph`, or all graphs merged when it is `None`.
+///
+/// [trig]: https://www.w3.org/TR/trig/
+///
+/// # Errors
+///
+/// This functions fails with an error if the selected graph is empty or the data does not
+/// match the structure of `T`.
+pub fn from_trig_str<'de, T>(s: &'de str, graph: Option<&str>) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_trig_str(s, graph)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from a string of [N-Quads][n-quads], reading only the
+/// named graph with the IRI `graph`, or all graphs merged when it is `None`.
+///
+/// [n-quads]: https://www.w3.org/TR/n-quads/
+///
+/// # Errors
+///
+/// This functions fails with an error if the selected graph is empty or the data does not
+/// match the structure of `T`.
+pub fn from_nquads_str<'de, T>(s: &'de str, graph: Option<&str>) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_nquads_str(s, graph)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from an IO stream of N-Triples, without reading it into
+/// memory first.
+///
+/// # Errors
+///
+/// This functions fails with an error if reading fails or the data does not match the
+/// structure of `T`.
+pub fn from_ntriples_reader<R, T>(reader: R) -> Result<T>
+where
+    R: io::Read,
+    T: de::DeserializeOwned,
+{
+    let deserializer = Deserializer::from_ntriples_reader(reader)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from a string of [RDF/XML][rdf-xml].
+///
+/// [rdf-xml]: https://www.w3.org/TR/rdf-syntax-grammar/
+///
+/// # Errors
+///
+/// This functions fails with an error if the data does not match the structure of `T`.
+#[cfg(feature = "xml")]
+pub fn from_rdfxml_str<'de, T>(s: &'de str) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_rdfxml_str(s)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from an IO stream of RDF/XML, without reading it into
+/// memory first.
+///
+/// # Errors
+///
+/// This functions fails with an error if reading fails or the data does not match the
+/// structure of `T`.
+#[cfg(feature = "xml")]
+pub fn from_rdfxml_reader<R, T>(reader: R) -> Result<T>
+where
+    R: io::Read,
+    T: de::DeserializeOwned,
+{
+    let deserializer = Deserializer::from_rdfxml_reader(reader)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from a string of [expanded JSON-LD][expanded], i.e. node
+/// objects with full IRIs as keys.
+///
+/// [expanded]: https://www.w3.org/TR/json-ld11/#expanded-document-form
+///
+/// # Errors
+///
+/// This functions fails with an error if the input is compacted JSON-LD, or if the data does
+/// not match the structure of `T`.
+#[cfg(feature = "jsonld")]
+pub fn from_jsonld_str<'de, T>(s: &'de str) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let deserializer = Deserializer::from_jsonld_str(s)?;
+    T::deserialize(deserializer)
+}
+
+/// Deserialize an instance of type `T` from each solution of a SPARQL SELECT query in the
+/// [JSON results format](https://www.w3.org/TR/sparql11-results-json/), i.e. a response of type
+/// `application/sparql-results+json`.
+///
+/// The variables are matched with the fields by name, as predicates are without a config, and
+/// their literals are read like those of RDF input. Unbound variables leave optional fields
+/// `None`. The variable `id` populates an `id` field, and the IRIs of other variables can be
+/// read into strings or `url::Url` fields.
+///
+/// # Errors
+///
+/// This functions fails with an error if the input is not in the JSON results format, or if
+/// a solution does not match the structure of `T`.
+#[cfg(feature = "sparql")]
+pub fn from_sparql_json<T>(json: &str) -> Result<Vec<T>>
+where
+    T: de::DeserializeOwned,
+{
+    crate::sparql::solutions(json)?
+        .into_iter()
+        .map(|(subject, triples)| {
+            T::deserialize(Deserializer {
+                subject: Some(subject),
+                ..Deserializer::from_triples(triples)
+            })
+        })
+        .collect()
+}
+
+/// Deserialize an instance of type `T` from bytes of RDF text, which must be valid UTF-8.
+///
+/// # Errors
+///
+/// This functions fails with an error if the bytes are not valid UTF-8 or the data does not
+/// match the structure of `T`.
+pub fn from_slice<'de, T>(v: &'de [u8]) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    from_str(std::str::from_utf8(v)?)
+}
+
+/// Deserialize an instance of type `T` from an IO stream of RDF text, without reading it into
+/// memory first.
+///
+/// # Errors
+///
+/// This functions fails with an error if reading fails or the data does not match the
+/// structure of `T`.
+pub fn from_reader<R, T>(reader: R) -> Result<T>
+where
+    R: io::Read,
+    T: de::DeserializeOwned,
+{
+    let deserializer = Deserializer::from_reader(reader)?;
+    T::deserialize(deserializer)
+}
+
+/// The root value of the document is the selected subject, or else its root subject.
+macro_rules! deserialize_root {
+    ($($method:ident($($arg:ident: $ty:ty),*),)*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                let root = match &self.subject {
+                    Some(subject) => subject.clone(),
+                    None => self.graph.root(self.config)?,
+                };
+                Values {
+                    graph: &self.graph,
+                    config: self.config,
+                    field: None,
+                    subject: None,
+                    predicate: None,
+                    objects: vec![&root],
+                }
+                .$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_, 'de> {
+    type Error = Error;
+
+    deserialize_root! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+}
+
+/// All objects of one predicate. A single value is taken from the first object, while
+/// sequences take all of them in the order of the triples in the document.
+struct Values<'a, 'de> {
+    graph: &'a Graph<'de>,
+    config: Option<&'a DeserializerConfig>,
+    /// The struct field the values are read into, if any.
+    field: Option<&'static str>,
+    /// The subject the values belong to, if any.
+    subject: Option<&'a OwnedObject>,
+    /// The predicate linking the values to their subject, if any.
+    predicate: Option<&'a str>,
+    objects: Vec<&'a OwnedObject>,
+}
+
+impl<'a, 'de> Values<'a, 'de> {
+    /// The single object of a single-valued field. Several objects are an error unless the
+    /// config lets the first one win.
+    fn first(&self) -> Result<Term<'a, 'de>> {
+        if self.objects.len() > 1 && !self.config.is_some_and(|config| config.first_wins) {
+            return Err(Error::CardinalityViolation {
+                subject: self.subject.map(label).unwrap_or_default(),
+                predicate: self.predicate.unwrap_or_default().to_string(),
+                expected: 1,
+                found: self.objects.len(),
+            });
+        }
+        let object = self
+            .objects
+            .first()
+            .ok_or_else(|| Error::new("deserialize -> no value found"))?;
+        Ok(Term {
+            graph: self.graph,
+            config: self.config,
+            field: self.field,
+            subject: self.subject,
+            predicate: self.predicate,
+            object,
+        })
+    }
+
+    /// The object a string is read from. Of several language-tagged literals, the first one in
+    /// the configured language priority is taken, falling back to the first literal.
+    fn string(&self) -> Result<Term<'a, 'de>> {
+        let languages = self
+            .objects
+            .iter()
+            .map(|object| match object {
+                OwnedObject::Literal(OwnedLiteral::LanguageTaggedString { language, .. }) => {
+                    Some(language.as_str())
+                }
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        let languages = match languages {
+            Some(languages) if languages.len() > 1 => languages,
+            _ => return self.first(),
+        };
+        let priority = self
+            .config
+            .map(|config| config.language_priority.as_slice())
+            .unwrap_or_default();
+        if priority.is_empty() {
+            return Err(Error::Message(format!(
+                "deserialize_string -> several languages and no language priority: {}",
+                languages.join(", ")
+            )));
+        }
+        let index = priority
+            .iter()
+            .find_map(|preferred| {
+                languages
+                    .iter()
+                    .position(|language| language.eq_ignore_ascii_case(preferred))
+            })
+            .unwrap_or(0);
+        Ok(Term {
+            graph: self.graph,
+            config: self.config,
+            field: self.field,
+            subject: self.subject,
+            predicate: self.predicate,
+            object: self.objects[index],
+        })
+    }
+
+    fn terms(&self) -> impl Iterator<Item = Term<'a, 'de>> + '_ {
+        self.objects.iter().map(|object| Term {
+            graph: self.graph,
+            config: self.config,
+            field: self.field,
+            subject: self.subject,
+            predicate: self.predicate,
+            object,
+        })
+    }
+}
+
+/// Deserialize single values from the first object.
+macro_rules! deserialize_first {
+    ($($method:ident($($arg:ident: $ty:ty),*),)*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                self.first()?.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Values<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.objects.len() {
+            0 => visitor.visit_unit(),
+            1 => self.first()?.deserialize_any(visitor),
+            _ => self.deserialize_seq(visitor),
+        }
+    }
+
+    // Options are `None` without objects. Otherwise the inner type takes the objects as if
+    // there was no option, so that a single value reports several objects as a
+    // `CardinalityViolation` unless the first one wins, while sequences take all of them.
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.objects.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if [value::TERM, value::LITERAL, value::NODE].contains(&name) || with::NAMES.contains(&name)
+        {
+            return self.first()?.deserialize_newtype_struct(name, visitor);
+        }
+        visitor.visit_newtype_struct(self)
+    }
+
+    // A single RDF collection is unfolded into its elements.
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // Binary literals are unfolded into their bytes, e.g. for `Vec<u8>` fields.
+        if let [OwnedObject::Literal(OwnedLiteral::Typed { datatype, .. })] = &self.objects[..] {
+            if matches!(
+                datatype.strip_prefix(XSD),
+                Some("base64Binary" | "hexBinary")
+            ) {
+                let mut seq = SeqDeserializer::<_, Error>::new(self.first()?.binary()?.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                return Ok(value);
+            }
+        }
+        let list = match self.objects[..] {
+            [head] => self.graph.list(head)?,
+            _ => None,
+        };
+        let values = match list {
+            Some(objects) => Values { objects, ..self },
+            None => self,
+        };
+        let mut seq = SeqDeserializer::new(values.terms());
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    // Maps are keyed by the language of their literals. Of several literals with the same
+    // language, the map keeps the last one unless the config is strict. A single subject is
+    // read as the map of its predicates instead.
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if let [object] = self.objects[..] {
+            if as_subject(object).is_some() {
+                return self.first()?.deserialize_map(visitor);
+            }
+        }
+        let strict = self
+            .config
+            .is_some_and(|config| config.strict_language_tags);
+        let entries = self
+            .terms()
+            .map(|term| match term.object {
+                OwnedObject::Literal(OwnedLiteral::LanguageTaggedString { language, .. }) => {
+                    let tag = LanguageTag {
+                        tag: language,
+                        strict,
+                    };
+                    Ok((tag, term))
+                }
+                _ => Err(Error::Message(format!(
+                    "deserialize_map -> expected a language-tagged literal, found: {}",
+                    term.lexical()
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if self.config.is_some_and(|config| config.strict) {
+            let mut languages = HashSet::new();
+            for (LanguageTag { tag: language, .. }, _) in &entries {
+                if !languages.insert(language.to_ascii_lowercase()) {
+                    return Err(Error::Message(format!(
+                        "deserialize_map -> duplicate language: {}",
+                        language
+                    )));
+                }
+            }
+        }
+        let mut map = MapDeserializer::new(entries.into_iter());
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.string()?.deserialize_str(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.string()?.deserialize_string(visitor)
+    }
+
+    deserialize_first! {
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
+/// A single object.
+#[derive(Clone, Copy)]
+struct Term<'a, 'de> {
+    graph: &'a Graph<'de>,
+    config: Option<&'a DeserializerConfig>,
+    field: Option<&'static str>,
+    subject: Option<&'a OwnedObject>,
+    predicate: Option<&'a str>,
+    object: &'a OwnedObject,
+}
+
+impl<'a, 'de> Term<'a, 'de> {
+    fn lexical(&self) -> &'a str {
+        match self.object {
+            OwnedObject::NamedNode(iri) => iri,
+            OwnedObject::BlankNode(id) => id,
+            OwnedObject::Literal(
+                OwnedLiteral::Simple { value }
+                | OwnedLiteral::LanguageTaggedString { value, .. }
+                | OwnedLiteral::Typed { value, .. },
+            ) => value,
+            OwnedObject::Triple(_) => "",
+        }
+    }
+
+    /// The lexical form of a literal of one of the XSD integer datatypes without surrounding
+    /// whitespace, which is accepted like a leading `+`, as in the XSD lexical space.
+    fn integer(&self, ty: &'static str) -> Result<&'a str> {
+        if !self.has_datatype(XSD_INTEGER_TYPES) {
+            return Err(self.unexpected_datatype(ty, "integer"));
+        }
+        let value = self.lexical().trim_matches(XSD_WHITESPACE);
+        let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::InvalidLexicalValue {
+                value: value.to_string(),
+                datatype: "xsd:integer",
+            });
+        }
+        Ok(value)
+    }
+
+    /// The value of an `xsd:boolean` literal: `true`, `false`, `1` or `0`.
+    fn boolean(&self) -> Result<bool> {
+        if !self.has_datatype(&["boolean"]) {
+            return Err(self.unexpected_datatype("bool", "boolean"));
+        }
+        match self.lexical().trim_matches(XSD_WHITESPACE) {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            value => Err(Error::InvalidLexicalValue {
+                value: value.to_string(),
+                datatype: "xsd:boolean",
+            }),
+        }
+    }
+
+    /// The lexical form of a literal of `xsd:double`, `xsd:float`, `xsd:decimal` or one of the
+    /// integer datatypes, with the special values `INF`, `-INF` and `NaN` spelled the way Rust
+    /// parses them.
+    fn float(&self, ty: &'static str) -> Result<&'a str> {
+        let floating = self.has_datatype(&["double", "float"]);
+        if !floating && !self.has_datatype(&["decimal"]) && !self.has_datatype(XSD_INTEGER_TYPES) {
+            return Err(self.unexpected_datatype(ty, "double"));
+        }
+        let value = self.lexical().trim_matches(XSD_WHITESPACE);
+        match value {
+            "INF" | "+INF" if floating => return Ok("inf"),
+            "-INF" if floating => return Ok("-inf"),
+            "NaN" if floating => return Ok("NaN"),
+            _ => {}
+        }
+        let valid = match value.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => {
+                let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+                floating
+                    && is_decimal(mantissa)
+                    && !digits.is_empty()
+                    && digits.bytes().all(|b| b.is_ascii_digit())
+            }
+            None => is_decimal(value),
+        };
+        match valid {
+            true => Ok(value),
+            false => Err(Error::InvalidLexicalValue {
+                value: value.to_string(),
+                datatype: if floating {
+                    "xsd:double"
+                } else {
+                    "xsd:decimal"
+                },
+            }),
+        }
+    }
+
+    /// Check that an `xsd:dateTime` or `xsd:date` literal is well-formed and within the range
+    /// of the chrono types.
+    #[cfg(feature = "chrono")]
+    fn check_temporal(&self, datatype: &str) -> Result<()> {
+        let value = self.lexical().trim_matches(XSD_WHITESPACE);
+        match datatype {
+            "dateTime" => chrono::DateTime::parse_from_rfc3339(value)
+                .map(|_| ())
+                .or_else(|_| value.parse::<chrono::NaiveDateTime>().map(|_| ())),
+            _ => value.parse::<chrono::NaiveDate>().map(|_| ()),
+        }
+        .map_err(Error::new)
+    }
+
+    /// The bytes of an `xsd:base64Binary` or `xsd:hexBinary` literal.
+    fn binary(&self) -> Result<Vec<u8>> {
+        let value = self.lexical().trim_matches(XSD_WHITESPACE);
+        if self.has_datatype(&["base64Binary"]) {
+            decode_base64(value)
+        } else if self.xsd_datatype() == Some("hexBinary") {
+            decode_hex(value)
+        } else {
+            Err(Error::Message(format!(
+                "deserialize_bytes -> expected an xsd:base64Binary or xsd:hexBinary literal, \
+                 found: {}",
+                label(self.object)
+            )))
+        }
+    }
+
+    fn out_of_range(&self, ty: &'static str) -> Error {
+        Error::OutOfRange {
+            field: self.field,
+            value: self.lexical().trim().to_string(),
+            ty,
+        }
+    }
+
+    /// Whether the object is a literal of one of the given XSD datatypes. Simple literals are
+    /// accepted as well if the config is lenient, and `xsd:string` literals if it coerces them.
+    fn has_datatype(&self, datatypes: &[&str]) -> bool {
+        let coerce = self.config.is_some_and(|config| config.coerce_literals);
+        match self.object {
+            OwnedObject::Literal(OwnedLiteral::Simple { .. }) => {
+                coerce || self.config.is_some_and(|config| config.lenient)
+            }
+            _ => self.xsd_datatype().is_some_and(|datatype| {
+                datatypes.contains(&datatype) || (coerce && datatype == "string")
+            }),
+        }
+    }
+
+    /// The error for an object that is not a literal of the `expected` XSD datatype, which is
+    /// an [`Error::UnexpectedDatatype`] for other literals.
+    fn unexpected_datatype(&self, method: &str, expected: &str) -> Error {
+        let found = match self.object {
+            OwnedObject::Literal(OwnedLiteral::Simple { .. }) => format!("{XSD}string"),
+            OwnedObject::Literal(OwnedLiteral::LanguageTaggedString { .. }) => {
+                RDF_LANG_STRING.to_string()
+            }
+            OwnedObject::Literal(OwnedLiteral::Typed { datatype, .. }) => datatype.clone(),
+            _ => {
+                return Error::Message(format!(
+                    "deserialize_{} -> expected an xsd:{} literal, found: {}",
+                    method,
+                    expected,
+                    label(self.object)
+                ))
+            }
+        };
+        Error::UnexpectedDatatype {
+            expected: format!("{XSD}{expected}"),
+            found,
+            subject: self.subject.map(label).unwrap_or_default(),
+            predicate: self.predicate.unwrap_or_default().to_string(),
+        }
+    }
+
+    /// The value of an `xsd:decimal` or integer literal in the canonical form of
+    /// `rust_decimal::Decimal`, which keeps the scale of the lexical form, e.g. `0.10`.
+    #[cfg(feature = "decimal")]
+    fn decimal(&self) -> Result<String> {
+        if !self.has_datatype(&["decimal"]) && !self.has_datatype(XSD_INTEGER_TYPES) {
+            return Err(self.unexpected_datatype("decimal", "decimal"));
+        }
+        let value = self.lexical().trim_matches(XSD_WHITESPACE);
+        // Unlike floats, xsd:decimal has no exponent notation.
+        let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+        if digits.is_empty()
+            || digits == "."
+            || digits.matches('.').count() > 1
+            || !digits.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+        {
+            return Err(Error::InvalidLexicalValue {
+                value: value.to_string(),
+                datatype: "xsd:decimal",
+            });
+        }
+        let decimal = rust_decimal::Decimal::from_str_exact(value).map_err(|err| {
+            Error::Message(format!(
+                "deserialize_decimal -> {value} cannot be represented exactly: {err}"
+            ))
+        })?;
+        Ok(decimal.to_string())
+    }
+
+    /// The local name of the XSD datatype of a typed literal.
+    fn xsd_datatype(&self) -> Option<&str> {
+        match self.object {
+            OwnedObject::Literal(OwnedLiteral::Typed { datatype, .. }) => {
+                datatype.strip_prefix(XSD)
+            }
+            _ => None,
+        }
+    }
+
+    /// The classes of the subject this term refers to.
+    fn types(&self) -> Vec<&'a str> {
+        let Some(subject) = as_subject(self.object) else {
+            return Vec::new();
+        };
+        let type_predicate = type_predicate(self.config);
+        self.graph
+            .statements(&subject)
+            .iter()
+            .filter_map(|(predicate, object)| match object {
+                OwnedObject::NamedNode(class) if *predicate == type_predicate => {
+                    Some(class.as_str())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The config of the subjects of a class, if any.
+    fn subject_config(&self, class: &str) -> Option<&'a SubjectConfig> {
+        let config = self.config?;
+        config.subjects.values().find(|subject| {
+            !subject.rdf_type.is_empty() && config.expand_prefixed_name(&subject.rdf_type) == class
+        })
+    }
+
+    /// The name of the enum variant representing a class: the one mapped in the config, the
+    /// name of the struct configured with the class as its `rdf_type`, or the local name of
+    /// the class.
+    fn class_name(&self, class: &'a str) -> &'a str {
+        let mapped = self.config.and_then(|config| {
+            config
+                .classes
+                .iter()
+                .find(|(key, _)| config.expand_prefixed_name(key) == class)
+                .map(|(_, variant)| variant.as_str())
+        });
+        mapped
+            .or_else(|| {
+                self.subject_config(class)
+                    .map(|subject| subject.struct_name.as_str())
+            })
+            .unwrap_or_else(|| class.rsplit(['#', '/']).next().unwrap_or(class))
+    }
+
+    /// All statements of the subject this term refers to, keyed by field, for self-describing
+    /// types such as internally tagged and untagged enums. Next to the identifier, the name of
+    /// its class is held by the type tag. The fields are named by the subject config of the
+    /// class, or else by the local names of the predicates without a leading `has`, e.g. `name`
+    /// for `hasName`.
+    fn description(&self) -> Vec<(Cow<'a, str>, Field<'a, 'de>)> {
+        let Some(subject) = as_subject(self.object) else {
+            return Vec::new();
+        };
+        let class = self.types().first().copied();
+        let subject_config = class.and_then(|class| self.subject_config(class));
+
+        let mut entries = Vec::new();
+        let (id_field, prefix) = match (self.config, subject_config) {
+            (Some(config), Some(subject_config)) => (
+                subject_config.identifier_field.as_str(),
+                match config.strip_identifier_prefix {
+                    true => subject_config.identifier_prefix.as_str(),
+                    false => "",
+                },
+            ),
+            _ => (ID_FIELD, ""),
+        };
+        if !id_field.is_empty() {
+            entries.push((Cow::Borrowed(id_field), Field::Id(self.identifier(prefix))));
+        }
+        if let Some(class) = class {
+            let tag = self
+                .config
+                .and_then(|config| config.type_tag.as_deref())
+                .unwrap_or(TYPE_TAG);
+            let name = Identifier {
+                value: Cow::Borrowed(self.class_name(class)),
+                source: None,
+                absent: false,
+            };
+            entries.push((Cow::Borrowed(tag), Field::Id(name)));
+        }
+
+        let statements = self.graph.statements(&subject);
+        let type_predicate = type_predicate(self.config);
+        let mut predicates: Vec<&str> = Vec::new();
+        for (predicate, _) in statements {
+            if *predicate != type_predicate && !predicates.contains(&predicate.as_str()) {
+                predicates.push(predicate);
+            }
+        }
+        for predicate in predicates {
+            let field = match (self.config, subject_config) {
+                (Some(config), Some(subject_config)) => {
+                    let property = subject_config.properties.iter().find(|property| {
+                        config.expand_prefixed_name(&property.rdf_property) == predicate
+                    });
+                    match property {
+                        Some(property) => Cow::Borrowed(property.struct_field.as_str()),
+                        None => continue,
+                    }
+                }
+                _ => {
+                    let local = local_name(predicate);
+                    let mut chars = local.chars();
+                    match chars.next() {
+                        Some(first) if first.is_uppercase() => {
+                            Cow::Owned(first.to_lowercase().chain(chars).collect())
+                        }
+                        _ => Cow::Borrowed(local),
+                    }
+                }
+            };
+            let statements = statements.iter().filter(|(p, _)| p == predicate).collect();
+            entries.push((field, Field::Values(self.values(None, statements))));
+        }
+        entries
+    }
+
+    fn values(
+        &self,
+        field: Option<&'static str>,
+        statements: Vec<&'a (String, OwnedObject)>,
+    ) -> Values<'a, 'de> {
+        Values {
+            graph: self.graph,
+            config: self.config,
+            field,
+            subject: Some(self.object),
+            predicate: statements.first().map(|(predicate, _)| predicate.as_str()),
+            objects: statements.iter().map(|(_, object)| object).collect(),
+        }
+    }
+
+    /// The identifier of the subject this term refers to: its IRI without the given prefix, or
+    /// the label of a blank node. Skolem IRIs under the configured `skolem_base` count as
+    /// blank nodes labeled with the rest of the IRI.
+    fn identifier(&self, prefix: &str) -> Identifier<'a, 'de> {
+        let label = match self.object {
+            OwnedObject::BlankNode(id) => Some(id.as_str()),
+            OwnedObject::NamedNode(iri) => self
+                .config
+                .and_then(|config| config.skolem_base.as_deref())
+                .and_then(|base| iri.strip_prefix(base)),
+            _ => None,
+        };
+        match label {
+            Some(label) => Identifier {
+                value: Cow::Owned(format!("_:{label}")),
+                source: None,
+                absent: !self.config.is_some_and(|config| config.blank_node_labels),
+            },
+            _ => {
+                let iri = self.lexical();
+                Identifier {
+                    value: Cow::Borrowed(iri.strip_prefix(prefix).unwrap_or(iri)),
+                    source: Some((self.graph, iri)),
+                    absent: false,
+                }
+            }
+        }
+    }
+
+    /// The values of the fields of a struct read from the subject this term refers to.
+    fn fields(
+        &self,
+        name: &'static str,
+        fields: &'static [&'static str],
+    ) -> Result<Vec<(Cow<'a, str>, Field<'a, 'de>)>> {
+        let subject = as_subject(self.object).ok_or_else(|| {
+            Error::Message(format!(
+                "deserialize_struct -> expected a subject for struct {}, found: {}",
+                name,
+                self.lexical()
+            ))
+        })?;
+        let statements = self.graph.statements(&subject);
+        let matching = |matches: &dyn Fn(&str) -> bool| -> Vec<_> {
+            statements
+                .iter()
+                .filter(|(predicate, _)| matches(predicate))
+                .collect()
+        };
+
+        let mut entries = Vec::new();
+        let Some(config) = self.config else {
+            for field in fields {
+                if *field == ID_FIELD {
+                    entries.push((Cow::Borrowed(*field), Field::Id(self.identifier(""))));
+                    continue;
+                }
+                let statements = matching(&|predicate| guess_field(predicate, field));
+                // Absent fields are left to serde, which fills in `None` for options.
+                if !statements.is_empty() {
+                    let values = self.values(Some(field), statements);
+                    entries.push((Cow::Borrowed(*field), Field::Values(values)));
+                }
+            }
+            return Ok(entries);
+        };
+
+        let subject_config =
+            config
+                .subjects
+                .get(name)
+                .ok_or_else(|| Error::MissingSubjectConfig {
+                    struct_name: name.to_string(),
+                })?;
+        let mut consumed = HashSet::new();
+        for field in fields {
+            if *field == subject_config.identifier_field {
+                let prefix = match config.strip_identifier_prefix {
+                    true => subject_config.identifier_prefix.as_str(),
+                    false => "",
+                };
+                entries.push((Cow::Borrowed(*field), Field::Id(self.identifier(prefix))));
+                continue;
+            }
+            if subject_config.type_field.as_deref() == Some(*field) {
+                if let Some(class) = self.types().first() {
+                    let class = Identifier {
+                        value: Cow::Borrowed(*class),
+                        source: None,
+                        absent: false,
+                    };
+                    entries.push((Cow::Borrowed(*field), Field::Id(class)));
+                }
+                continue;
+            }
+            if config.graph_field.as_deref() == Some(*field) {
+                if let Some(graph) = &self.graph.name {
+                    let graph = Identifier {
+                        value: Cow::Borrowed(graph),
+                        source: None,
+                        absent: false,
+                    };
+                    entries.push((Cow::Borrowed(*field), Field::Id(graph)));
+                }
+                continue;
+            }
+            // Fields without a property mapping are not part of the RDF representation.
+            let Some(property) = subject_config
+                .properties
+                .iter()
+                .find(|p| p.struct_field == *field)
+            else {
+                continue;
+            };
+            let rdf_property = config.expand_prefixed_name(&property.rdf_property);
+            let find = |rdf_property: &str| -> Vec<_> {
+                match property.inverse {
+                    true => self
+                        .graph
+                        .referrers(&subject)
+                        .iter()
+                        .filter(|(predicate, _)| predicate == rdf_property)
+                        .collect(),
+                    false => matching(&|predicate| predicate == rdf_property),
+                }
+            };
+            let mut statements = find(&rdf_property);
+            for alias in &property.aliases {
+                let alias = config.expand_prefixed_name(alias);
+                let found = find(&alias);
+                let same = |a: &[&(String, OwnedObject)], b: &[&(String, OwnedObject)]| {
+                    a.iter().all(|(_, x)| b.iter().any(|(_, y)| x == y))
+                };
+                if statements.is_empty() {
+                    statements = found;
+                } else if !found.is_empty()
+                    && !(same(&statements, &found) && same(&found, &statements))
+                    && !config.prefer_primary_property
+                {
+                    return Err(Error::Message(format!(
+                        "deserialize_struct -> conflicting values of {} for field {}.{}: <{}> \
+                         and its alias <{}>",
+                        label(self.object),
+                        name,
+                        field,
+                        statements[0].0,
+                        alias
+                    )));
+                }
+                consumed.insert(alias);
+            }
+            check_datatype(config, property, self.object, &statements)?;
+            if config.check_counts {
+                property.check_count(|| label(self.object), &rdf_property, statements.len())?;
+            }
+            if !statements.is_empty() {
+                let values = self.values(Some(field), statements);
+                entries.push((Cow::Borrowed(*field), Field::Values(values)));
+            } else if let Some(literal) = &property.default_literal {
+                let object = default_literal(config, field, literal)?;
+                let default = Field::Default {
+                    graph: self.graph,
+                    config,
+                    field,
+                    subject: self.object,
+                    predicate: rdf_property.clone(),
+                    object,
+                };
+                entries.push((Cow::Borrowed(*field), default));
+            }
+            // Absent fields without a default are left to serde, which fills in
+            // `#[serde(default)]` or reports them as missing.
+            consumed.insert(rdf_property);
+        }
+
+        if config.deny_unknown_predicates {
+            let type_predicate = config.type_predicate();
+            let mut unknown: Vec<_> = statements
+                .iter()
+                .map(|(predicate, _)| predicate.as_str())
+                .filter(|predicate| *predicate != type_predicate && !consumed.contains(*predicate))
+                .collect();
+            unknown.sort_unstable();
+            unknown.dedup();
+            if !unknown.is_empty() {
+                return Err(Error::Message(format!(
+                    "deserialize_struct -> unknown predicates on {}: <{}>",
+                    label(self.object),
+                    unknown.join(">, <")
+                )));
+            }
+        }
+        Ok(entries)
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Term<'_, 'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Deserialize integers from XSD integer literals, checking that the value fits the target type.
+macro_rules! deserialize_integer {
+    ($($method:ident => $visit:ident($ty:ident),)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                let value = self.integer(stringify!($ty))?;
+                // Lexically valid values only fail to parse if they exceed the type, where `-0`
+                // is zero for unsigned types as well.
+                let value = value
+                    .strip_prefix('-')
+                    .filter(|digits| digits.bytes().all(|b| b == b'0'))
+                    .unwrap_or(value);
+                let value = value
+                    .parse::<$ty>()
+                    .map_err(|_| self.out_of_range(stringify!($ty)))?;
+                visitor.$visit(value)
+            }
+        )*
+    };
+}
+
+/// Deserialize floating point numbers from XSD numeric literals.
+macro_rules! deserialize_float {
+    ($($method:ident => $visit:ident($ty:ident),)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                let value = self.float(stringify!($ty))?;
+                visitor.$visit(value.parse::<$ty>().map_err(Error::new)?)
+            }
+        )*
+    };
+}
+
+/// Deserialize primitives by parsing the lexical form of the object.
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                let value = self.lexical();
+                visitor.$visit(value.parse().map_err(|err| {
+                    Error::Message(format!(
+                        "{} -> invalid lexical form {:?}: {}",
+                        stringify!($method),
+                        value,
+                        err
+                    ))
+                })?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Term<'_, 'de> {
+    type Error = Error;
+
+    // Typed literals are handed to the visitor as the closest Rust type, everything else
+    // as its lexical form.
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // Subjects with statements are described as maps.
+        if let Some(subject) = as_subject(self.object) {
+            if !self.graph.statements(&subject).is_empty() {
+                let entries = self.description();
+                let max_depth = self
+                    .config
+                    .and_then(|config| config.max_depth)
+                    .unwrap_or(DEFAULT_MAX_DEPTH);
+                self.graph.enter(self.object, max_depth)?;
+                let value = visitor.visit_map(Fields {
+                    entries: entries.into_iter(),
+                    value: None,
+                    key: Cow::Borrowed(""),
+                    context: None,
+                });
+                self.graph.leave();
+                return value;
+            }
+        }
+        let value = self.lexical();
+        match self.xsd_datatype() {
+            Some("boolean") => self.deserialize_bool(visitor),
+            // Integers beyond 128 bits are handed over as their lexical form.
+            Some(datatype) if XSD_INTEGER_TYPES.contains(&datatype) => {
+                if let Ok(n) = value.parse::<u64>() {
+                    visitor.visit_u64(n)
+                } else if let Ok(n) = value.parse::<i64>() {
+                    visitor.visit_i64(n)
+                } else if let Ok(n) = value.parse::<u128>() {
+                    visitor.visit_u128(n)
+                } else if let Ok(n) = value.parse::<i128>() {
+                    visitor.visit_i128(n)
+                } else {
+                    visitor.visit_str(value)
+                }
+            }
+            Some("decimal" | "double" | "float") => match value.parse::<f64>() {
+                Ok(n) => visitor.visit_f64(n),
+                _ => visitor.visit_str(value),
+            },
+            _ => visitor.visit_str(value),
+        }
+    }
+
+    deserialize_integer! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bool(self.boolean()?)
+    }
+
+    deserialize_float! {
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    deserialize_parsed! {
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.graph.borrow(self.lexical()) {
+            Some(value) => visitor.visit_borrowed_str(value),
+            None => visitor.visit_str(self.lexical()),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_byte_buf(self.binary()?)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    // The dynamic value model is handed over in N-Triples syntax, and the fields of the
+    // `with` modules once their literals are checked.
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // `std::time::Duration` is read from day-time durations.
+        if name == with::DURATION {
+            if !self.has_datatype(&["duration", "dayTimeDuration"]) {
+                return Err(self.unexpected_datatype("newtype_struct", "duration"));
+            }
+            let lexical = self.lexical().trim_matches(XSD_WHITESPACE);
+            std::time::Duration::try_from(lexical.parse::<XsdDuration>()?)?;
+            let lexical: StrDeserializer<'_, Error> = lexical.into_deserializer();
+            return visitor.visit_newtype_struct(lexical);
+        }
+        #[cfg(feature = "decimal")]
+        if name == with::DECIMAL {
+            let decimal: StringDeserializer<Error> = self.decimal()?.into_deserializer();
+            return visitor.visit_newtype_struct(decimal);
+        }
+        #[cfg(feature = "url")]
+        if name == with::URL {
+            if !matches!(self.object, OwnedObject::NamedNode(_)) && !self.has_datatype(&["anyURI"])
+            {
+                return Err(Error::Message(format!(
+                    "deserialize_newtype_struct -> expected an IRI or an xsd:anyURI literal for \
+                     a url::Url of {}, found: {}",
+                    self.predicate
+                        .map_or("value".to_string(), |p| format!("<{p}>")),
+                    label(self.object)
+                )));
+            }
+            return visitor.visit_newtype_struct(self);
+        }
+        #[cfg(feature = "chrono")]
+        if name == with::CHRONO {
+            if let Some(datatype @ ("dateTime" | "date")) = self.xsd_datatype() {
+                self.check_temporal(datatype).map_err(|err| {
+                    Error::Message(format!(
+                        "deserialize_newtype_struct -> invalid xsd:{} {:?} of {}: {}",
+                        datatype,
+                        self.lexical(),
+                        self.predicate
+                            .map_or("value".to_string(), |p| format!("<{p}>")),
+                        err
+                    ))
+                })?;
+            }
+            return visitor.visit_newtype_struct(self);
+        }
+        let ntriples = match name {
+            value::TERM => value::Term::from_object(self.object)?.to_string(),
+            value::LITERAL => match self.object {
+                OwnedObject::Literal(_) => value::Term::from_object(self.object)?.to_string(),
+                object => {
+                    return Err(Error::Message(format!(
+                        "deserialize_newtype_struct -> expected a literal, found: {}",
+                        label(object)
+                    )))
+                }
+            },
+            value::NODE => {
+                let subject = as_subject(self.object).ok_or_else(|| {
+                    Error::Message(format!(
+                        "deserialize_newtype_struct -> expected a subject for a value::Node, \
+                         found: {}",
+                        self.lexical()
+                    ))
+                })?;
+                value::Node::from_statements(&subject, self.graph.statements(&subject))?.to_string()
+            }
+            _ => return visitor.visit_newtype_struct(self),
+        };
+        let ntriples: StringDeserializer<Error> = ntriples.into_deserializer();
+        visitor.visit_newtype_struct(ntriples)
+    }
+
+    // Structs are read from the statements of the subject the object refers to.
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let entries = self.fields(name, fields)?;
+        let max_depth = self
+            .config
+            .and_then(|config| config.max_depth)
+            .unwrap_or(DEFAULT_MAX_DEPTH);
+        self.graph.enter(self.object, max_depth)?;
+        let value = visitor.visit_map(Fields {
+            entries: entries.into_iter(),
+            value: None,
+            key: Cow::Borrowed(""),
+            context: Some((self.object, name)),
+        });
+        self.graph.leave();
+        value.map_err(|err| err.context("deserializing", || label(self.object), None))
+    }
+
+    // Enums are chosen by the `rdf:type` of the subject, whose struct is read into the variant.
+    // Otherwise they are unit variants named by a literal, or by an IRI that is either mapped
+    // in the config or has the variant as its local name.
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let types = self.types();
+        let typed = types.iter().copied().find_map(|class| {
+            let class = self.class_name(class);
+            variants.iter().find(|variant| **variant == class).copied()
+        });
+        if let Some(variant) = typed {
+            return visitor.visit_enum(TypedVariant {
+                term: self,
+                variant,
+            });
+        }
+
+        let value = self.lexical();
+        let mapped = match (self.object, self.config) {
+            (OwnedObject::NamedNode(iri), Some(config)) => config
+                .variants
+                .iter()
+                .find(|(key, _)| config.expand_prefixed_name(key) == iri.as_str())
+                .map(|(_, variant)| variant.as_str()),
+            _ => None,
+        };
+        let local = match (mapped, self.object) {
+            (Some(variant), _) => variant,
+            (None, OwnedObject::NamedNode(iri)) => iri.rsplit(['#', '/']).next().unwrap_or(iri),
+            (None, _) => value,
+        };
+        let described = as_subject(self.object)
+            .is_some_and(|subject| !self.graph.statements(&subject).is_empty());
+        let variant = variants
+            .iter()
+            .find(|variant| **variant == local)
+            .copied()
+            .ok_or_else(|| match (types.is_empty(), described) {
+                (false, _) => Error::Message(format!(
+                    "deserialize_enum -> no variant of {} matches the rdf:type of {}: <{}>, \
+                     expected one of: {}",
+                    name,
+                    label(self.object),
+                    types.join(">, <"),
+                    variants.join(", ")
+                )),
+                (true, true) => Error::Message(format!(
+                    "deserialize_enum -> {} has no rdf:type to choose a variant of {} from, \
+                     expected one of: {}",
+                    label(self.object),
+                    name,
+                    variants.join(", ")
+                )),
+                (true, false) => Error::UnknownVariant {
+                    value: match self.object {
+                        OwnedObject::Literal(_) => format!("{value:?}"),
+                        object => label(object),
+                    },
+                    expected: variants,
+                },
+            })?;
+        visitor.visit_enum(variant.into_deserializer())
+    }
+
+    // Maps are read from the statements of the subject, keyed by the IRIs of their predicates
+    // in the order they first appear, with all objects of each predicate as the value.
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let Some(subject) = as_subject(self.object) else {
+            return self.deserialize_any(visitor);
+        };
+        let statements = self.graph.statements(&subject);
+        let mut predicates: Vec<&str> = Vec::new();
+        for (predicate, _) in statements {
+            if !predicates.contains(&predicate.as_str()) {
+                predicates.push(predicate);
+            }
+        }
+        let entries: Vec<_> = predicates
+            .into_iter()
+            .map(|predicate| {
+                let statements = statements.iter().filter(|(p, _)| p == predicate).collect();
+                (
+                    Cow::Borrowed(predicate),
+                    Field::Values(self.values(None, statements)),
+                )
+            })
+            .collect();
+        let max_depth = self
+            .config
+            .and_then(|config| config.max_depth)
+            .unwrap_or(DEFAULT_MAX_DEPTH);
+        self.graph.enter(self.object, max_depth)?;
+        let value = visitor.visit_map(Fields {
+            entries: entries.into_iter(),
+            value: None,
+            key: Cow::Borrowed(""),
+            context: None,
+        });
+        self.graph.leave();
+        value
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        unit unit_struct seq tuple tuple_struct
+    }
+}
+
+/// The value of a struct field.
+enum Field<'a, 'de> {
+    /// The IRI of the subject, or another name such as the one of its class.
+    Id(Identifier<'a, 'de>),
+    Values(Values<'a, 'de>),
+    /// The `default_literal` of a field without triples.
+    Default {
+        graph: &'a Graph<'de>,
+        config: &'a DeserializerConfig,
+        field: &'static str,
+        subject: &'a OwnedObject,
+        predicate: Cow<'a, str>,
+        object: OwnedObject,
+    },
+}
+
+/// The fields of a struct that have a value.
+struct Fields<'a, 'de> {
+    entries: std::vec::IntoIter<(Cow<'a, str>, Field<'a, 'de>)>,
+    value: Option<Field<'a, 'de>>,
+    /// The key of the value.
+    key: Cow<'a, str>,
+    /// The subject and the name of its struct, which are added to the errors of the fields.
+    context: Option<(&'a OwnedObject, &'static str)>,
+}
+
+impl<'de> MapAccess<'de> for Fields<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                let deserializer: StrDeserializer<'_, Error> = key.as_ref().into_deserializer();
+                let key_value = seed.deserialize(deserializer).map(Some);
+                self.key = key;
+                key_value
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<S>(&mut self, seed: S) -> Result<S::Value>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let value = match self.value.take() {
+            Some(Field::Id(id)) => seed.deserialize(id),
+            Some(Field::Values(values)) => seed.deserialize(values),
+            Some(Field::Default {
+                graph,
+                config,
+                field,
+                subject,
+                predicate,
+                object,
+            }) => seed.deserialize(Term {
+                graph,
+                config: Some(config),
+                field: Some(field),
+                subject: Some(subject),
+                predicate: Some(&predicate),
+                object: &object,
+            }),
+            None => Err(Error::new(
+                "deserialize_struct -> value requested before key",
+            )),
+        };
+        match self.context {
+            Some((subject, name)) => value.map_err(|err| {
+                let field = format!("{name}.{}", self.key);
+                err.context("deserializing", || label(subject), Some(field))
+            }),
+            None => value,
+        }
+    }
+}
+
+/// The variant of an enum chosen by the class of a subject, which is read into it.
+struct TypedVariant<'a, 'de> {
+    term: Term<'a, 'de>,
+    variant: &'static str,
+}
+
+impl<'de> EnumAccess<'de> for TypedVariant<'_, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self)>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let variant: StrDeserializer<'_, Error> = self.variant.into_deserializer();
+        Ok((seed.deserialize(variant)?, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for TypedVariant<'_, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.term)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::Message(format!(
+            "deserialize_enum -> tuple variant {} cannot be read from {}",
+            self.variant,
+            label(self.term.object)
+        )))
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(self.term, self.variant, fields, visitor)
+    }
+}
+
+/// The identifier of a subject. It is absent for blank nodes, so that an `Option` receives
+/// `None`, unless their labels are kept, whereas a `String` always receives the label.
+struct Identifier<'a, 'de> {
+    value: Cow<'a, str>,
+    /// The graph whose source the value may be borrowed from, with the IRI the value is the
+    /// end of.
+    source: Option<(&'a Graph<'de>, &'a str)>,
+    absent: bool,
+}
+
+impl<'de> de::Deserializer<'de> for Identifier<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(&self.value)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // An IRI written as a prefixed name may still have the value as its local name.
+        let borrowed = self.source.and_then(|(graph, iri)| {
+            graph
+                .borrow(iri)
+                .map(|iri| &iri[iri.len() - self.value.len()..])
+                .or_else(|| graph.borrow(&self.value))
+        });
+        match borrowed {
+            Some(value) => visitor.visit_borrowed_str(value),
+            None => visitor.visit_str(&self.value),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.absent {
+            true => visitor.visit_none(),
+            false => visitor.visit_some(self),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// The language tag keying a language map. Enum keys such as `IsoCode::EN` match the tag
+/// ignoring case, and unless strict, also its primary language subtag, e.g. `en` of `en-US`.
+struct LanguageTag<'a> {
+    tag: &'a str,
+    /// Report tags that match no variant, instead of leaving them to serde.
+    strict: bool,
+}
+
+impl<'de> IntoDeserializer<'de, Error> for LanguageTag<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for LanguageTag<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.tag)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let find = |tag: &str| {
+            variants
+                .iter()
+                .find(|variant| variant.eq_ignore_ascii_case(tag))
+                .copied()
+        };
+        let variant = match self.strict {
+            true => find(self.tag).ok_or_else(|| Error::UnknownVariant {
+                value: format!("@{}", self.tag),
+                expected: variants,
+            })?,
+            false => find(self.tag)
+                .or_else(|| find(self.tag.split('-').next().unwrap_or(self.tag)))
+                .unwrap_or(self.tag),
+        };
+        visitor.visit_enum(variant.into_deserializer())
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        from_ntriples_reader, from_ntriples_str, from_reader, from_slice, from_str, from_str_all,
+        from_str_by_type, from_str_for_subject, from_str_with_config, from_triples, to_string,
+        DeserializerConfig, Error, ErrorKind, OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple,
+        PropertyConfig, SerializerConfig, SubjectConfig, XsdDuration, XsdType,
+    };
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Project {
+        id: String,
+        name: String,
+        description: HashMap<String, String>,
+        shortcode: String,
+        public: bool,
+        size: u32,
+        datasets: Vec<Dataset>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Dataset {
+        id: String,
+        name: String,
+        keyword: Option<String>,
+    }
+
+    const PROJECT: &str = r#"
+        @prefix dsp: <https://ns.dasch.swiss/repository#> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        <https://ark.dasch.swiss/ark:/72163/1/081C> a dsp:Project ;
+            dsp:hasName "Hôtel de Musique Bern"^^xsd:string ;
+            dsp:hasDescription "Das Hôtel de Musique"@de, "The Hôtel de Musique"@en ;
+            dsp:hasShortcode "081C" ;
+            dsp:public true ;
+            dsp:hasSize 42 ;
+            dsp:hasDataset <https://ark.dasch.swiss/ark:/72163/1/081C/d1> .
+
+        <https://ark.dasch.swiss/ark:/72163/1/081C/d1> a dsp:Dataset ;
+            dsp:hasName "Events" .
+    "#;
+
+    #[test]
+    fn test_from_str() {
+        let input = PROJECT;
+
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                id: "https://ark.dasch.swiss/ark:/72163/1/081C".to_string(),
+                name: "Hôtel de Musique Bern".to_string(),
+                description: HashMap::from([
+                    ("de".to_string(), "Das Hôtel de Musique".to_string()),
+                    ("en".to_string(), "The Hôtel de Musique".to_string()),
+                ]),
+                shortcode: "081C".to_string(),
+                public: true,
+                size: 42,
+                datasets: vec![Dataset {
+                    id: "https://ark.dasch.swiss/ark:/72163/1/081C/d1".to_string(),
+                    name: "Events".to_string(),
+                    keyword: None,
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_turtle_abbreviations() {
+        let input = r#"
+            @base <https://ark.dasch.swiss/ark:/72163/1/> .
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+            <081C/d1> a dsp:Dataset ;
+                dsp:hasName "Events"^^xsd:string ;
+                dsp:hasKeyword "music" .
+        "#;
+        let dataset: Dataset = from_str(input).unwrap();
+        assert_eq!(
+            dataset,
+            Dataset {
+                id: "https://ark.dasch.swiss/ark:/72163/1/081C/d1".to_string(),
+                name: "Events".to_string(),
+                keyword: Some("music".to_string()),
+            }
+        );
+
+        // The `xsd` and `dsp` prefixes are not declared.
+        let input = r#"
+            <https://example.org/d1> a dsp:Dataset ;
+                dsp:hasName "Events"^^xsd:string .
+        "#;
+        assert!(from_str::<Dataset>(input).is_err());
+
+        let property = |field: &str, predicate: &str| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("dsp:{predicate}"),
+            ..Default::default()
+        };
+        let config = DeserializerConfig {
+            namespaces: HashMap::from([(
+                "dsp".to_string(),
+                "https://ns.dasch.swiss/repository#".to_string(),
+            )]),
+            subjects: HashMap::from([(
+                "Dataset".to_string(),
+                SubjectConfig {
+                    struct_name: "Dataset".to_string(),
+                    identifier_field: "id".to_string(),
+                    properties: vec![
+                        property("name", "hasName"),
+                        property("keyword", "hasKeyword"),
+                    ],
+                    ..Default::default()
+                },
+            )]),
+            default_prefixes: HashMap::from([
+                (
+                    "dsp".to_string(),
+                    "https://ns.dasch.swiss/repository#".to_string(),
+                ),
+                (
+                    "xsd".to_string(),
+                    "http://www.w3.org/2001/XMLSchema#".to_string(),
+                ),
+            ]),
+            ..Default::default()
+        };
+        assert!(from_str_with_config::<Dataset>(input, &config).is_err());
+        let lenient = DeserializerConfig {
+            lenient: true,
+            ..config
+        };
+        let dataset: Dataset = from_str_with_config(input, &lenient).unwrap();
+        assert_eq!(dataset.name, "Events");
+
+        // Strings are still borrowed from the input.
+        {
+            #[derive(Debug, Deserialize)]
+            struct Dataset<'a> {
+                id: &'a str,
+                name: &'a str,
+            }
+            let dataset: Dataset = from_str_with_config(input, &lenient).unwrap();
+            assert_eq!(dataset.id, "https://example.org/d1");
+            assert!(input
+                .as_bytes()
+                .as_ptr_range()
+                .contains(&dataset.name.as_ptr()));
+        }
+
+        // Declarations in the document override the default prefixes.
+        let input = r#"
+            @prefix dsp: <https://example.org/other#> .
+            <https://example.org/d1> dsp:hasName "Events" .
+        "#;
+        let error = from_str_with_config::<Dataset>(input, &lenient).unwrap_err();
+        assert!(error.to_string().contains("name"), "{error}");
+    }
+
+    #[test]
+    fn test_borrowed_strings() {
+        use std::borrow::Cow;
+
+        #[derive(Debug, Deserialize)]
+        struct Dataset<'a> {
+            id: &'a str,
+            name: &'a str,
+            #[serde(borrow)]
+            keyword: Cow<'a, str>,
+        }
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/d> dsp:hasName "Events" ;
+                dsp:hasKeyword "music" .
+        "#;
+        let dataset: Dataset = from_str(input).unwrap();
+        assert_eq!(dataset.id, "https://example.org/d");
+        assert_eq!(dataset.name, "Events");
+        let source = input.as_bytes().as_ptr_range();
+        assert!(source.contains(&dataset.id.as_ptr()));
+        assert!(source.contains(&dataset.name.as_ptr()));
+        assert!(matches!(dataset.keyword, Cow::Borrowed("music")));
+
+        // Strings are borrowed from the literal, not from other text that contains them.
+        let input = r#"
+            <https://example.org/Events> <https://example.org/name> "Events" ;
+                <https://example.org/keyword> """music""" .
+        "#;
+        let dataset: Dataset = from_str(input).unwrap();
+        let literal = input.find("\"Events\"").unwrap() + 1;
+        assert_eq!(dataset.name.as_ptr(), input[literal..].as_ptr());
+        assert!(matches!(dataset.keyword, Cow::Borrowed("music")));
+
+        // Escaped literals cannot be borrowed.
+        let input = r#"
+            <https://example.org/d> <https://example.org/name> "Events" ;
+                <https://example.org/keyword> "Caf\u00E9 concert" .
+        "#;
+        let dataset: Dataset = from_str(input).unwrap();
+        assert!(matches!(dataset.keyword, Cow::Owned(keyword) if keyword == "Café concert"));
+        let input = r#"
+            <https://example.org/d> <https://example.org/name> "Caf\u00E9" ;
+                <https://example.org/keyword> "music" .
+        "#;
+        let error = from_str::<Dataset>(input).unwrap_err().to_string();
+        assert!(error.contains("borrowed"), "{error}");
+    }
+
+    #[test]
+    fn test_from_str_lenient() {
+        use crate::from_str_lenient;
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/d> dsp:hasName "Events" .
+            <https://example.org/d> dsp:hasKeyword <not an iri> .
+            <https://example.org/d> dsp:hasKeyword "music" .
+        "#;
+        assert!(from_str::<Dataset>(input).is_err());
+        let (dataset, issues): (Dataset, _) = from_str_lenient(input).unwrap();
+        assert_eq!(dataset.name, "Events");
+        assert_eq!(dataset.keyword.as_deref(), Some("music"));
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].line, 5);
+        assert!(!issues[0].message.is_empty());
+
+        // The prefixes declared before a rejected line still apply after it, and strings are
+        // borrowed from the input.
+        #[derive(Debug, Deserialize)]
+        struct Borrowed<'a> {
+            name: &'a str,
+            keyword: Vec<&'a str>,
+        }
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            <https://example.org/d> dsp:hasKeyword "music" ;
+                dsp:hasKeyword <not an iri> .
+            <https://example.org/d> dsp:hasKeyword ) .
+            <https://example.org/d> dsp:hasName "Events" ;
+                dsp:hasKeyword "concert" .
+        "#;
+        let (dataset, issues): (Borrowed, _) = from_str_lenient(input).unwrap();
+        assert_eq!(dataset.name, "Events");
+        assert_eq!(dataset.keyword, ["concert"]);
+        assert!(input
+            .as_bytes()
+            .as_ptr_range()
+            .contains(&dataset.name.as_ptr()));
+        let lines: Vec<_> = issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(lines, [4, 5], "{issues:?}");
+
+        let (project, issues): (Project, _) = from_str_lenient(PROJECT).unwrap();
+        assert!(issues.is_empty());
+        assert_eq!(project, from_str(PROJECT).unwrap());
+    }
+
+    #[test]
+    fn test_from_str_for_subject() {
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/d1> dsp:hasName "First" .
+            <https://example.org/d2> dsp:hasName "Second" ; dsp:isPartOf <https://example.org/p> .
+        "#;
+
+        // Without a subject, the first one in document order is taken.
+        let dataset: Dataset = from_str(input).unwrap();
+        assert_eq!(dataset.name, "First");
+
+        let dataset: Dataset = from_str_for_subject(input, "https://example.org/d2").unwrap();
+        assert_eq!(dataset.id, "https://example.org/d2");
+        assert_eq!(dataset.name, "Second");
+
+        for missing in ["https://example.org/d3", "https://example.org/p"] {
+            let error = from_str_for_subject::<Dataset>(input, missing).unwrap_err();
+            assert!(
+                matches!(&error, Error::SubjectNotFound(iri) if iri == missing),
+                "{error}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_predicate_maps() {
+        const DSP: &str = "https://ns.dasch.swiss/repository#";
+
+        let subject = "https://ark.dasch.swiss/ark:/72163/1/081C";
+        let map: HashMap<String, Vec<String>> = from_str_for_subject(PROJECT, subject).unwrap();
+        assert_eq!(map.len(), 7);
+        assert_eq!(map[super::RDF_TYPE], [format!("{DSP}Project")]);
+        assert_eq!(map[&format!("{DSP}hasName")], ["Hôtel de Musique Bern"]);
+        assert_eq!(
+            map[&format!("{DSP}hasDescription")],
+            ["Das Hôtel de Musique", "The Hôtel de Musique"]
+        );
+        assert_eq!(map[&format!("{DSP}hasSize")], ["42"]);
+        assert_eq!(
+            map[&format!("{DSP}hasDataset")],
+            ["https://ark.dasch.swiss/ark:/72163/1/081C/d1"]
+        );
+
+        // Without a subject, the first one is read.
+        let map: HashMap<String, Vec<String>> = from_str(PROJECT).unwrap();
+        assert_eq!(map[&format!("{DSP}hasShortcode")], ["081C"]);
+    }
+
+    #[test]
+    fn test_literal_fields() {
+        use crate::value::Literal;
+
+        const WKT: &str = "http://www.opengis.net/ont/geosparql#wktLiteral";
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Place {
+            id: String,
+            location: Literal,
+        }
+
+        let config = SerializerConfig {
+            subjects: HashMap::from([(
+                "Place".to_string(),
+                SubjectConfig {
+                    struct_name: "Place".to_string(),
+                    rdf_type: "https://example.org/ns#Place".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "location".to_string(),
+                        rdf_property: "http://www.opengis.net/ont/geosparql#asWKT".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let mut de_config = DeserializerConfig::try_from(&config).unwrap();
+        de_config.strip_identifier_prefix = true;
+
+        let place = Place {
+            id: "bern".to_string(),
+            location: Literal::typed("POINT(7.4474 46.948)", WKT),
+        };
+        let output = to_string(&place, &config).unwrap();
+        assert!(
+            output.contains(&format!(r#""POINT(7.4474 46.948)"^^<{WKT}>"#)),
+            "{output}"
+        );
+        let round_trip: Place = from_str_with_config(&output, &de_config).unwrap();
+        assert_eq!(round_trip, place);
+
+        assert_eq!(
+            r#""Bern"@de"#.parse::<Literal>().unwrap(),
+            Literal::language_tagged("Bern", "de")
+        );
+        assert!("<https://example.org/bern>".parse::<Literal>().is_err());
+        let input = r#"<https://example.org/bern> <http://www.opengis.net/ont/geosparql#asWKT> <https://example.org/point> ."#;
+        assert!(from_str_with_config::<Place>(input, &de_config).is_err());
+    }
+
+    #[test]
+    fn test_from_str_by_type() {
+        const PROJECT_CLASS: &str = "https://ns.dasch.swiss/repository#Project";
+
+        let project: Project = from_str_by_type(PROJECT, PROJECT_CLASS).unwrap();
+        assert_eq!(project, from_str(PROJECT).unwrap());
+
+        let dataset: Dataset =
+            from_str_by_type(PROJECT, "https://ns.dasch.swiss/repository#Dataset").unwrap();
+        assert_eq!(dataset.name, "Events");
+
+        let error =
+            from_str_by_type::<Project>(PROJECT, "https://ns.dasch.swiss/repository#Person")
+                .unwrap_err();
+        assert!(matches!(error, Error::SubjectNotFound(_)), "{error}");
+
+        let input = format!(
+            "{PROJECT}\n<https://ark.dasch.swiss/ark:/72163/1/0801> a dsp:Project ; dsp:hasName \"Other\" ."
+        );
+        let error = from_str_by_type::<Project>(&input, PROJECT_CLASS).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::AmbiguousSubject(candidates) if candidates == &[
+                    "https://ark.dasch.swiss/ark:/72163/1/081C",
+                    "https://ark.dasch.swiss/ark:/72163/1/0801",
+                ]
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_from_str_all() {
+        const PROJECT_CLASS: &str = "https://ns.dasch.swiss/repository#Project";
+
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            id: String,
+            name: String,
+            #[serde(default)]
+            datasets: Vec<Dataset>,
+        }
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p2> a dsp:Project ; dsp:hasName "Second" .
+            <https://example.org/p1> a dsp:Project ; dsp:hasName "First" ;
+                dsp:hasDataset <https://example.org/d1>, <https://example.org/d2> .
+            <https://example.org/d1> a dsp:Dataset ; dsp:hasName "Letters" .
+            <https://example.org/d2> a dsp:Dataset ; dsp:hasName "Events" .
+            <https://example.org/p3> a dsp:Project ; dsp:hasName "Third" .
+        "#;
+        let projects: Vec<Project> = from_str_all(input, PROJECT_CLASS).unwrap();
+        assert_eq!(projects.len(), 3);
+        let names: Vec<_> = projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Second", "First", "Third"]);
+        assert_eq!(projects[1].id, "https://example.org/p1");
+        assert_eq!(projects[1].datasets.len(), 2);
+
+        let input = input.replace(r#"dsp:hasName "Third""#, "dsp:hasSize 3");
+        let error = from_str_all::<Project>(&input, PROJECT_CLASS).unwrap_err();
+        let Error::Contextual {
+            subject, source, ..
+        } = &error
+        else {
+            panic!("{error:?}");
+        };
+        assert_eq!(subject, "<https://example.org/p3>");
+        assert!(
+            source.to_string().contains("missing field `name`"),
+            "{error}"
+        );
+
+        let none: Vec<Project> = from_str_all(input.as_str(), "https://example.org/None").unwrap();
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_from_triples() {
+        let triple = |subject: &str, predicate: &str, object: OwnedObject| OwnedTriple {
+            subject: OwnedSubject::NamedNode(subject.to_string()),
+            predicate: format!("https://ns.dasch.swiss/repository#{predicate}"),
+            object,
+        };
+        let literal = |value: &str, datatype: &str| {
+            OwnedObject::Literal(OwnedLiteral::Typed {
+                value: value.to_string(),
+                datatype: format!("http://www.w3.org/2001/XMLSchema#{datatype}"),
+            })
+        };
+        let language = |value: &str, language: &str| {
+            OwnedObject::Literal(OwnedLiteral::LanguageTaggedString {
+                value: value.to_string(),
+                language: language.to_string(),
+            })
+        };
+        let project = "https://ark.dasch.swiss/ark:/72163/1/081C";
+        let dataset = "https://ark.dasch.swiss/ark:/72163/1/081C/d1";
+        let triples = vec![
+            triple(
+                project,
+                "hasName",
+                literal("Hôtel de Musique Bern", "string"),
+            ),
+            triple(
+                project,
+                "hasDescription",
+                language("Das Hôtel de Musique", "de"),
+            ),
+            triple(
+                project,
+                "hasDescription",
+                language("The Hôtel de Musique", "en"),
+            ),
+            triple(project, "hasShortcode", literal("081C", "string")),
+            triple(project, "public", literal("true", "boolean")),
+            triple(project, "hasSize", literal("42", "integer")),
+            triple(
+                project,
+                "hasDataset",
+                OwnedObject::NamedNode(dataset.to_string()),
+            ),
+            triple(dataset, "hasName", literal("Events", "string")),
+        ];
+
+        let project: Project = from_triples(triples.clone(), project).unwrap();
+        assert_eq!(project, from_str(PROJECT).unwrap());
+
+        let dataset: Dataset = from_triples(triples, dataset).unwrap();
+        assert_eq!(dataset.name, "Events");
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        let input = r#"<https://example.org/p> <https://example.org/name> "unterminated ."#;
+        assert!(from_str::<Dataset>(input).is_err());
+
+        let input = r#"@prefix ex: <https://example.org/> .
+ex:p ex:name "Events" .
+ex:p ex:name "Concerts" ex:name .
+"#;
+        let error = from_str::<Dataset>(input).unwrap_err();
+        assert!(matches!(error, Error::Parse { .. }), "{error}");
+        assert_eq!(error.line(), Some(3));
+        assert!(error.column().is_some());
+        assert!(error.to_string().starts_with("line 3, column "), "{error}");
+        assert_eq!(
+            from_ntriples_str::<Dataset>(input).unwrap_err().line(),
+            Some(1)
+        );
+
+        // The error of the parser is kept as the source.
+        let source = std::error::Error::source(&error).unwrap();
+        let source = source.downcast_ref::<rio_turtle::TurtleError>().unwrap();
+        let position = rio_api::parser::ParseError::textual_position(source).unwrap();
+        assert_eq!(position.line_number(), 3);
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let project: Project = from_slice(PROJECT.as_bytes()).unwrap();
+        assert_eq!(project, from_str(PROJECT).unwrap());
+
+        let mut invalid = PROJECT.as_bytes().to_vec();
+        invalid.extend_from_slice(&[0xC3, 0x28]);
+        let error = from_slice::<Project>(&invalid).unwrap_err();
+        assert!(matches!(error, Error::Utf8(_)), "{error}");
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let expected: Project = from_str(PROJECT).unwrap();
+
+        let project: Project = from_reader(std::io::Cursor::new(PROJECT)).unwrap();
+        assert_eq!(project, expected);
+
+        let path = std::env::temp_dir().join(format!("serde-rdf-{}.ttl", std::process::id()));
+        std::fs::write(&path, PROJECT).unwrap();
+        let project: Result<Project, _> = from_reader(std::fs::File::open(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(project.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_from_ntriples() {
+        let input = r#"
+<https://ark.dasch.swiss/ark:/72163/1/081C> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Project> .
+<https://ark.dasch.swiss/ark:/72163/1/081C> <https://ns.dasch.swiss/repository#hasName> "H\u00F4tel de Musique Bern"^^<http://www.w3.org/2001/XMLSchema#string> .
+<https://ark.dasch.swiss/ark:/72163/1/081C> <https://ns.dasch.swiss/repository#hasDescription> "Das H\u00F4tel de Musique"@de .
+<https://ark.dasch.swiss/ark:/72163/1/081C> <https://ns.dasch.swiss/repository#hasDescription> "The H\u00F4tel de Musique"@en .
+<https://ark.dasch.swiss/ark:/72163/1/081C> <https://ns.dasch.swiss/repository#hasShortcode> "081C" .
+<https://ark.dasch.swiss/ark:/72163/1/081C> <https://ns.dasch.swiss/repository#public> "true"^^<http://www.w3.org/2001/XMLSchema#boolean> .
+<https://ark.dasch.swiss/ark:/72163/1/081C> <https://ns.dasch.swiss/repository#hasSize> "42"^^<http://www.w3.org/2001/XMLSchema#integer> .
+<https://ark.dasch.swiss/ark:/72163/1/081C> <https://ns.dasch.swiss/repository#hasDataset> <https://ark.dasch.swiss/ark:/72163/1/081C/d1> .
+<https://ark.dasch.swiss/ark:/72163/1/081C/d1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Dataset> .
+<https://ark.dasch.swiss/ark:/72163/1/081C/d1> <https://ns.dasch.swiss/repository#hasName> "Events" .
+"#;
+        let expected: Project = from_str(PROJECT).unwrap();
+        let project: Project = from_ntriples_str(input).unwrap();
+        assert_eq!(project, expected);
+        let project: Project = from_ntriples_reader(std::io::Cursor::new(input)).unwrap();
+        assert_eq!(project, expected);
+
+        // Blank nodes keep their labels, so that references between them are preserved.
+        let input = r#"
+_:p <https://ns.dasch.swiss/repository#hasDataset> _:d .
+_:d <https://ns.dasch.swiss/repository#hasName> "\u0041rchive" .
+"#;
+        #[derive(Debug, Deserialize)]
+        struct Root {
+            dataset: Dataset,
+        }
+        let root: Root = from_ntriples_str(input).unwrap();
+        assert_eq!(root.dataset.id, "_:d");
+        assert_eq!(root.dataset.name, "Archive");
+
+        assert!(from_ntriples_str::<Project>(PROJECT).is_err());
+    }
+
+    #[test]
+    fn test_from_quads() {
+        use crate::{from_nquads_str, from_trig_str, Deserializer};
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/d> dsp:hasName "Events" .
+
+            <https://example.org/v1> {
+                <https://example.org/d> dsp:hasName "Concerts" .
+            }
+            <https://example.org/v2> {
+                <https://example.org/d> dsp:hasName "Concerts and Events" ;
+                    dsp:hasKeyword "music" .
+            }
+        "#;
+        let dataset: Dataset = from_trig_str(input, Some("https://example.org/v1")).unwrap();
+        assert_eq!(dataset.name, "Concerts");
+        assert_eq!(dataset.keyword, None);
+        let dataset: Dataset = from_trig_str(input, Some("https://example.org/v2")).unwrap();
+        assert_eq!(dataset.name, "Concerts and Events");
+        assert_eq!(dataset.keyword.as_deref(), Some("music"));
+
+        // All graphs merged, the default graph included.
+        #[derive(Debug, Deserialize)]
+        struct Names {
+            name: Vec<String>,
+        }
+        let names: Names = from_trig_str(input, None).unwrap();
+        assert_eq!(names.name, ["Events", "Concerts", "Concerts and Events"]);
+
+        let error = from_trig_str::<Dataset>(input, Some("https://example.org/v3"))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("<https://example.org/v3>"), "{error}");
+
+        let input = r#"
+            <https://example.org/d> <https://ns.dasch.swiss/repository#hasName> "Concerts" <https://example.org/v1> .
+            <https://example.org/d> <https://ns.dasch.swiss/repository#hasName> "Events" <https://example.org/v2> .
+        "#;
+        let dataset: Dataset = from_nquads_str(input, Some("https://example.org/v2")).unwrap();
+        assert_eq!(dataset.name, "Events");
+
+        #[derive(Debug, Deserialize)]
+        struct Version {
+            name: String,
+            graph_iri: Option<String>,
+        }
+        let config = DeserializerConfig {
+            subjects: HashMap::from([(
+                "Version".to_string(),
+                SubjectConfig {
+                    struct_name: "Version".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "https://ns.dasch.swiss/repository#hasName".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            graph_field: Some("graph_iri".to_string()),
+            ..Default::default()
+        };
+        let deserializer = Deserializer::from_nquads_str(input, Some("https://example.org/v1"))
+            .unwrap()
+            .with_config(&config);
+        let version = Version::deserialize(deserializer).unwrap();
+        assert_eq!(version.name, "Concerts");
+        assert_eq!(version.graph_iri.as_deref(), Some("https://example.org/v1"));
+    }
+
+    #[test]
+    fn test_stream_by_type() {
+        use crate::Deserializer;
+
+        let input = r#"
+<https://example.org/d1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Dataset> .
+<https://example.org/d1> <https://ns.dasch.swiss/repository#hasName> "Concerts" .
+<https://example.org/p> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Project> .
+<https://example.org/p> <https://ns.dasch.swiss/repository#hasName> "Hôtel de Musique" .
+<https://example.org/d2> <https://ns.dasch.swiss/repository#hasName> "Balls" .
+<https://example.org/d2> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Dataset> .
+<https://example.org/d2> <https://ns.dasch.swiss/repository#hasKeyword> "dance" .
+<https://example.org/d3> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Dataset> .
+<https://example.org/d3> <https://ns.dasch.swiss/repository#hasName> "Operas" .
+"#;
+        let mut stream = Deserializer::stream_by_type::<_, Dataset>(
+            std::io::Cursor::new(input),
+            "https://ns.dasch.swiss/repository#Dataset",
+        );
+        let dataset = stream.next().unwrap().unwrap();
+        assert_eq!(dataset.name, "Concerts");
+        // Only the first triple of the next subject is kept.
+        assert_eq!(stream.buffer.len(), 1);
+        let dataset = stream.next().unwrap().unwrap();
+        assert_eq!(dataset.name, "Balls");
+        assert_eq!(dataset.keyword.as_deref(), Some("dance"));
+        assert_eq!(stream.buffer.len(), 1);
+        let dataset = stream.next().unwrap().unwrap();
+        assert_eq!(dataset.id, "https://example.org/d3");
+        assert!(stream.buffer.is_empty());
+        assert!(stream.next().is_none());
+
+        let input = "<https://example.org/d1> <https://example.org/name> broken .\n";
+        let mut stream = Deserializer::stream_by_type::<_, Dataset>(
+            std::io::Cursor::new(input),
+            "https://ns.dasch.swiss/repository#Dataset",
+        );
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn test_from_rdfxml() {
+        use crate::{from_rdfxml_reader, from_rdfxml_str};
+
+        let input = r#"<?xml version="1.0" encoding="utf-8"?>
+            <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+                     xmlns:dsp="https://ns.dasch.swiss/repository#">
+              <dsp:Project rdf:about="https://ark.dasch.swiss/ark:/72163/1/081C">
+                <dsp:hasName rdf:datatype="http://www.w3.org/2001/XMLSchema#string">Hôtel de Musique Bern</dsp:hasName>
+                <dsp:hasDescription xml:lang="de">Das Hôtel de Musique</dsp:hasDescription>
+                <dsp:hasDescription xml:lang="en">The Hôtel de Musique</dsp:hasDescription>
+                <dsp:hasShortcode>081C</dsp:hasShortcode>
+                <dsp:public rdf:datatype="http://www.w3.org/2001/XMLSchema#boolean">true</dsp:public>
+                <dsp:hasSize rdf:datatype="http://www.w3.org/2001/XMLSchema#integer">42</dsp:hasSize>
+                <dsp:hasDataset>
+                  <dsp:Dataset rdf:about="https://ark.dasch.swiss/ark:/72163/1/081C/d1">
+                    <dsp:hasName>Events</dsp:hasName>
+                  </dsp:Dataset>
+                </dsp:hasDataset>
+              </dsp:Project>
+            </rdf:RDF>"#;
+        let expected: Project = from_str(PROJECT).unwrap();
+        let project: Project = from_rdfxml_str(input).unwrap();
+        assert_eq!(project, expected);
+        let project: Project = from_rdfxml_reader(std::io::Cursor::new(input)).unwrap();
+        assert_eq!(project, expected);
+
+        let input = r#"<?xml version="1.0" encoding="utf-8"?>
+            <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+                     xmlns:dsp="https://ns.dasch.swiss/repository#">
+              <rdf:Description rdf:about="https://example.org/d">
+                <dsp:hasName rdf:parseType="Literal"><b>Events</b></dsp:hasName>
+              </rdf:Description>
+            </rdf:RDF>"#;
+        let error = from_rdfxml_str::<Dataset>(input).unwrap_err().to_string();
+        assert!(error.contains("rdf:parseType"), "{error}");
+    }
+
+    #[test]
+    fn test_format_from_media_type() {
+        use crate::Format;
+
+        let cases = [
+            ("text/turtle", Some(Format::Turtle)),
+            ("text/turtle; charset=utf-8", Some(Format::Turtle)),
+            ("Text/Turtle", Some(Format::Turtle)),
+            ("application/x-turtle", Some(Format::Turtle)),
+            ("application/n-triples", Some(Format::NTriples)),
+            ("application/n-quads", Some(Format::NQuads)),
+            ("text/x-nquads", Some(Format::NQuads)),
+            ("application/trig", Some(Format::TriG)),
+            ("application/rdf+xml", Some(Format::RdfXml)),
+            (
+                "application/ld+json;profile=\"http://www.w3.org/ns/json-ld#expanded\"",
+                Some(Format::JsonLd),
+            ),
+            ("application/json", None),
+            ("text/plain", None),
+            ("", None),
+        ];
+        for (media_type, expected) in cases {
+            assert_eq!(
+                Format::from_media_type(media_type),
+                expected,
+                "{media_type}"
+            );
+        }
+
+        assert_eq!(Format::from_extension("ttl"), Some(Format::Turtle));
+        assert_eq!(Format::from_extension(".NQ"), Some(Format::NQuads));
+        assert_eq!(Format::from_extension("owl"), None);
+        assert_eq!(Format::NTriples.media_type(), "application/n-triples");
+        assert_eq!(Format::RdfXml.extension(), "rdf");
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        use crate::{from_str_with_format, to_string_with_format, Format};
+
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Place {
+            id: String,
+            name: String,
+            size: u32,
+        }
+
+        let config = || SerializerConfig {
+            subjects: HashMap::from([(
+                "Place".to_string(),
+                SubjectConfig {
+                    struct_name: "Place".to_string(),
+                    rdf_type: "https://example.org/ns#Place".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: ["name", "size"]
+                        .map(|field| PropertyConfig {
+                            struct_field: field.to_string(),
+                            rdf_property: format!("https://example.org/ns#{field}"),
+                            ..Default::default()
+                        })
+                        .to_vec(),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let place = Place {
+            id: "bern".to_string(),
+            name: "Bern".to_string(),
+            size: 42,
+        };
+        for format in [Format::Turtle, Format::NTriples] {
+            let output = to_string_with_format(&place, &config(), format).unwrap();
+            let read: Place = from_str_with_format(&output, format).unwrap();
+            assert_eq!(read.id, "https://example.org/bern", "{format}");
+            assert_eq!((read.name.as_str(), read.size), ("Bern", 42), "{format}");
+        }
+
+        let output = to_string_with_format(&place, &config(), Format::NTriples).unwrap();
+        assert!(output.lines().all(|line| line.ends_with(" .")));
+        assert!(
+            output.contains("<https://example.org/bern> <https://example.org/ns#name> \"Bern\"")
+        );
+
+        let err = to_string_with_format(&place, &config(), Format::JsonLd).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnsupportedFormat {
+                format: Format::JsonLd,
+                feature: None,
+                ..
+            }
+        ));
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        #[cfg(not(feature = "xml"))]
+        assert_eq!(
+            from_str_with_format::<Place>("", Format::RdfXml)
+                .unwrap_err()
+                .to_string(),
+            "reading RDF/XML requires the `xml` feature of serde_rdf"
+        );
+    }
+
+    #[test]
+    fn test_from_reader_io_error() {
+        struct Failing;
+
+        impl std::io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk on fire"))
+            }
+        }
+
+        let error = from_reader::<_, Project>(Failing).unwrap_err();
+        assert!(matches!(error, Error::Io(_)), "{error}");
+    }
+
+    #[test]
+    fn test_vec_fields() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Iri(String);
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Project {
+            id: String,
+            keywords: Vec<String>,
+            licenses: Vec<Iri>,
+            datasets: Vec<Dataset>,
+        }
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> a dsp:Project ;
+                dsp:hasKeyword "music" ;
+                dsp:hasLicense <https://creativecommons.org/licenses/by/4.0/> ;
+                dsp:hasKeyword "Bern", "concerts" ;
+                dsp:hasDataset <https://example.org/d2>, <https://example.org/d1> .
+
+            <https://example.org/d1> dsp:hasName "First" .
+            <https://example.org/d2> dsp:hasName "Second" ; dsp:hasKeyword "scans" .
+        "#;
+
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(project.keywords, ["music", "Bern", "concerts"]);
+        assert_eq!(
+            project.licenses,
+            [Iri(
+                "https://creativecommons.org/licenses/by/4.0/".to_string()
+            )]
+        );
+        assert_eq!(
+            project.datasets,
+            [
+                Dataset {
+                    id: "https://example.org/d2".to_string(),
+                    name: "Second".to_string(),
+                    keyword: Some("scans".to_string()),
+                },
+                Dataset {
+                    id: "https://example.org/d1".to_string(),
+                    name: "First".to_string(),
+                    keyword: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct LangString(HashMap<String, String>);
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Project {
+            shortcode: Option<String>,
+            size: Option<i64>,
+            description: Option<LangString>,
+            dataset: Option<Dataset>,
+            #[serde(default)]
+            keywords: Vec<String>,
+            #[serde(default)]
+            public: bool,
+        }
+
+        let input = r#"<https://example.org/p> a <https://ns.dasch.swiss/repository#Project> ."#;
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                shortcode: None,
+                size: None,
+                description: None,
+                dataset: None,
+                keywords: Vec::new(),
+                public: false,
+            }
+        );
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> dsp:hasShortcode "081C" ;
+                dsp:hasSize 42 ;
+                dsp:hasDescription "Das Hôtel"@de ;
+                dsp:hasDataset <https://example.org/d1> ;
+                dsp:hasKeyword "music" ;
+                dsp:public true .
+
+            <https://example.org/d1> dsp:hasName "Events" .
+        "#;
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                shortcode: Some("081C".to_string()),
+                size: Some(42),
+                description: Some(LangString(HashMap::from([(
+                    "de".to_string(),
+                    "Das Hôtel".to_string()
+                )]))),
+                dataset: Some(Dataset {
+                    id: "https://example.org/d1".to_string(),
+                    name: "Events".to_string(),
+                    keyword: None,
+                }),
+                keywords: vec!["music".to_string()],
+                public: true,
+            }
+        );
+
+        let error = from_str::<Dataset>(r#"<https://example.org/d> <https://example.org/x> "y" ."#)
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("missing field `name`"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_cardinality() {
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            shortcode: String,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Projects {
+            shortcodes: Vec<String>,
+        }
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> dsp:hasShortcode "081C", "0801" .
+        "#;
+        let error = from_str::<Project>(input).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::CardinalityViolation { subject, predicate, expected: 1, found: 2 }
+                    if subject == "<https://example.org/p>"
+                        && predicate == "https://ns.dasch.swiss/repository#hasShortcode"
+            ),
+            "{error}"
+        );
+
+        let config = DeserializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "shortcode".to_string(),
+                        rdf_property: "dsp:hasShortcode".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            namespaces: HashMap::from([(
+                "dsp".to_string(),
+                "https://ns.dasch.swiss/repository#".to_string(),
+            )]),
+            first_wins: true,
+            ..Default::default()
+        };
+        let project: Project = from_str_with_config(input, &config).unwrap();
+        assert_eq!(project.shortcode, "081C");
+
+        let projects: Projects = from_str(input).unwrap();
+        assert_eq!(projects.shortcodes, ["081C", "0801"]);
+    }
+
+    #[test]
+    fn test_optional_cardinality() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Project {
+            shortcode: Option<String>,
+            dataset: Option<Dataset>,
+        }
+
+        let input = |shortcodes: &str, datasets: &str| {
+            format!(
+                r#"
+                @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+                <https://example.org/p> a dsp:Project {shortcodes} {datasets} .
+
+                <https://example.org/d1> dsp:hasName "Events" .
+                <https://example.org/d2> dsp:hasName "Letters" .
+                "#
+            )
+        };
+        let dataset = |id: &str, name: &str| Dataset {
+            id: format!("https://example.org/{id}"),
+            name: name.to_string(),
+            keyword: None,
+        };
+        let one_shortcode = r#"; dsp:hasShortcode "081C""#;
+        let two_shortcodes = r#"; dsp:hasShortcode "081C", "0801""#;
+        let one_dataset = "; dsp:hasDataset <https://example.org/d1>";
+        let two_datasets = "; dsp:hasDataset <https://example.org/d1>, <https://example.org/d2>";
+
+        let project: Project = from_str(&input("", "")).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                shortcode: None,
+                dataset: None,
+            }
+        );
+
+        let project: Project = from_str(&input(one_shortcode, one_dataset)).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                shortcode: Some("081C".to_string()),
+                dataset: Some(dataset("d1", "Events")),
+            }
+        );
+
+        for (shortcodes, datasets, predicate) in [
+            (two_shortcodes, one_dataset, "hasShortcode"),
+            (one_shortcode, two_datasets, "hasDataset"),
+        ] {
+            let error = from_str::<Project>(&input(shortcodes, datasets)).unwrap_err();
+            assert!(
+                matches!(
+                    &error,
+                    Error::CardinalityViolation { predicate: p, expected: 1, found: 2, .. }
+                        if p == &format!("https://ns.dasch.swiss/repository#{predicate}")
+                ),
+                "{error}"
+            );
+        }
+
+        // Unless the first one wins.
+        let property = |field: &str, predicate: &str| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("dsp:{predicate}"),
+            ..Default::default()
+        };
+        let subject = |name: &str, properties| SubjectConfig {
+            struct_name: name.to_string(),
+            identifier_field: "id".to_string(),
+            properties,
+            ..Default::default()
+        };
+        let config = DeserializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    subject(
+                        "Project",
+                        vec![
+                            property("shortcode", "hasShortcode"),
+                            property("dataset", "hasDataset"),
+                        ],
+                    ),
+                ),
+                (
+                    "Dataset".to_string(),
+                    subject("Dataset", vec![property("name", "hasName")]),
+                ),
+            ]),
+            namespaces: HashMap::from([(
+                "dsp".to_string(),
+                "https://ns.dasch.swiss/repository#".to_string(),
+            )]),
+            first_wins: true,
+            ..Default::default()
+        };
+        let project: Project =
+            from_str_with_config(&input(two_shortcodes, two_datasets), &config).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                shortcode: Some("081C".to_string()),
+                dataset: Some(dataset("d1", "Events")),
+            }
+        );
+    }
+
+    #[test]
+    fn test_default_values() {
+        fn unknown() -> String {
+            "unknown".to_string()
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Project {
+            size: u32,
+            #[serde(default = "unknown")]
+            name: String,
+            #[serde(default)]
+            public: bool,
+        }
+
+        let property = |field: &str, predicate: &str, default: Option<&str>| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("dsp:{predicate}"),
+            default_literal: default.map(str::to_string),
+            ..Default::default()
+        };
+        let config = |size: Option<&str>, public: Option<&str>| DeserializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    properties: vec![
+                        property("size", "hasSize", size),
+                        property("name", "hasName", None),
+                        property("public", "isPublic", public),
+                    ],
+                    ..Default::default()
+                },
+            )]),
+            namespaces: HashMap::from([
+                (
+                    "dsp".to_string(),
+                    "https://ns.dasch.swiss/repository#".to_string(),
+                ),
+                (
+                    "xsd".to_string(),
+                    "http://www.w3.org/2001/XMLSchema#".to_string(),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let complete = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> dsp:hasSize 3 ; dsp:hasName "Events" ; dsp:isPublic true .
+        "#;
+        let incomplete = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> a dsp:Project .
+        "#;
+
+        // A triple takes precedence over the defaults.
+        let project: Project =
+            from_str_with_config(complete, &config(Some("0"), Some("false"))).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                size: 3,
+                name: "Events".to_string(),
+                public: true,
+            }
+        );
+
+        // The default of the config over the one of serde.
+        let project: Project = from_str_with_config(
+            incomplete,
+            &config(Some(r#""7"^^xsd:integer"#), Some("true")),
+        )
+        .unwrap();
+        assert_eq!(
+            project,
+            Project {
+                size: 7,
+                name: "unknown".to_string(),
+                public: true,
+            }
+        );
+
+        // The default of serde over an error.
+        let project: Project = from_str_with_config(incomplete, &config(Some("0"), None)).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                size: 0,
+                name: "unknown".to_string(),
+                public: false,
+            }
+        );
+
+        let error = from_str_with_config::<Project>(incomplete, &config(None, None)).unwrap_err();
+        assert!(
+            error.to_string().contains("missing field `size`"),
+            "{error}"
+        );
+
+        let error = from_str_with_config::<Project>(incomplete, &config(Some("dsp:Zero"), None))
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("invalid default_literal of field size"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_error_context() {
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            datasets: Vec<Dataset>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Dataset {
+            name: String,
+            size: u8,
+        }
+
+        let input = |size: &str| {
+            format!(
+                r#"
+                @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+                <https://example.org/p> a dsp:Project ;
+                    dsp:hasDataset <https://example.org/d1>, <https://example.org/d2> .
+                <https://example.org/d1> dsp:hasName "Events" ; dsp:hasSize 3 .
+                <https://example.org/d2> dsp:hasName "Letters" {size} .
+                "#
+            )
+        };
+
+        let error = from_str::<Project>(&input("; dsp:hasSize 300")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "while deserializing <https://example.org/d2> (Dataset.size): 300 is out of range \
+             for u8 in field size"
+        );
+        assert!(
+            matches!(error.inner(), Error::OutOfRange { ty: "u8", .. }),
+            "{error}"
+        );
+        let source = std::error::Error::source(&error).unwrap();
+        assert!(
+            source.to_string().starts_with("300 is out of range"),
+            "{source}"
+        );
+
+        let error = from_str::<Project>(&input("")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "while deserializing <https://example.org/d2>: missing field `size`"
+        );
+    }
+
+    #[test]
+    fn test_configured_datatype() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Invoice {
+            id: String,
+            amount: String,
+        }
+
+        let config = DeserializerConfig {
+            subjects: HashMap::from([(
+                "Invoice".to_string(),
+                SubjectConfig {
+                    struct_name: "Invoice".to_string(),
+                    identifier_field: "id".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "amount".to_string(),
+                        rdf_property: "https://example.org/ns#amount".to_string(),
+                        datatype: Some("xsd:decimal".parse().unwrap()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let input = r#"
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+            <https://example.org/i1> <https://example.org/ns#amount> "0.10"^^xsd:decimal .
+        "#;
+        let invoice: Invoice = from_str_with_config(input, &config).unwrap();
+        assert_eq!(invoice.amount, "0.10");
+
+        let input = r#"
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+            <https://example.org/i1> <https://example.org/ns#amount> "0.10"^^xsd:double .
+        "#;
+        let error = from_str_with_config::<Invoice>(input, &config).unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::UnexpectedDatatype { expected, found, subject, .. }
+                    if expected == "http://www.w3.org/2001/XMLSchema#decimal"
+                        && found == "http://www.w3.org/2001/XMLSchema#double"
+                        && subject == "<https://example.org/i1>"
+            ),
+            "{error}"
+        );
+
+        // Simple literals are left to the target type.
+        let input = r#"<https://example.org/i1> <https://example.org/ns#amount> "0.10" ."#;
+        let invoice: Invoice = from_str_with_config(input, &config).unwrap();
+        assert_eq!(invoice.amount, "0.10");
+    }
+
+    #[test]
+    fn test_check_counts() {
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            names: Vec<String>,
+        }
+
+        let config = |check_counts| DeserializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "names".to_string(),
+                        rdf_property: "https://ns.dasch.swiss/repository#hasName".to_string(),
+                        min_count: Some(1),
+                        max_count: Some(2),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            check_counts,
+            ..Default::default()
+        };
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            <https://example.org/p> dsp:hasName "a", "b", "c" .
+        "#;
+        let project: Project = from_str_with_config(input, &config(false)).unwrap();
+        assert_eq!(project.names.len(), 3);
+
+        let error = from_str_with_config::<Project>(input, &config(true)).unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::CardinalityViolation { subject, expected: 2, found: 3, .. }
+                    if subject == "<https://example.org/p>"
+            ),
+            "{error}"
+        );
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            <https://example.org/p> dsp:hasName "a" .
+        "#;
+        let project: Project = from_str_with_config(input, &config(true)).unwrap();
+        assert_eq!(project.names, ["a"]);
+    }
+
+    #[test]
+    fn test_inverse_property() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Project {
+            id: String,
+            datasets: Vec<Dataset>,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Dataset {
+            id: String,
+            title: String,
+        }
+
+        let mut config = SerializerConfig::builder()
+            .prefix("dsp", "https://ns.dasch.swiss/repository#")
+            .default_identifier_prefix("https://ark.dasch.swiss/ark:/72163/1/")
+            .subject(
+                SubjectConfig::builder("Project")
+                    .rdf_type("dsp:Project")
+                    .identifier("id", "")
+                    .property("datasets", "dsp:isPartOf")
+                    .build(),
+            )
+            .subject(
+                SubjectConfig::builder("Dataset")
+                    .rdf_type("dsp:Dataset")
+                    .identifier("id", "")
+                    .property("title", "dsp:hasTitle")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        config.subjects.get_mut("Project").unwrap().properties[0].inverse = true;
+        let config = DeserializerConfig::try_from(&config).unwrap();
+
+        // The datasets come first, but the project is the root as they refer to it.
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            @prefix ark: <https://ark.dasch.swiss/ark:/72163/1/> .
+
+            ark:081C-1 a dsp:Dataset ;
+                dsp:hasTitle "Letters" ;
+                dsp:isPartOf ark:081C .
+            ark:081C-2 a dsp:Dataset ;
+                dsp:hasTitle "Photos" ;
+                dsp:isPartOf ark:081C .
+            ark:081C a dsp:Project .
+        "#;
+        let project: Project = from_str_with_config(input, &config).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                id: "081C".to_string(),
+                datasets: vec![
+                    Dataset {
+                        id: "081C-1".to_string(),
+                        title: "Letters".to_string(),
+                    },
+                    Dataset {
+                        id: "081C-2".to_string(),
+                        title: "Photos".to_string(),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_property_aliases() {
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            name: String,
+        }
+
+        let config = |prefer_primary_property| DeserializerConfig {
+            namespaces: HashMap::from([(
+                "dsp".to_string(),
+                "https://ns.dasch.swiss/repository#".to_string(),
+            )]),
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    rdf_type: "https://ns.dasch.swiss/repository#Project".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "dsp:hasName".to_string(),
+                        aliases: vec!["dsp:hasTitle".to_string()],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            prefer_primary_property,
+            ..Default::default()
+        };
+        let input = |statements: &str| {
+            format!(
+                "@prefix dsp: <https://ns.dasch.swiss/repository#> .\n\
+                 <https://example.org/p> a dsp:Project ; {statements} ."
+            )
+        };
+
+        // Old data only has the alias.
+        let project: Project =
+            from_str_with_config(&input("dsp:hasTitle \"Events\""), &config(false)).unwrap();
+        assert_eq!(project.name, "Events");
+
+        let both = input("dsp:hasName \"Events\" ; dsp:hasTitle \"Events\"");
+        let project: Project = from_str_with_config(&both, &config(false)).unwrap();
+        assert_eq!(project.name, "Events");
+
+        let conflicting = input("dsp:hasName \"Events\" ; dsp:hasTitle \"Old events\"");
+        let error = from_str_with_config::<Project>(&conflicting, &config(false))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("conflicting values of <https://example.org/p> for field Project.name"),
+            "{error}"
+        );
+        let project: Project = from_str_with_config(&conflicting, &config(true)).unwrap();
+        assert_eq!(project.name, "Events");
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let kind = |input: &str| from_str::<Dataset>(input).unwrap_err().kind();
+        assert_eq!(kind("<https://example.org/d> ."), ErrorKind::Syntax);
+        assert_eq!(
+            kind(r#"<https://example.org/d> <https://example.org/ns#name> "a", "b" ."#),
+            ErrorKind::CardinalityViolation
+        );
+        let error = from_str_with_config::<Dataset>(PROJECT, &Default::default()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::MissingSubjectConfig);
+        assert!(error.is_config_error() && !error.is_data_error());
+
+        let errors = [
+            (Error::Message("custom".to_string()), ErrorKind::Other),
+            (Error::Io(std::io::Error::other("closed")), ErrorKind::Io),
+            (
+                Error::CannotSerializePrimitive("f32"),
+                ErrorKind::Unsupported,
+            ),
+            (
+                Error::SubjectNotFound("https://example.org/p".to_string()),
+                ErrorKind::SubjectNotFound,
+            ),
+            (
+                Error::AmbiguousSubject(Vec::new()),
+                ErrorKind::AmbiguousSubject,
+            ),
+            (
+                Error::OutOfRange {
+                    field: None,
+                    value: "256".to_string(),
+                    ty: "u8",
+                },
+                ErrorKind::OutOfRange,
+            ),
+            (
+                Error::UnknownVariant {
+                    value: "<https://example.org/Closed>".to_string(),
+                    expected: &["Active"],
+                },
+                ErrorKind::UnknownVariant,
+            ),
+            (
+                Error::CyclicReference { path: Vec::new() },
+                ErrorKind::CyclicReference,
+            ),
+            (
+                Error::InvalidLexicalValue {
+                    value: "yes".to_string(),
+                    datatype: "xsd:boolean",
+                },
+                ErrorKind::InvalidLexicalValue,
+            ),
+            (
+                Error::UnexpectedDatatype {
+                    expected: String::new(),
+                    found: String::new(),
+                    subject: String::new(),
+                    predicate: String::new(),
+                },
+                ErrorKind::UnexpectedDatatype,
+            ),
+            (
+                Error::InvalidIri {
+                    iri: "p".to_string(),
+                    context: "map key".to_string(),
+                },
+                ErrorKind::InvalidIri,
+            ),
+            (
+                Error::MissingPropertyConfig {
+                    struct_name: "Project".to_string(),
+                    field: "shortcode".to_string(),
+                },
+                ErrorKind::MissingPropertyConfig,
+            ),
+            (
+                Error::IdentifierFieldMissing {
+                    struct_name: "Project".to_string(),
+                    identifier_field: "shortCode".to_string(),
+                    available_fields: vec!["shortcode".to_string()],
+                },
+                ErrorKind::IdentifierFieldMissing,
+            ),
+            (
+                Error::UnknownPrefix {
+                    prefix: "dsp".to_string(),
+                    value: "dsp:hasName".to_string(),
+                    known: Vec::new(),
+                },
+                ErrorKind::UnknownPrefix,
+            ),
+        ];
+        for (error, kind) in errors {
+            assert_eq!(error.kind(), kind, "{error}");
+            // The context does not change the kind.
+            let error = error.context("deserializing", || "<https://example.org/p>".into(), None);
+            assert_eq!(error.kind(), kind, "{error}");
+            assert_eq!(error.is_io(), kind == ErrorKind::Io);
+        }
+
+        let error = from_slice::<Dataset>(b"<https://example.org/d> \xff").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Utf8);
+        assert!(error.is_data_error() && !error.is_config_error());
+    }
+
+    #[test]
+    fn test_deny_unknown_predicates() {
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            name: String,
+            dataset: Dataset,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Dataset {
+            name: String,
+        }
+
+        let subject = |name: &str, properties: &[&str]| SubjectConfig {
+            struct_name: name.to_string(),
+            properties: properties
+                .iter()
+                .map(|field| PropertyConfig {
+                    struct_field: field.to_string(),
+                    rdf_property: format!("https://example.org/ns#{field}"),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let config = DeserializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    subject("Project", &["name", "dataset"]),
+                ),
+                ("Dataset".to_string(), subject("Dataset", &["name"])),
+            ]),
+            deny_unknown_predicates: true,
+            ..Default::default()
+        };
+        let input = |project: &str, dataset: &str| {
+            format!(
+                r#"
+                @prefix ex: <https://example.org/ns#> .
+
+                <https://example.org/p> a ex:Project ; ex:name "Project" ;
+                    ex:dataset <https://example.org/d> {project} .
+                <https://example.org/d> a ex:Dataset ; ex:name "Dataset" {dataset} .
+                "#
+            )
+        };
+
+        let project: Project = from_str_with_config(&input("", ""), &config).unwrap();
+        assert_eq!(project.dataset.name, "Dataset");
+
+        let error = from_str_with_config::<Project>(&input(r#"; ex:extra "x""#, ""), &config)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains(
+                "unknown predicates on <https://example.org/p>: <https://example.org/ns#extra>"
+            ),
+            "{error}"
+        );
+        let error =
+            from_str_with_config::<Project>(&input("", r#"; ex:size 1 ; ex:extra "x""#), &config)
+                .unwrap_err()
+                .to_string();
+        assert!(
+            error.contains(
+                "on <https://example.org/d>: <https://example.org/ns#extra>, <https://example.org/ns#size>"
+            ),
+            "{error}"
+        );
+
+        let permissive = DeserializerConfig {
+            deny_unknown_predicates: false,
+            ..config
+        };
+        let project: Project = from_str_with_config(
+            &input(r#"; ex:extra "x""#, r#"; ex:extra "x""#),
+            &permissive,
+        )
+        .unwrap();
+        assert_eq!(project.name, "Project");
+    }
+
+    #[test]
+    fn test_blank_nodes() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Place {
+            id: Option<String>,
+            name: String,
+            address: Option<Address>,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Deserialize)]
+        struct Address {
+            id: String,
+            street: String,
+            city: Option<City>,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Deserialize)]
+        struct City {
+            id: Option<String>,
+            name: String,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Places {
+            place: Vec<Place>,
+        }
+
+        let input = r#"
+            @prefix ex: <https://example.org/ns#> .
+
+            <https://example.org/places> ex:place _:hotel, _:theatre .
+            _:hotel ex:name "Hôtel de Musique" ; ex:address _:a .
+            _:theatre ex:name "Stadttheater" ; ex:address _:a .
+            _:a ex:street "Hotelgasse 10" ; ex:city _:bern .
+            _:bern ex:name "Bern" .
+        "#;
+        let places: Places = from_str(input).unwrap();
+        let address = Address {
+            id: "_:a".to_string(),
+            street: "Hotelgasse 10".to_string(),
+            city: Some(City {
+                id: None,
+                name: "Bern".to_string(),
+            }),
+        };
+        assert_eq!(
+            places.place,
+            [
+                Place {
+                    id: None,
+                    name: "Hôtel de Musique".to_string(),
+                    address: Some(address.clone()),
+                },
+                Place {
+                    id: None,
+                    name: "Stadttheater".to_string(),
+                    address: Some(address),
+                },
+            ]
+        );
+
+        let input = r#"
+            <https://example.org/p> <https://example.org/ns#name> "Kornhaus" .
+        "#;
+        let place: Place = from_str(input).unwrap();
+        assert_eq!(place.id.as_deref(), Some("https://example.org/p"));
+
+        let config = DeserializerConfig {
+            subjects: HashMap::from([(
+                "City".to_string(),
+                SubjectConfig {
+                    struct_name: "City".to_string(),
+                    identifier_field: "id".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "https://example.org/ns#name".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let input = r#"_:bern <https://example.org/ns#name> "Bern" ."#;
+        let city: City = from_str_with_config(input, &config).unwrap();
+        assert_eq!(city.id, None);
+        let labels = DeserializerConfig {
+            blank_node_labels: true,
+            ..config
+        };
+        let city: City = from_str_with_config(input, &labels).unwrap();
+        assert_eq!(city.id.as_deref(), Some("_:bern"));
+    }
+
+    #[test]
+    fn test_cyclic_references() {
+        #[derive(Debug, Deserialize)]
+        struct Person {
+            name: String,
+            knows: Vec<Person>,
+        }
+
+        let input = r#"
+            @prefix ex: <https://example.org/ns#> .
+
+            <https://example.org/a> ex:name "A" ; ex:knows <https://example.org/b> .
+            <https://example.org/b> ex:name "B" ; ex:knows <https://example.org/a> .
+        "#;
+        let error = from_str::<Person>(input).unwrap_err();
+        let Error::CyclicReference { path } = &error else {
+            panic!("unexpected error: {error}");
+        };
+        assert_eq!(
+            path,
+            &[
+                "<https://example.org/a>",
+                "<https://example.org/b>",
+                "<https://example.org/a>"
+            ]
+        );
+
+        let input = r#"
+            <https://example.org/a> <https://example.org/ns#name> "A" ;
+                <https://example.org/ns#knows> <https://example.org/a> .
+        "#;
+        let error = from_str::<Person>(input).unwrap_err();
+        assert!(
+            matches!(&error, Error::CyclicReference { path } if path.len() == 2),
+            "{error}"
+        );
+
+        // References read as IRIs are not followed.
+        #[derive(Debug, Deserialize)]
+        struct Acquaintance {
+            name: String,
+            knows: Vec<String>,
+        }
+        let person: Acquaintance = from_str(input).unwrap();
+        assert_eq!(person.knows, ["https://example.org/a"]);
+
+        // A chain of three nodes without cycles.
+        #[derive(Debug, Deserialize)]
+        struct Node {
+            next: Option<Box<Node>>,
+        }
+        let input = r#"
+            <https://example.org/a> <https://example.org/ns#next> <https://example.org/b> .
+            <https://example.org/b> <https://example.org/ns#next> <https://example.org/c> .
+            <https://example.org/c> <https://example.org/ns#name> "C" .
+        "#;
+        let config = |max_depth| DeserializerConfig {
+            subjects: HashMap::from([(
+                "Node".to_string(),
+                SubjectConfig {
+                    struct_name: "Node".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "next".to_string(),
+                        rdf_property: "https://example.org/ns#next".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            max_depth: Some(max_depth),
+            ..Default::default()
+        };
+        assert!(from_str::<Node>(input).is_ok());
+        assert!(from_str_with_config::<Node>(input, &config(3)).is_ok());
+        let error = from_str_with_config::<Node>(input, &config(2)).unwrap_err();
+        assert!(error.to_string().contains("deeper than 2"), "{error}");
+    }
+
+    #[test]
+    fn test_enum_variants() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Status {
+            Active,
+            Archived,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            status: Status,
+        }
+
+        let input = r#"
+            <https://example.org/p> <https://ns.example.org/status> <https://ns.example.org/Active> .
+        "#;
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(project.status, Status::Active);
+        let input = r#"
+            <https://example.org/p> <https://ns.example.org/status> <https://ns.example.org/status/Archived> .
+        "#;
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(project.status, Status::Archived);
+        let input = r#"
+            <https://example.org/p> <https://ns.example.org/status> "Archived" .
+        "#;
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(project.status, Status::Archived);
+
+        let input = r#"
+            <https://example.org/p> <https://ns.example.org/status> <https://ns.example.org/Closed> .
+        "#;
+        let error = from_str::<Project>(input).unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::UnknownVariant { value, expected }
+                    if value == "<https://ns.example.org/Closed>"
+                        && *expected == ["Active", "Archived"]
+            ),
+            "{error}"
+        );
+
+        // An explicit mapping takes precedence over the local name.
+        let config = DeserializerConfig {
+            namespaces: HashMap::from([("ex".to_string(), "https://ns.example.org/".to_string())]),
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "status".to_string(),
+                        rdf_property: "ex:status".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            variants: HashMap::from([
+                ("ex:Closed".to_string(), "Archived".to_string()),
+                ("ex:Active".to_string(), "Archived".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let project: Project = from_str_with_config(input, &config).unwrap();
+        assert_eq!(project.status, Status::Archived);
+        let input = r#"
+            <https://example.org/p> <https://ns.example.org/status> <https://ns.example.org/Active> .
+        "#;
+        let project: Project = from_str_with_config(input, &config).unwrap();
+        assert_eq!(project.status, Status::Archived);
+    }
+
+    #[test]
+    fn test_typed_enums() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Summary {
+            id: String,
+            name: String,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Resource {
+            Project(Summary),
+            Dataset(Dataset),
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(tag = "type")]
+        enum Tagged {
+            Project { id: String, shortcode: String },
+            Dataset { id: String, name: String },
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(untagged)]
+        enum Untagged {
+            Project { shortcode: String, name: String },
+            Dataset { name: String },
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Catalog<T> {
+            resources: Vec<T>,
+        }
+
+        let input = r#"
+            @prefix ex: <https://example.org/ns#> .
+
+            <https://example.org/catalog> ex:hasResource <https://example.org/p>, <https://example.org/d> .
+            <https://example.org/p> a ex:Project ;
+                ex:hasName "Hôtel de Musique" ;
+                ex:hasShortcode "081C" .
+            <https://example.org/d> a ex:Dataset ;
+                ex:hasName "Events" .
+        "#;
+        let catalog: Catalog<Resource> = from_str(input).unwrap();
+        assert_eq!(
+            catalog.resources,
+            [
+                Resource::Project(Summary {
+                    id: "https://example.org/p".to_string(),
+                    name: "Hôtel de Musique".to_string(),
+                }),
+                Resource::Dataset(Dataset {
+                    id: "https://example.org/d".to_string(),
+                    name: "Events".to_string(),
+                    keyword: None,
+                }),
+            ]
+        );
+
+        let catalog: Catalog<Tagged> = from_str(input).unwrap();
+        assert_eq!(
+            catalog.resources,
+            [
+                Tagged::Project {
+                    id: "https://example.org/p".to_string(),
+                    shortcode: "081C".to_string(),
+                },
+                Tagged::Dataset {
+                    id: "https://example.org/d".to_string(),
+                    name: "Events".to_string(),
+                },
+            ]
+        );
+
+        let catalog: Catalog<Untagged> = from_str(input).unwrap();
+        assert_eq!(
+            catalog.resources,
+            [
+                Untagged::Project {
+                    shortcode: "081C".to_string(),
+                    name: "Hôtel de Musique".to_string(),
+                },
+                Untagged::Dataset {
+                    name: "Events".to_string(),
+                },
+            ]
+        );
+
+        // Classes may be mapped onto variants with other names, and the tag renamed.
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(tag = "kind")]
+        enum Renamed {
+            Collection { shortcode: String },
+            Record { name: String },
+        }
+        let config = DeserializerConfig {
+            namespaces: HashMap::from([("ex".to_string(), "https://example.org/ns#".to_string())]),
+            classes: HashMap::from([
+                ("ex:Project".to_string(), "Collection".to_string()),
+                ("ex:Dataset".to_string(), "Record".to_string()),
+            ]),
+            type_tag: Some("kind".to_string()),
+            ..Default::default()
+        };
+        let input = r#"
+            <https://example.org/d> a <https://example.org/ns#Dataset> ;
+                <https://example.org/ns#hasName> "Events" .
+        "#;
+        let renamed: Renamed = from_str_with_config(input, &config).unwrap();
+        assert_eq!(
+            renamed,
+            Renamed::Record {
+                name: "Events".to_string()
+            }
+        );
+
+        let input = r#"
+            <https://example.org/p> <https://example.org/ns#hasName> "Hôtel de Musique" .
+        "#;
+        let error = from_str::<Resource>(input).unwrap_err().to_string();
+        assert!(error.contains("has no rdf:type"), "{error}");
+        let input = r#"
+            <https://example.org/p> a <https://example.org/ns#Person> .
+        "#;
+        let error = from_str::<Resource>(input).unwrap_err().to_string();
+        assert!(
+            error.contains("<https://example.org/ns#Person>")
+                && error.contains("expected one of: Project, Dataset"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_rdf_lists() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Project {
+            id: String,
+            keywords: Vec<String>,
+            datasets: Vec<Dataset>,
+        }
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> dsp:hasKeyword ("music" "Bern" "concerts") ;
+                dsp:hasDataset (<https://example.org/d2> <https://example.org/d1>) .
+
+            <https://example.org/d1> dsp:hasName "First" .
+            <https://example.org/d2> dsp:hasName "Second" .
+        "#;
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(project.keywords, ["music", "Bern", "concerts"]);
+        let names: Vec<_> = project.datasets.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["Second", "First"]);
+        assert_eq!(project.datasets[0].id, "https://example.org/d2");
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> dsp:hasKeyword () ; dsp:hasDataset () .
+        "#;
+        let project: Project = from_str(input).unwrap();
+        assert!(project.keywords.is_empty());
+        assert!(project.datasets.is_empty());
+    }
+
+    #[test]
+    fn test_rdf_list_errors() {
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            keywords: Vec<String>,
+        }
+
+        let cyclic = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            @prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+
+            <https://example.org/p> dsp:hasKeyword _:l1 .
+            _:l1 rdf:first "a" ; rdf:rest _:l2 .
+            _:l2 rdf:first "b" ; rdf:rest _:l1 .
+        "#;
+        let error = from_str::<Project>(cyclic).unwrap_err().to_string();
+        assert!(error.contains("rdf:List _:l1"), "{error}");
+        assert!(error.contains("cycle"), "{error}");
+
+        let unterminated = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            @prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+
+            <https://example.org/p> dsp:hasKeyword _:l1 .
+            _:l1 rdf:first "a" .
+        "#;
+        let error = from_str::<Project>(unterminated).unwrap_err().to_string();
+        assert!(error.contains("rdf:List _:l1"), "{error}");
+        assert!(error.contains("missing rdf:rest"), "{error}");
+    }
+
+    #[test]
+    fn test_language_maps() {
+        #[derive(Debug, Hash, PartialEq, Eq, Deserialize)]
+        enum IsoCode {
+            DE,
+            EN,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct LangString(HashMap<IsoCode, String>);
+
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            description: LangString,
+        }
+
+        // The description of the example document.
+        let description = "The database documents the events that took place in the Hôtel de \
+            Musique in Bern between 1766 and 1905.";
+        let input = format!(
+            r#"<https://ark.dasch.swiss/ark:/72163/1/081C> <https://ns.dasch.swiss/repository#hasDescription> "{description}"@en ."#
+        );
+        let project: Project = from_str(&input).unwrap();
+        assert_eq!(
+            project.description.0,
+            HashMap::from([(IsoCode::EN, description.to_string())])
+        );
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> dsp:hasDescription "Erste"@de, "Zweite"@DE, "Only"@en .
+        "#;
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(project.description.0[&IsoCode::DE], "Zweite");
+        assert_eq!(project.description.0[&IsoCode::EN], "Only");
+
+        let config = DeserializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "description".to_string(),
+                        rdf_property: "https://ns.dasch.swiss/repository#hasDescription"
+                            .to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            strict: true,
+            ..Default::default()
+        };
+        let error = from_str_with_config::<Project>(input, &config)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("duplicate language"), "{error}");
+
+        // Region subtags are ignored unless the tags are strict.
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> dsp:hasDescription "Grüezi"@de-CH, "Howdy"@en-US .
+        "#;
+        let project: Project = from_str(input).unwrap();
+        assert_eq!(project.description.0[&IsoCode::DE], "Grüezi");
+        assert_eq!(project.description.0[&IsoCode::EN], "Howdy");
+        let strict = DeserializerConfig {
+            strict: false,
+            strict_language_tags: true,
+            ..config
+        };
+        // The parser lowercases the tags.
+        let error = from_str_with_config::<Project>(input, &strict).unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::UnknownVariant { value, expected }
+                    if value == "@de-ch" && *expected == ["DE", "EN"]
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_integers() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Numbers {
+            small: u8,
+            medium: i32,
+            large: i64,
+        }
+
+        let input = |small: &str, medium: &str| {
+            format!(
+                r#"
+                @prefix ex: <https://example.org/ns#> .
+                @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+                <https://example.org/n> ex:small {small} ;
+                    ex:medium {medium} ;
+                    ex:large "-9000000000"^^xsd:long .
+                "#
+            )
+        };
+
+        let numbers: Numbers =
+            from_str(&input(r#""255"^^xsd:unsignedByte"#, r#"" +42 "^^xsd:int"#)).unwrap();
+        assert_eq!(
+            numbers,
+            Numbers {
+                small: 255,
+                medium: 42,
+                large: -9_000_000_000,
+            }
+        );
+
+        let error = from_str::<Numbers>(&input("256", "0")).unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::OutOfRange { field: Some("small"), value, ty: "u8" } if value == "256"
+            ),
+            "{error}"
+        );
+        let error = from_str::<Numbers>(&input("0", "99999999999999999999999999999999999999999"))
+            .unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::OutOfRange {
+                    field: Some("medium"),
+                    ..
+                }
+            ),
+            "{error}"
+        );
+
+        let error = from_str::<Numbers>(&input("-1", "0")).unwrap_err();
+        assert_eq!(
+            error.inner().to_string(),
+            "-1 is out of range for u8 in field small"
+        );
+
+        let error = from_str::<Numbers>(&input(r#""42""#, "0")).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::UnexpectedDatatype { expected, found, .. }
+                    if expected == "http://www.w3.org/2001/XMLSchema#integer"
+                        && found == "http://www.w3.org/2001/XMLSchema#string"
+            ),
+            "{error}"
+        );
+        let error = from_str::<Numbers>(&input("4.2", "0")).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::UnexpectedDatatype { found, .. }
+                    if found == "http://www.w3.org/2001/XMLSchema#decimal"
+            ),
+            "{error}"
+        );
+        let numbers: Numbers = from_str(&input("-0", "-0")).unwrap();
+        assert_eq!((numbers.small, numbers.medium), (0, 0));
+    }
+
+    #[test]
+    fn test_big_integers() {
+        use crate::value::Literal;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Constants {
+            id: String,
+            max: u128,
+            min: i128,
+        }
+
+        let config = || SerializerConfig {
+            subjects: HashMap::from([(
+                "Constants".to_string(),
+                SubjectConfig {
+                    struct_name: "Constants".to_string(),
+                    rdf_type: "https://example.org/ns#Constants".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: ["max", "min"]
+                        .map(|field| PropertyConfig {
+                            struct_field: field.to_string(),
+                            rdf_property: format!("https://example.org/ns#{field}"),
+                            ..Default::default()
+                        })
+                        .to_vec(),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let mut de_config = DeserializerConfig::try_from(&config()).unwrap();
+        de_config.strip_identifier_prefix = true;
+
+        let constants = Constants {
+            id: "c".to_string(),
+            max: u128::MAX,
+            min: i128::MIN,
+        };
+        let output = to_string(&constants, &config()).unwrap();
+        assert!(
+            output.contains(&format!(
+                r#""{}"^^<http://www.w3.org/2001/XMLSchema#integer>"#,
+                u128::MAX
+            )),
+            "{output}"
+        );
+        let round_trip: Constants = from_str_with_config(&output, &de_config).unwrap();
+        assert_eq!(round_trip, constants);
+
+        // Beyond 128 bits, the lexical form is kept by strings and literals.
+        let digits = "123456789012345678901234567890123456789012345";
+        let input = format!(
+            r#"<https://example.org/c> <https://example.org/ns#max> {digits} ;
+                <https://example.org/ns#min> -1 ."#
+        );
+        let error = from_str_with_config::<Constants>(&input, &de_config).unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::OutOfRange { field: Some("max"), value, ty: "u128" } if value == digits
+            ),
+            "{error}"
+        );
+
+        #[derive(Debug, Deserialize)]
+        struct Lossless {
+            max: String,
+            min: Literal,
+        }
+        let input = input.replace("-1", digits);
+        let lossless: Lossless = from_str(&input).unwrap();
+        assert_eq!(lossless.max, digits);
+        assert_eq!(
+            lossless.min,
+            Literal::typed(digits, "http://www.w3.org/2001/XMLSchema#integer")
+        );
+    }
+
+    #[test]
+    fn test_floats() {
+        #[derive(Debug, Deserialize)]
+        struct Measurement {
+            value: f64,
+        }
+
+        let value = |literal: &str| {
+            let input = format!(
+                r#"
+                @prefix ex: <https://example.org/ns#> .
+                @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+                <https://example.org/m> ex:value {literal} .
+                "#
+            );
+            from_str::<Measurement>(&input).map(|m| m.value)
+        };
+
+        let cases = [
+            (r#""1.5"^^xsd:double"#, 1.5),
+            (r#""-1.5E3"^^xsd:double"#, -1500.0),
+            (r#""1e-2"^^xsd:double"#, 0.01),
+            (r#""+.5"^^xsd:double"#, 0.5),
+            (r#""7."^^xsd:float"#, 7.0),
+            (r#"" 12.25 "^^xsd:float"#, 12.25),
+            (r#""INF"^^xsd:double"#, f64::INFINITY),
+            (r#""+INF"^^xsd:float"#, f64::INFINITY),
+            (r#""-INF"^^xsd:double"#, f64::NEG_INFINITY),
+            (r#""12.5"^^xsd:decimal"#, 12.5),
+            (r#""-0.001"^^xsd:decimal"#, -0.001),
+            ("42", 42.0),
+            ("4.2E1", 42.0),
+        ];
+        for (literal, expected) in cases {
+            assert_eq!(value(literal).unwrap(), expected, "{literal}");
+        }
+        assert!(value(r#""NaN"^^xsd:double"#).unwrap().is_nan());
+
+        for invalid in [
+            r#""inf"^^xsd:double"#,
+            r#""nan"^^xsd:float"#,
+            r#""1e5"^^xsd:decimal"#,
+            r#""INF"^^xsd:decimal"#,
+            r#""1.2.3"^^xsd:double"#,
+        ] {
+            let error = value(invalid).unwrap_err();
+            assert!(
+                matches!(error.inner(), Error::InvalidLexicalValue { .. }),
+                "{invalid}: {error}"
+            );
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Single {
+            value: f32,
+        }
+        let input = r#"
+            @prefix ex: <https://example.org/ns#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+            <https://example.org/m> ex:value "0.1234567890123456789"^^xsd:decimal .
+        "#;
+        let single: Single = from_str(input).unwrap();
+        assert!((single.value - 0.123_456_79).abs() < f32::EPSILON);
+
+        let error = value(r#""1.5"^^xsd:string"#).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::UnexpectedDatatype { expected, .. }
+                    if expected == "http://www.w3.org/2001/XMLSchema#double"
+            ),
+            "{error}"
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimals() {
+        use rust_decimal::Decimal;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Invoice {
+            id: String,
+            #[serde(with = "serde_rdf::with::decimal")]
+            amount: Decimal,
+        }
+
+        let config = || SerializerConfig {
+            namespaces: HashMap::from([(
+                "xsd".to_string(),
+                "http://www.w3.org/2001/XMLSchema#".to_string(),
+            )]),
+            subjects: HashMap::from([(
+                "Invoice".to_string(),
+                SubjectConfig {
+                    struct_name: "Invoice".to_string(),
+                    rdf_type: "https://example.org/ns#Invoice".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "amount".to_string(),
+                        rdf_property: "https://example.org/ns#amount".to_string(),
+                        datatype: Some(XsdType::Decimal),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let mut de_config = DeserializerConfig::try_from(&config()).unwrap();
+        de_config.strip_identifier_prefix = true;
+
+        for amount in ["0.10", "12345678901234567890.123", "-7"] {
+            let invoice = Invoice {
+                id: "i1".to_string(),
+                amount: amount.parse().unwrap(),
+            };
+            let output = to_string(&invoice, &config()).unwrap();
+            assert!(
+                output.contains(&format!(
+                    r#""{amount}"^^<http://www.w3.org/2001/XMLSchema#decimal>"#
+                )),
+                "{output}"
+            );
+            let round_trip: Invoice = from_str_with_config(&output, &de_config).unwrap();
+            assert_eq!(round_trip, invoice);
+            assert_eq!(round_trip.amount.to_string(), amount);
+        }
+
+        for invalid in [
+            r#""1.5E2"^^xsd:decimal"#,
+            r#""1.5E2"^^xsd:double"#,
+            r#""12.3.4"^^xsd:decimal"#,
+            // More digits than a Decimal holds.
+            r#""0.123456789012345678901234567890"^^xsd:decimal"#,
+        ] {
+            let input = format!(
+                "@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\
+                 <https://example.org/i1> <https://example.org/ns#amount> {invalid} ."
+            );
+            assert!(
+                from_str_with_config::<Invoice>(&input, &de_config).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn test_url_fields() {
+        #[derive(Debug, Deserialize)]
+        struct Link {
+            #[serde(with = "serde_rdf::with::url")]
+            homepage: url::Url,
+        }
+
+        // Relative IRIs are resolved against the base of the document.
+        let input = r#"
+            @base <https://example.org/> .
+            @prefix ex: <https://example.org/ns#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+            <d1> ex:hasHomepage <projects/081C> .
+            <d2> ex:hasHomepage "https://example.org/projects/d2"^^xsd:anyURI .
+            <d3> ex:hasHomepage "https://example.org/projects/d3" .
+        "#;
+        let link: Link = from_str_for_subject(input, "https://example.org/d1").unwrap();
+        assert_eq!(link.homepage.as_str(), "https://example.org/projects/081C");
+        let link: Link = from_str_for_subject(input, "https://example.org/d2").unwrap();
+        assert_eq!(link.homepage.as_str(), "https://example.org/projects/d2");
+        let error = from_str_for_subject::<Link>(input, "https://example.org/d3").unwrap_err();
+        assert!(error.to_string().contains("xsd:anyURI"), "{error}");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Event {
+            id: String,
+            #[serde(with = "serde_rdf::with::chrono")]
+            start: DateTime<FixedOffset>,
+            #[serde(with = "serde_rdf::with::chrono")]
+            end: DateTime<Utc>,
+            #[serde(with = "serde_rdf::with::chrono")]
+            day: NaiveDate,
+            #[serde(with = "serde_rdf::with::chrono")]
+            local: NaiveDateTime,
+        }
+
+        let input = r#"
+            @prefix ex: <https://example.org/ns#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+            <https://example.org/e1> ex:start "2023-05-01T10:00:00+02:00"^^xsd:dateTime ;
+                ex:end "2023-05-01T12:30:00+02:00"^^xsd:dateTime ;
+                ex:day "2023-05-01"^^xsd:date ;
+                ex:local "2023-05-01T10:00:00"^^xsd:dateTime .
+        "#;
+        let event: Event = from_str(input).unwrap();
+        assert_eq!(event.start.to_rfc3339(), "2023-05-01T10:00:00+02:00");
+        assert_eq!(
+            event.end,
+            Utc.with_ymd_and_hms(2023, 5, 1, 10, 30, 0).unwrap()
+        );
+        assert_eq!(event.day, NaiveDate::from_ymd_opt(2023, 5, 1).unwrap());
+        assert_eq!(event.local, event.day.and_hms_opt(10, 0, 0).unwrap());
+
+        let config = SerializerConfig {
+            subjects: HashMap::from([(
+                "Event".to_string(),
+                SubjectConfig {
+                    struct_name: "Event".to_string(),
+                    rdf_type: "https://example.org/ns#Event".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: ["start", "end", "day", "local"]
+                        .map(|field| PropertyConfig {
+                            struct_field: field.to_string(),
+                            rdf_property: format!("https://example.org/ns#{field}"),
+                            ..Default::default()
+                        })
+                        .to_vec(),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let de_config = DeserializerConfig::try_from(&config).unwrap();
+        let event = Event {
+            id: "e1".to_string(),
+            ..event
+        };
+        let turtle = to_string(&event, &config).unwrap();
+        let round_trip: Event = from_str_with_config(&turtle, &de_config).unwrap();
+        assert_eq!(round_trip, event);
+        assert_eq!(
+            round_trip.start.offset(),
+            &FixedOffset::east_opt(7200).unwrap()
+        );
+
+        let error = from_str::<Event>(&input.replace("2023-05-01\"", "2023-02-30\""))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("<https://example.org/ns#day>"), "{error}");
+        let error = from_str::<Event>(&input.replace("2023-05-01T10:00:00+02:00", "yesterday"))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("<https://example.org/ns#start>"), "{error}");
+
+        // Strings take any lexical form, including valid ones chrono does not support.
+        #[derive(Debug, Deserialize)]
+        struct Period {
+            start: String,
+            end: String,
+        }
+
+        let input = r#"
+            @prefix ex: <https://example.org/ns#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+            <https://example.org/p1> ex:start "2023-05-01T24:00:00"^^xsd:dateTime ;
+                ex:end "12023-05-01"^^xsd:date .
+        "#;
+        let period: Period = from_str(input).unwrap();
+        assert_eq!(period.start, "2023-05-01T24:00:00");
+        assert_eq!(period.end, "12023-05-01");
+    }
+
+    #[test]
+    fn test_durations() {
+        #[derive(Debug, Deserialize)]
+        struct Dataset {
+            embargo: XsdDuration,
+            #[serde(with = "serde_rdf::with::duration")]
+            window: Duration,
+            offset: Option<XsdDuration>,
+        }
+
+        let input = |window: &str| {
+            format!(
+                r#"
+                @prefix ex: <https://example.org/ns#> .
+                @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+                <https://example.org/d> ex:embargo "P2Y6M"^^xsd:duration ;
+                    ex:window "{window}"^^xsd:duration ;
+                    ex:offset "-P1DT0.25S"^^xsd:duration .
+                "#
+            )
+        };
+        let dataset: Dataset = from_str(&input("PT1H30M")).unwrap();
+        assert_eq!(dataset.window, Duration::from_secs(5400));
+        assert_eq!(
+            dataset.embargo,
+            XsdDuration {
+                years: 2,
+                months: 6,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            dataset.offset,
+            Some(XsdDuration {
+                negative: true,
+                days: 1,
+                seconds: 0.25,
+                ..Default::default()
+            })
+        );
+        assert_eq!(dataset.embargo.to_string(), "P2Y6M");
+        assert_eq!(dataset.offset.unwrap().to_string(), "-P1DT0.25S");
+
+        let dataset: Dataset = from_str(&input("P1DT2.5S")).unwrap();
+        assert_eq!(dataset.window, Duration::new(86_402, 500_000_000));
+
+        for window in ["P1M", "-PT1S"] {
+            let error = from_str::<Dataset>(&input(window)).unwrap_err();
+            assert!(error.to_string().contains("cannot represent"), "{error}");
+        }
+        for window in ["P", "PT", "P1H", "PT1.5M", "P1D2Y", "1D"] {
+            let error = from_str::<Dataset>(&input(window)).unwrap_err();
+            assert!(
+                matches!(error.inner(), Error::InvalidLexicalValue { value, .. } if value == window),
+                "{window}: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_binary() {
+        #[derive(Debug, Deserialize)]
+        struct File {
+            checksum: Vec<u8>,
+        }
+
+        let checksum = |literal: &str| {
+            let input = format!(
+                r#"
+                @prefix ex: <https://example.org/ns#> .
+                @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+                <https://example.org/f> ex:checksum {literal} .
+                "#
+            );
+            from_str::<File>(&input).map(|file| file.checksum)
+        };
+
+        assert_eq!(
+            checksum(r#""aGVsbG8="^^xsd:base64Binary"#).unwrap(),
+            b"hello"
+        );
+        assert_eq!(
+            checksum(r#""aGVs bG8h"^^xsd:base64Binary"#).unwrap(),
+            b"hello!"
+        );
+        assert_eq!(
+            checksum(r#""68656c6C6F"^^xsd:hexBinary"#).unwrap(),
+            b"hello"
+        );
+
+        for invalid in ["aGVsbG8", "aGVsbG8==", "aGVsbG9="] {
+            let error = checksum(&format!(r#""{invalid}"^^xsd:base64Binary"#)).unwrap_err();
+            assert!(
+                error.to_string().contains("invalid padding"),
+                "{invalid}: {error}"
+            );
+        }
+        let error = checksum(r#""aGV$bG8="^^xsd:base64Binary"#).unwrap_err();
+        assert!(
+            error.to_string().contains("'$' at byte offset 3"),
+            "{error}"
+        );
+        let error = checksum(r#""68656x6c6f"^^xsd:hexBinary"#).unwrap_err();
+        assert!(
+            error.to_string().contains("'x' at byte offset 5"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_booleans() {
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            public: bool,
+        }
+
+        let input = |public: &str| {
+            format!(
+                r#"
+                @prefix dsp: <https://ns.dasch.swiss/repository#> .
+                @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+                <https://example.org/p> dsp:public {public} .
+                "#
+            )
+        };
+        let public = |public: &str| from_str::<Project>(&input(public)).map(|p| p.public);
+
+        assert!(public("true").unwrap());
+        assert!(!public("false").unwrap());
+        assert!(public(r#""1"^^xsd:boolean"#).unwrap());
+        assert!(!public(r#""0"^^xsd:boolean"#).unwrap());
+
+        for invalid in ["TRUE", "yes"] {
+            let error = public(&format!(r#""{invalid}"^^xsd:boolean"#)).unwrap_err();
+            assert!(
+                matches!(error.inner(), Error::InvalidLexicalValue { value, .. } if value == invalid),
+                "{error}"
+            );
+        }
+
+        // Simple literals are only accepted in lenient mode.
+        let error = public(r#""true""#).unwrap_err();
+        assert!(
+            matches!(&error, Error::UnexpectedDatatype { expected, .. } if expected.ends_with("#boolean")),
+            "{error}"
+        );
+        let config = DeserializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "public".to_string(),
+                        rdf_property: "https://ns.dasch.swiss/repository#public".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            lenient: true,
+            ..Default::default()
+        };
+        let project: Project = from_str_with_config(&input(r#""true""#), &config).unwrap();
+        assert!(project.public);
+    }
+
+    #[test]
+    fn test_coerce_literals() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Project {
+            size: u32,
+            public: bool,
+        }
+
+        let input = |size: &str, public: &str| {
+            format!(
+                r#"
+                @prefix dsp: <https://ns.dasch.swiss/repository#> .
+                @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+                <https://example.org/p> dsp:hasSize {size} ; dsp:isPublic {public} .
+                "#
+            )
+        };
+        let config = |coerce_literals| DeserializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    properties: vec![
+                        PropertyConfig {
+                            struct_field: "size".to_string(),
+                            rdf_property: "https://ns.dasch.swiss/repository#hasSize".to_string(),
+                            ..Default::default()
+                        },
+                        PropertyConfig {
+                            struct_field: "public".to_string(),
+                            rdf_property: "https://ns.dasch.swiss/repository#isPublic".to_string(),
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+            )]),
+            coerce_literals,
+            ..Default::default()
+        };
+
+        let project: Project =
+            from_str_with_config(&input(r#""42"^^xsd:string"#, r#""true""#), &config(true))
+                .unwrap();
+        assert_eq!(
+            project,
+            Project {
+                size: 42,
+                public: true,
+            }
+        );
+        let project: Project =
+            from_str_with_config(&input(r#""7""#, r#""0"^^xsd:string"#), &config(true)).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                size: 7,
+                public: false,
+            }
+        );
+
+        // Coerced literals must still have a valid lexical form.
+        let error = from_str_with_config::<Project>(&input(r#""many""#, "true"), &config(true))
+            .unwrap_err();
+        assert!(
+            matches!(error.inner(), Error::InvalidLexicalValue { value, .. } if value == "many"),
+            "{error}"
+        );
+
+        let error =
+            from_str_with_config::<Project>(&input(r#""42"^^xsd:string"#, "true"), &config(false))
+                .unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::UnexpectedDatatype { expected, found, subject, predicate }
+                    if expected == "http://www.w3.org/2001/XMLSchema#integer"
+                        && found == "http://www.w3.org/2001/XMLSchema#string"
+                        && subject == "<https://example.org/p>"
+                        && predicate == "https://ns.dasch.swiss/repository#hasSize"
+            ),
+            "{error}"
+        );
+        assert_eq!(
+            error.to_string(),
+            "expected a literal of <http://www.w3.org/2001/XMLSchema#integer> as object of \
+             <https://ns.dasch.swiss/repository#hasSize> for <https://example.org/p>, found \
+             <http://www.w3.org/2001/XMLSchema#string>"
+        );
+    }
+
+    #[test]
+    fn test_language_priority() {
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            description: String,
+        }
+
+        let config = |language_priority: &[&str]| DeserializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "description".to_string(),
+                        rdf_property: "https://ns.dasch.swiss/repository#hasDescription"
+                            .to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            language_priority: language_priority.iter().map(|l| l.to_string()).collect(),
+            ..Default::default()
+        };
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+
+            <https://example.org/p> dsp:hasDescription "Le Hôtel"@fr, "The Hôtel"@en, "Das Hôtel"@de .
+        "#;
+
+        let project: Project = from_str_with_config(input, &config(&["de", "en"])).unwrap();
+        assert_eq!(project.description, "Das Hôtel");
+        let project: Project = from_str_with_config(input, &config(&["it", "EN"])).unwrap();
+        assert_eq!(project.description, "The Hôtel");
+
+        // Without a matching language, the first literal is taken.
+        let project: Project = from_str_with_config(input, &config(&["it"])).unwrap();
+        assert_eq!(project.description, "Le Hôtel");
+
+        let error = from_str_with_config::<Project>(input, &config(&[]))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("no language priority"), "{error}");
+        let error = from_str::<Project>(input).unwrap_err().to_string();
+        assert!(error.contains("fr, en, de"), "{error}");
+    }
+
+    #[test]
+    fn test_from_str_with_config() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Project {
+            shortcode: String,
+            title: String,
+            public: bool,
+            size: u32,
+            collections: Vec<Collection>,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Collection {
+            id: String,
+            label: Option<String>,
+        }
+
+        let subject = |name: &str, identifier_field: &str, properties| SubjectConfig {
+            struct_name: name.to_string(),
+            rdf_type: format!("https://example.org/ns#{name}"),
+            identifier_field: identifier_field.to_string(),
+            identifier_prefix: "https://example.org/".to_string(),
+            properties,
+            ..Default::default()
+        };
+        // Predicate names deliberately unrelated to the field names.
+        let property = |field: &str, predicate: &str| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("https://example.org/ns#{predicate}"),
+            ..Default::default()
+        };
+        let config = SerializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    subject(
+                        "Project",
+                        "shortcode",
+                        vec![
+                            property("title", "hasName"),
+                            property("public", "isPublic"),
+                            property("size", "extent"),
+                            property("collections", "contains"),
+                        ],
+                    ),
+                ),
+                (
+                    "Collection".to_string(),
+                    subject("Collection", "id", vec![property("label", "prefLabel")]),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let de_config = DeserializerConfig::try_from(&config).unwrap();
+
+        let project = Project {
+            shortcode: "081C".to_string(),
+            title: "Hôtel de Musique Bern".to_string(),
+            public: true,
+            size: 42,
+            collections: vec![
+                Collection {
+                    id: "c1".to_string(),
+                    label: Some("Events".to_string()),
+                },
+                Collection {
+                    id: "c2".to_string(),
+                    label: None,
+                },
+            ],
+        };
+        let turtle = to_string(&project, &config).unwrap();
+        let mut round_trip: Project = from_str_with_config(&turtle, &de_config).unwrap();
+        round_trip.collections.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(round_trip, project);
+
+        // Without stripping, identifiers hold the full IRI.
+        let full = DeserializerConfig {
+            strip_identifier_prefix: false,
+            ..de_config.clone()
+        };
+        let round_trip: Project = from_str_with_config(&turtle, &full).unwrap();
+        assert_eq!(round_trip.shortcode, "https://example.org/081C");
+
+        // Unmapped predicates are ignored.
+        let input = r#"
+            <https://example.org/c3> a <https://example.org/ns#Collection> ;
+                <https://example.org/ns#prefLabel> "Letters" ;
+                <https://example.org/ns#unknown> "ignored" .
+        "#;
+        let collection: Collection = from_str_with_config(input, &de_config).unwrap();
+        assert_eq!(
+            collection,
+            Collection {
+                id: "c3".to_string(),
+                label: Some("Letters".to_string()),
+            }
+        );
+
+        let error = from_str_with_config::<Collection>(input, &Default::default()).unwrap_err();
+        assert!(error.to_string().contains("no subject config"), "{error}");
+    }
+
+    #[test]
+    fn test_type_field_and_predicate_round_trip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Resource {
+            id: String,
+            kind: String,
+            title: String,
+        }
+
+        let config = SerializerConfig {
+            namespaces: HashMap::from([
+                ("ex".to_string(), "https://example.org/ns#".to_string()),
+                (
+                    "dcterms".to_string(),
+                    "http://purl.org/dc/terms/".to_string(),
+                ),
+            ]),
+            subjects: HashMap::from([(
+                "Resource".to_string(),
+                SubjectConfig {
+                    struct_name: "Resource".to_string(),
+                    rdf_type: "ex:Resource".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    type_field: Some("kind".to_string()),
+                    properties: vec![PropertyConfig {
+                        struct_field: "title".to_string(),
+                        rdf_property: "dcterms:title".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            type_predicate: Some("dcterms:type".to_string()),
+            ..Default::default()
+        };
+        let de_config = DeserializerConfig {
+            deny_unknown_predicates: true,
+            ..DeserializerConfig::try_from(&config).unwrap()
+        };
+
+        let resource = Resource {
+            id: "r1".to_string(),
+            kind: "https://example.org/ns#Dataset".to_string(),
+            title: "Incunabula".to_string(),
+        };
+        let turtle = to_string(&resource, &config).unwrap();
+        assert!(
+            turtle.contains("<http://purl.org/dc/terms/type>"),
+            "{turtle}"
+        );
+        let round_trip: Resource = from_str_with_config(&turtle, &de_config).unwrap();
+        assert_eq!(round_trip, resource);
+
+        let by_type = crate::Deserializer::from_raw_str(&turtle)
+            .unwrap()
+            .with_config(&de_config)
+            .with_type("https://example.org/ns#Dataset")
+            .unwrap();
+        assert_eq!(Resource::deserialize(by_type).unwrap(), resource);
+
+        // Errors in the serializer config are not dropped.
+        let config = SerializerConfig {
+            subjects: HashMap::from([(
+                "Resource".to_string(),
+                SubjectConfig {
+                    include_properties: vec!["missing".to_string()],
+                    ..config.subjects["Resource"].clone()
+                },
+            )]),
+            ..config
+        };
+        assert!(DeserializerConfig::try_from(&config).is_err());
     }
 }
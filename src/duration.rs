@@ -0,0 +1,205 @@
+//! The `xsd:duration` datatype.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::error::{Error, Result};
+
+/// An [`xsd:duration`](https://www.w3.org/TR/xmlschema11-2/#duration) such as `P2Y6M` or
+/// `-PT1H30.5S`.
+///
+/// Unlike [`std::time::Duration`], which only holds day-time durations, it keeps the year and
+/// month components, whose length in seconds depends on the date they are added to. Hours and
+/// minutes are folded into `seconds`. It is serialized as its lexical form.
+///
+/// Example:
+/// ```
+/// use serde_rdf::XsdDuration;
+/// let embargo: XsdDuration = "P2Y6M".parse().unwrap();
+/// assert_eq!((embargo.years, embargo.months), (2, 6));
+/// assert_eq!(embargo.to_string(), "P2Y6M");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct XsdDuration {
+    pub negative: bool,
+    pub years: u64,
+    pub months: u64,
+    pub days: u64,
+    pub seconds: f64,
+}
+
+impl FromStr for XsdDuration {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let invalid = || Error::InvalidLexicalValue {
+            value: value.to_string(),
+            datatype: "xsd:duration",
+        };
+        let (negative, rest) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+        let (date, time) = match rest.split_once('T') {
+            Some((date, time)) => (date, Some(time)),
+            None => (rest, None),
+        };
+
+        let mut duration = XsdDuration {
+            negative,
+            ..Default::default()
+        };
+        // At least one component is required, and a `T` must be followed by one.
+        if (date.is_empty() && time.is_none()) || time == Some("") {
+            return Err(invalid());
+        }
+        let date = components(date, "YMD").ok_or_else(invalid)?;
+        let time = components(time.unwrap_or_default(), "HMS").ok_or_else(invalid)?;
+        for (number, designator) in date {
+            let number: u64 = number.parse().map_err(|_| invalid())?;
+            match designator {
+                'Y' => duration.years = number,
+                'M' => duration.months = number,
+                _ => duration.days = number,
+            }
+        }
+        for (number, designator) in time {
+            if designator != 'S' && number.contains('.') {
+                return Err(invalid());
+            }
+            let number: f64 = number.parse().map_err(|_| invalid())?;
+            duration.seconds += match designator {
+                'H' => number * 3600.0,
+                'M' => number * 60.0,
+                _ => number,
+            };
+        }
+        Ok(duration)
+    }
+}
+
+/// Split the date or time part of a duration, e.g. `2Y6M`, into its numbers and designators,
+/// which must appear at most once and in the given order.
+fn components<'s>(part: &'s str, designators: &str) -> Option<Vec<(&'s str, char)>> {
+    let mut components = Vec::new();
+    let mut rest = part;
+    let mut allowed = designators;
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let number = &rest[..end];
+        let (integral, fraction) = number.split_once('.').unwrap_or((number, "0"));
+        if integral.is_empty() || fraction.is_empty() || fraction.contains('.') {
+            return None;
+        }
+        let designator = rest[end..].chars().next()?;
+        allowed = &allowed[allowed.find(designator)? + 1..];
+        components.push((number, designator));
+        rest = &rest[end + 1..];
+    }
+    Some(components)
+}
+
+impl Display for XsdDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            f.write_str("-")?;
+        }
+        f.write_str("P")?;
+        if self.years > 0 {
+            write!(f, "{}Y", self.years)?;
+        }
+        if self.months > 0 {
+            write!(f, "{}M", self.months)?;
+        }
+        if self.days > 0 {
+            write!(f, "{}D", self.days)?;
+        }
+        let empty = self.years == 0 && self.months == 0 && self.days == 0;
+        if self.seconds > 0.0 || empty {
+            f.write_str("T")?;
+            let hours = (self.seconds / 3600.0).floor();
+            let minutes = ((self.seconds - hours * 3600.0) / 60.0).floor();
+            let seconds = self.seconds - hours * 3600.0 - minutes * 60.0;
+            if hours > 0.0 {
+                write!(f, "{hours}H")?;
+            }
+            if minutes > 0.0 {
+                write!(f, "{minutes}M")?;
+            }
+            if seconds > 0.0 || (hours == 0.0 && minutes == 0.0) {
+                write!(f, "{seconds}S")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Day-time durations that are not negative.
+impl TryFrom<XsdDuration> for Duration {
+    type Error = Error;
+
+    fn try_from(duration: XsdDuration) -> Result<Self> {
+        if duration.negative || duration.years > 0 || duration.months > 0 {
+            return Err(Error::Message(format!(
+                "duration {duration} is negative or has years or months, which \
+                 std::time::Duration cannot represent"
+            )));
+        }
+        let seconds = Duration::try_from_secs_f64(duration.seconds).map_err(Error::new)?;
+        duration
+            .days
+            .checked_mul(86_400)
+            .map(Duration::from_secs)
+            .and_then(|days| days.checked_add(seconds))
+            .ok_or_else(|| Error::Message(format!("duration {duration} is out of range")))
+    }
+}
+
+impl From<Duration> for XsdDuration {
+    fn from(duration: Duration) -> Self {
+        XsdDuration {
+            seconds: duration.as_secs_f64(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Serialize for XsdDuration {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for XsdDuration {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct DurationVisitor;
+
+        impl Visitor<'_> for DurationVisitor {
+            type Value = XsdDuration;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("an xsd:duration")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<XsdDuration, E>
+            where
+                E: de::Error,
+            {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(DurationVisitor)
+    }
+}
@@ -4,8 +4,12 @@ use std::fmt::{Display, Formatter};
 use std::io;
 use std::str::Utf8Error;
 
+use rio_api::parser::ParseError;
+use rio_turtle::TurtleError;
 use serde::{de, ser};
 
+use crate::format::Format;
+
 /// The result type used by this crate.
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -19,8 +23,209 @@ pub enum Error {
     Utf8(Utf8Error),
     /// Represents generic IO errors.
     Io(io::Error),
+    /// Represents input the parser rejected at a known position.
+    Parse {
+        /// The line of the error, starting at 1.
+        line: u64,
+        /// The column of the error in bytes, starting at 1.
+        column: u64,
+        /// The message of the parser.
+        message: String,
+        /// The error of the parser, which is the [`source`](std::error::Error::source) as well.
+        source: TurtleError,
+    },
+    /// Represents an error of the Turtle family of parsers without a position, e.g. an IO error
+    /// while reading.
+    Turtle(TurtleError),
+    /// Represents an error of the RDF/XML parser.
+    #[cfg(feature = "xml")]
+    RdfXml(rio_xml::RdfXmlError),
     /// Represents an error during serialization.
     CannotSerializePrimitive(&'static str),
+    /// Represents a subject IRI that has no statements in the input, or a class IRI without
+    /// instances.
+    SubjectNotFound(String),
+    /// Represents a class with several instances where a single subject was expected. Holds the
+    /// IRIs of the candidates.
+    AmbiguousSubject(Vec<String>),
+    /// Represents a number that does not fit the integer type it is deserialized into.
+    OutOfRange {
+        /// The struct field the number was read for, if any.
+        field: Option<&'static str>,
+        /// The lexical form of the number.
+        value: String,
+        /// The name of the target type, e.g. `u8`.
+        ty: &'static str,
+    },
+    /// Represents a single-valued field whose predicate has several objects.
+    CardinalityViolation {
+        /// The subject, written as `<iri>` or `_:label`.
+        subject: String,
+        /// The IRI of the predicate.
+        predicate: String,
+        /// The number of objects the field takes.
+        expected: usize,
+        /// The number of objects in the data.
+        found: usize,
+    },
+    /// Represents an IRI or literal that names none of the variants of an enum.
+    UnknownVariant {
+        /// The offending value, written as `<iri>`, as a literal or as a language tag `@tag`.
+        value: String,
+        /// The names of the variants of the enum.
+        expected: &'static [&'static str],
+    },
+    /// Represents a subject that refers back to a struct it is nested in.
+    CyclicReference {
+        /// The subjects from the root down to the repeated one, written as `<iri>` or
+        /// `_:label`.
+        path: Vec<String>,
+    },
+    /// Represents a literal whose lexical form is not valid for its datatype.
+    InvalidLexicalValue {
+        /// The offending lexical form.
+        value: String,
+        /// The datatype the value was read as, e.g. `xsd:boolean`.
+        datatype: &'static str,
+    },
+    /// Represents a struct without a subject config.
+    MissingSubjectConfig {
+        /// The name of the struct, e.g. `Project`.
+        struct_name: String,
+    },
+    /// Represents a struct field that is neither mapped to an RDF property nor the identifier
+    /// or type field of its subject config.
+    MissingPropertyConfig {
+        /// The name of the struct, e.g. `Project`.
+        struct_name: String,
+        /// The name of the field, e.g. `shortcode`.
+        field: String,
+    },
+    /// Represents a string that is not an absolute IRI where one is required.
+    InvalidIri {
+        /// The offending string.
+        iri: String,
+        /// Where the string came from, e.g. `identifier for subject Project`.
+        context: String,
+    },
+    /// Represents an error that occurred while a subject was processed, e.g. one of the fields
+    /// of its struct.
+    Contextual {
+        /// What was done with the subject, `serializing` or `deserializing`.
+        operation: &'static str,
+        /// The subject, written as `<iri>` or `_:label`, or the name of its struct if the
+        /// identifier is not known yet.
+        subject: String,
+        /// The struct and field that were processed, e.g. `Dataset.title`, if any.
+        field: Option<String>,
+        /// The error itself.
+        source: Box<Error>,
+    },
+    /// Represents a literal of another datatype than the one the target type is read from,
+    /// e.g. an `xsd:string` for an integer, unless literals are coerced.
+    UnexpectedDatatype {
+        /// The IRI of the datatype the target type is read from.
+        expected: String,
+        /// The IRI of the datatype of the literal, `xsd:string` for simple literals.
+        found: String,
+        /// The subject, written as `<iri>` or `_:label`.
+        subject: String,
+        /// The IRI of the predicate.
+        predicate: String,
+    },
+    /// Represents a struct that has no field with the name configured as its
+    /// `identifier_field`.
+    IdentifierFieldMissing {
+        /// The name of the struct, e.g. `Project`.
+        struct_name: String,
+        /// The configured identifier field.
+        identifier_field: String,
+        /// The fields of the struct that were serialized.
+        available_fields: Vec<String>,
+    },
+    /// Represents a map key that is not a language tag, e.g. `en US`, where it would become
+    /// the language of a literal.
+    InvalidLanguageTag(String),
+    /// Represents a prefixed name in a config whose prefix is not one of its namespaces.
+    UnknownPrefix {
+        /// The prefix, e.g. `dsp`.
+        prefix: String,
+        /// The prefixed name, e.g. `dsp:hasName`.
+        value: String,
+        /// The prefixes of the namespaces of the config, sorted.
+        known: Vec<String>,
+    },
+    /// Represents a format that cannot be read or written, because its feature is not enabled
+    /// or the crate has no parser or writer for it.
+    UnsupportedFormat {
+        /// The format.
+        format: Format,
+        /// What was done with the format, `reading` or `writing`.
+        operation: &'static str,
+        /// The feature that adds support for the format, if any.
+        feature: Option<&'static str>,
+    },
+}
+
+/// The kind of an [`Error`], which groups its variants and looks through the context of an
+/// [`Error::Contextual`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A message without a more specific kind, e.g. from serde or a `Serialize` impl.
+    Other,
+    /// [`Error::Io`].
+    Io,
+    /// [`Error::Utf8`].
+    Utf8,
+    /// Input the parser rejected: [`Error::Parse`] and the errors of the parsers.
+    Syntax,
+    /// [`Error::CannotSerializePrimitive`] and [`Error::UnsupportedFormat`].
+    Unsupported,
+    /// [`Error::SubjectNotFound`].
+    SubjectNotFound,
+    /// [`Error::AmbiguousSubject`].
+    AmbiguousSubject,
+    /// [`Error::OutOfRange`].
+    OutOfRange,
+    /// [`Error::CardinalityViolation`].
+    CardinalityViolation,
+    /// [`Error::UnknownVariant`].
+    UnknownVariant,
+    /// [`Error::CyclicReference`].
+    CyclicReference,
+    /// [`Error::InvalidLexicalValue`].
+    InvalidLexicalValue,
+    /// [`Error::UnexpectedDatatype`].
+    UnexpectedDatatype,
+    /// [`Error::InvalidIri`].
+    InvalidIri,
+    /// [`Error::InvalidLanguageTag`].
+    InvalidLanguageTag,
+    /// [`Error::MissingSubjectConfig`].
+    MissingSubjectConfig,
+    /// [`Error::MissingPropertyConfig`].
+    MissingPropertyConfig,
+    /// [`Error::IdentifierFieldMissing`].
+    IdentifierFieldMissing,
+    /// [`Error::UnknownPrefix`].
+    UnknownPrefix,
+}
+
+/// A malformed statement that was skipped when parsing leniently, e.g. by
+/// [`from_str_lenient`](crate::from_str_lenient).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIssue {
+    /// The line the parser reported the error on, starting at 1.
+    pub line: u64,
+    /// The message of the parser.
+    pub message: String,
+}
+
+impl Display for ParseIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
 }
 
 impl Error {
@@ -30,6 +235,117 @@ impl Error {
     {
         Error::Message(msg.to_string())
     }
+
+    /// Add the subject and field being processed to the error, unless it names its subject
+    /// already or has a context from a nested subject, which is the more specific one.
+    pub(crate) fn context(
+        self,
+        operation: &'static str,
+        subject: impl FnOnce() -> String,
+        field: Option<String>,
+    ) -> Error {
+        match self {
+            Error::Contextual { .. }
+            | Error::CardinalityViolation { .. }
+            | Error::UnexpectedDatatype { .. }
+            | Error::CyclicReference { .. } => self,
+            err => Error::Contextual {
+                operation,
+                subject: subject(),
+                field,
+                source: Box::new(err),
+            },
+        }
+    }
+
+    /// The error without the context of an [`Error::Contextual`].
+    pub fn inner(&self) -> &Error {
+        match self {
+            Error::Contextual { source, .. } => source.inner(),
+            err => err,
+        }
+    }
+
+    /// The kind of the error, or of the error within an [`Error::Contextual`].
+    pub fn kind(&self) -> ErrorKind {
+        match self.inner() {
+            Error::Message(_) | Error::Contextual { .. } => ErrorKind::Other,
+            Error::Utf8(_) => ErrorKind::Utf8,
+            Error::Io(_) => ErrorKind::Io,
+            Error::Parse { .. } | Error::Turtle(_) => ErrorKind::Syntax,
+            #[cfg(feature = "xml")]
+            Error::RdfXml(_) => ErrorKind::Syntax,
+            Error::CannotSerializePrimitive(_) => ErrorKind::Unsupported,
+            Error::SubjectNotFound(_) => ErrorKind::SubjectNotFound,
+            Error::AmbiguousSubject(_) => ErrorKind::AmbiguousSubject,
+            Error::OutOfRange { .. } => ErrorKind::OutOfRange,
+            Error::CardinalityViolation { .. } => ErrorKind::CardinalityViolation,
+            Error::UnknownVariant { .. } => ErrorKind::UnknownVariant,
+            Error::CyclicReference { .. } => ErrorKind::CyclicReference,
+            Error::InvalidLexicalValue { .. } => ErrorKind::InvalidLexicalValue,
+            Error::UnexpectedDatatype { .. } => ErrorKind::UnexpectedDatatype,
+            Error::InvalidIri { .. } => ErrorKind::InvalidIri,
+            Error::InvalidLanguageTag(_) => ErrorKind::InvalidLanguageTag,
+            Error::MissingSubjectConfig { .. } => ErrorKind::MissingSubjectConfig,
+            Error::MissingPropertyConfig { .. } => ErrorKind::MissingPropertyConfig,
+            Error::IdentifierFieldMissing { .. } => ErrorKind::IdentifierFieldMissing,
+            Error::UnknownPrefix { .. } => ErrorKind::UnknownPrefix,
+            Error::UnsupportedFormat { .. } => ErrorKind::Unsupported,
+        }
+    }
+
+    /// Whether the config does not cover the types that are (de)serialized. Retrying with the
+    /// same config fails again.
+    pub fn is_config_error(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::MissingSubjectConfig
+                | ErrorKind::MissingPropertyConfig
+                | ErrorKind::IdentifierFieldMissing
+                | ErrorKind::UnknownPrefix
+        )
+    }
+
+    /// Whether the input, or the values that are serialized, cannot be represented. This
+    /// includes invalid IRIs, which are mostly built from identifiers.
+    pub fn is_data_error(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::Utf8
+                | ErrorKind::Syntax
+                | ErrorKind::SubjectNotFound
+                | ErrorKind::AmbiguousSubject
+                | ErrorKind::OutOfRange
+                | ErrorKind::CardinalityViolation
+                | ErrorKind::UnknownVariant
+                | ErrorKind::CyclicReference
+                | ErrorKind::InvalidLexicalValue
+                | ErrorKind::UnexpectedDatatype
+                | ErrorKind::InvalidIri
+                | ErrorKind::InvalidLanguageTag
+        )
+    }
+
+    /// Whether reading or writing failed, which may succeed when retried.
+    pub fn is_io(&self) -> bool {
+        self.kind() == ErrorKind::Io
+    }
+
+    /// The line of a [`Error::Parse`], starting at 1.
+    pub fn line(&self) -> Option<u64> {
+        match self {
+            Error::Parse { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// The column of a [`Error::Parse`] in bytes, starting at 1.
+    pub fn column(&self) -> Option<u64> {
+        match self {
+            Error::Parse { column, .. } => Some(*column),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Error {
@@ -38,7 +354,120 @@ impl Display for Error {
             Error::Message(msg) => write!(f, "{msg}"),
             Error::Utf8(err) => write!(f, "{err}"),
             Error::Io(err) => write!(f, "{err}"),
+            Error::Parse {
+                line,
+                column,
+                message,
+                ..
+            } => write!(f, "line {line}, column {column}: {message}"),
+            Error::Turtle(err) => write!(f, "{err}"),
+            #[cfg(feature = "xml")]
+            Error::RdfXml(err) => write!(f, "{err}"),
             Error::CannotSerializePrimitive(msg) => write!(f, "{msg}"),
+            Error::SubjectNotFound(iri) => write!(f, "subject not found: <{iri}>"),
+            Error::AmbiguousSubject(candidates) => {
+                write!(f, "several subjects found: {}", candidates.join(", "))
+            }
+            Error::OutOfRange {
+                field: Some(field),
+                value,
+                ty,
+            } => {
+                write!(f, "{value} is out of range for {ty} in field {field}")
+            }
+            Error::OutOfRange {
+                field: None,
+                value,
+                ty,
+            } => {
+                write!(f, "{value} is out of range for {ty}")
+            }
+            Error::CardinalityViolation {
+                subject,
+                predicate,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {expected} object(s) of <{predicate}> for {subject}, found {found}"
+            ),
+            Error::UnknownVariant { value, expected } => {
+                write!(
+                    f,
+                    "unknown variant {value}, expected one of: {}",
+                    expected.join(", ")
+                )
+            }
+            Error::CyclicReference { path } => {
+                write!(f, "cyclic reference: {}", path.join(" -> "))
+            }
+            Error::InvalidLexicalValue { value, datatype } => {
+                write!(f, "invalid lexical value {value:?} for {datatype}")
+            }
+            Error::MissingSubjectConfig { struct_name } => {
+                write!(f, "no subject config found for struct: {struct_name}")
+            }
+            Error::MissingPropertyConfig { struct_name, field } => {
+                write!(f, "no RDF property configured for {struct_name}.{field}")
+            }
+            Error::InvalidIri { iri, context } => {
+                write!(f, "{context} is not an absolute IRI: {iri}")
+            }
+            Error::InvalidLanguageTag(tag) => write!(f, "invalid language tag: {tag}"),
+            Error::Contextual {
+                operation,
+                subject,
+                field: Some(field),
+                source,
+            } => write!(f, "while {operation} {subject} ({field}): {source}"),
+            Error::Contextual {
+                operation,
+                subject,
+                field: None,
+                source,
+            } => write!(f, "while {operation} {subject}: {source}"),
+            Error::UnexpectedDatatype {
+                expected,
+                found,
+                subject,
+                predicate,
+            } => write!(
+                f,
+                "expected a literal of <{expected}> as object of <{predicate}> for {subject}, \
+                 found <{found}>"
+            ),
+            Error::IdentifierFieldMissing {
+                struct_name,
+                identifier_field,
+                available_fields,
+            } => write!(
+                f,
+                "identifier field {identifier_field} not found in struct {struct_name}, \
+                 serialized fields: {}",
+                available_fields.join(", ")
+            ),
+            Error::UnknownPrefix {
+                prefix,
+                value,
+                known,
+            } => write!(
+                f,
+                "unknown prefix {prefix} in {value}, known prefixes: {}",
+                known.join(", ")
+            ),
+            Error::UnsupportedFormat {
+                format,
+                operation,
+                feature: Some(feature),
+            } => write!(
+                f,
+                "{operation} {format} requires the `{feature}` feature of serde_rdf"
+            ),
+            Error::UnsupportedFormat {
+                format,
+                operation,
+                feature: None,
+            } => write!(f, "{operation} {format} is not supported"),
         }
     }
 }
@@ -49,7 +478,57 @@ impl From<io::Error> for Error {
     }
 }
 
-impl std::error::Error for Error {}
+/// Errors with a position become [`Error::Parse`], others [`Error::Turtle`].
+impl From<TurtleError> for Error {
+    fn from(err: TurtleError) -> Self {
+        let Some(position) = err.textual_position() else {
+            return Error::Turtle(err);
+        };
+        // The parser counts both from 1 already.
+        let (line, column) = (position.line_number(), position.byte_number());
+        // The message of the parser ends with the position, which is kept separately.
+        let message = err.to_string();
+        let message = message
+            .strip_suffix(&format!(" on line {line} at position {column}"))
+            .map_or(message.clone(), str::to_string);
+        Error::Parse {
+            line,
+            column,
+            message,
+            source: err,
+        }
+    }
+}
+
+#[cfg(feature = "xml")]
+impl From<rio_xml::RdfXmlError> for Error {
+    fn from(err: rio_xml::RdfXmlError) -> Self {
+        Error::RdfXml(err)
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(value: Utf8Error) -> Self {
+        Error::Utf8(value)
+    }
+}
+
+/// The errors of the parsers and of IO, which includes those of the formatter, and the error
+/// within a context.
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Utf8(err) => Some(err),
+            Error::Io(err) => Some(err),
+            Error::Parse { source, .. } => Some(source),
+            Error::Turtle(err) => Some(err),
+            #[cfg(feature = "xml")]
+            Error::RdfXml(err) => Some(err),
+            Error::Contextual { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl ser::Error for Error {
     fn custom<T: Display>(msg: T) -> Self {
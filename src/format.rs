@@ -0,0 +1,125 @@
+//! The RDF serialization formats, to pick a parser or writer from a media type or a file
+//! extension.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// An RDF serialization format, see [`from_str_with_format`](crate::from_str_with_format) and
+/// [`to_string_with_format`](crate::to_string_with_format).
+///
+/// Example:
+/// ```
+/// use serde_rdf::Format;
+/// assert_eq!(Format::from_media_type("text/turtle; charset=utf-8"), Some(Format::Turtle));
+/// assert_eq!(Format::from_extension("nt"), Some(Format::NTriples));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    Turtle,
+    NTriples,
+    NQuads,
+    TriG,
+    /// Needs the `xml` feature.
+    RdfXml,
+    /// Expanded JSON-LD, which needs the `jsonld` feature and is only read.
+    JsonLd,
+}
+
+/// The formats with their media types, the first of which is the registered one.
+const MEDIA_TYPES: &[(Format, &[&str])] = &[
+    (Format::Turtle, &["text/turtle", "application/x-turtle"]),
+    (Format::NTriples, &["application/n-triples"]),
+    (Format::NQuads, &["application/n-quads", "text/x-nquads"]),
+    (Format::TriG, &["application/trig", "application/x-trig"]),
+    (Format::RdfXml, &["application/rdf+xml"]),
+    (Format::JsonLd, &["application/ld+json"]),
+];
+
+/// The formats with their file extensions, the first of which is the usual one.
+const EXTENSIONS: &[(Format, &[&str])] = &[
+    (Format::Turtle, &["ttl"]),
+    (Format::NTriples, &["nt"]),
+    (Format::NQuads, &["nq"]),
+    (Format::TriG, &["trig"]),
+    (Format::RdfXml, &["rdf", "xml"]),
+    (Format::JsonLd, &["jsonld"]),
+];
+
+impl Format {
+    /// The format of a media type, e.g. the `Content-Type` of a response. Parameters such as
+    /// `charset` are ignored, and the type is compared case-insensitively.
+    pub fn from_media_type(media_type: &str) -> Option<Format> {
+        let essence = media_type.split(';').next().unwrap_or_default().trim();
+        MEDIA_TYPES
+            .iter()
+            .find(|(_, types)| types.iter().any(|t| t.eq_ignore_ascii_case(essence)))
+            .map(|(format, _)| *format)
+    }
+
+    /// The format of a file extension, with or without the leading dot, e.g. `ttl`.
+    pub fn from_extension(extension: &str) -> Option<Format> {
+        let extension = extension.strip_prefix('.').unwrap_or(extension);
+        EXTENSIONS
+            .iter()
+            .find(|(_, extensions)| extensions.iter().any(|e| e.eq_ignore_ascii_case(extension)))
+            .map(|(format, _)| *format)
+    }
+
+    /// The registered media type of the format, e.g. `text/turtle`.
+    pub fn media_type(self) -> &'static str {
+        lookup(MEDIA_TYPES, self)
+    }
+
+    /// The usual file extension of the format, without a dot, e.g. `ttl`.
+    pub fn extension(self) -> &'static str {
+        lookup(EXTENSIONS, self)
+    }
+}
+
+fn lookup(table: &[(Format, &[&'static str])], format: Format) -> &'static str {
+    table
+        .iter()
+        .find(|(f, _)| *f == format)
+        .map_or("", |(_, names)| names[0])
+}
+
+/// Parses the name of a format ignoring case, dashes and slashes, e.g. `turtle`, `n-triples` or
+/// `RDF/XML`, as well as its file extensions and media types.
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let name: String = value
+            .chars()
+            .filter(|c| !matches!(c, '-' | '/'))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let format = match name.as_str() {
+            "turtle" => Some(Format::Turtle),
+            "ntriples" => Some(Format::NTriples),
+            "nquads" => Some(Format::NQuads),
+            "trig" => Some(Format::TriG),
+            "rdfxml" => Some(Format::RdfXml),
+            "jsonld" => Some(Format::JsonLd),
+            _ => Format::from_extension(value).or_else(|| Format::from_media_type(value)),
+        };
+        format.ok_or_else(|| Error::Message(format!("unknown RDF format: {value}")))
+    }
+}
+
+/// The name of the format, e.g. `N-Triples`.
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Turtle => "Turtle",
+            Format::NTriples => "N-Triples",
+            Format::NQuads => "N-Quads",
+            Format::TriG => "TriG",
+            Format::RdfXml => "RDF/XML",
+            Format::JsonLd => "JSON-LD",
+        })
+    }
+}
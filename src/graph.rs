@@ -0,0 +1,706 @@
+//! An in-memory set of triples, to merge the RDF of several values or sources before writing
+//! it out.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use rio_api::parser::TriplesParser;
+use rio_turtle::{TurtleError, TurtleParser};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::de::from_triples;
+use crate::error::Result;
+use crate::ser::to_triples;
+use crate::structure::SerializerConfig;
+use crate::triple::{OwnedObject, OwnedSubject, OwnedTriple};
+use crate::turtle::Compact;
+
+/// A set of triples, which keeps the order they were first inserted in.
+///
+/// Example:
+/// ```
+/// use serde_rdf::{Graph, OwnedObject, OwnedSubject};
+///
+/// let graph = Graph::parse_turtle(
+///     "<https://example.org/p> <https://example.org/ns#hasName> \"Events\" .",
+/// )
+/// .unwrap();
+/// let subject = OwnedSubject::NamedNode("https://example.org/p".to_string());
+/// let names: Vec<_> = graph
+///     .objects_for(&subject, "https://example.org/ns#hasName")
+///     .collect();
+/// assert_eq!(names.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    triples: Vec<OwnedTriple>,
+    index: HashSet<OwnedTriple>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    /// Parse a Turtle document into a graph.
+    pub fn parse_turtle(ttl: &str) -> Result<Graph> {
+        let mut graph = Graph::new();
+        TurtleParser::new(ttl.as_bytes(), None).parse_all(&mut |triple| -> std::result::Result<
+            (),
+            TurtleError,
+        > {
+            graph.insert(OwnedTriple::from(&triple));
+            Ok(())
+        })?;
+        Ok(graph)
+    }
+
+    /// Add a triple, returning whether it was not in the graph yet.
+    pub fn insert(&mut self, triple: OwnedTriple) -> bool {
+        if self.index.contains(&triple) {
+            return false;
+        }
+        self.index.insert(triple.clone());
+        self.triples.push(triple);
+        true
+    }
+
+    /// Remove a triple, returning whether it was in the graph.
+    pub fn remove(&mut self, triple: &OwnedTriple) -> bool {
+        if !self.index.remove(triple) {
+            return false;
+        }
+        self.triples.retain(|t| t != triple);
+        true
+    }
+
+    pub fn contains(&self, triple: &OwnedTriple) -> bool {
+        self.index.contains(triple)
+    }
+
+    pub fn len(&self) -> usize {
+        self.triples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triples.is_empty()
+    }
+
+    /// The triples in the order they were inserted.
+    pub fn triples(&self) -> &[OwnedTriple] {
+        &self.triples
+    }
+
+    /// The distinct subjects, in the order they first appear.
+    pub fn subjects(&self) -> impl Iterator<Item = &OwnedSubject> + '_ {
+        let mut seen = HashSet::new();
+        self.triples
+            .iter()
+            .map(|triple| &triple.subject)
+            .filter(move |subject| seen.insert(*subject))
+    }
+
+    /// The objects of the triples with the given subject and predicate IRI.
+    pub fn objects_for<'a>(
+        &'a self,
+        subject: &'a OwnedSubject,
+        predicate: &'a str,
+    ) -> impl Iterator<Item = &'a OwnedObject> + 'a {
+        self.triples
+            .iter()
+            .filter(move |triple| triple.subject == *subject && triple.predicate == predicate)
+            .map(|triple| &triple.object)
+    }
+
+    /// The triples of the graph that are not in `other`, compared by blank node label.
+    pub fn difference<'a>(&'a self, other: &'a Graph) -> impl Iterator<Item = &'a OwnedTriple> {
+        self.triples
+            .iter()
+            .filter(move |triple| !other.contains(triple))
+    }
+
+    /// The changes that turn this graph into `other`: the triples `other` adds and those it
+    /// lacks, e.g. to patch a store holding an earlier version of the same record. Blank nodes
+    /// are compared by label, which stay the same across serializations that are
+    /// [`deterministic`](SerializerConfig::deterministic) or
+    /// [`skolemize`](SerializerConfig::skolemize) them.
+    pub fn diff(&self, other: &Graph) -> GraphDiff {
+        GraphDiff {
+            added: other.difference(self).cloned().collect(),
+            removed: self.difference(other).cloned().collect(),
+        }
+    }
+
+    /// Add the triples of `other`, relabeling its blank nodes whose labels occur in this graph
+    /// as well, e.g. `_:b0` as `_:b0_1`, so that blank nodes of the two graphs stay distinct.
+    /// Triples that are already in the graph are not added twice.
+    pub fn merge(&mut self, other: Graph) {
+        let mut own = HashSet::new();
+        for triple in &self.triples {
+            for_blank_nodes(triple, &mut |node| {
+                own.insert(node.to_string());
+            });
+        }
+        let mut used = own.clone();
+        for triple in &other.triples {
+            for_blank_nodes(triple, &mut |node| {
+                used.insert(node.to_string());
+            });
+        }
+        let mut relabeled = HashMap::new();
+        for triple in &other.triples {
+            for_blank_nodes(triple, &mut |node| {
+                if !own.contains(node) || relabeled.contains_key(node) {
+                    return;
+                }
+                let label = (1..)
+                    .map(|n| format!("{node}_{n}"))
+                    .find(|label| !used.contains(label))
+                    .unwrap_or_default();
+                used.insert(label.clone());
+                relabeled.insert(node, label);
+            });
+        }
+        let bijection = relabeled
+            .iter()
+            .map(|(node, label)| (*node, label.as_str()))
+            .collect();
+        for triple in &other.triples {
+            self.insert(map_triple(triple, &bijection));
+        }
+    }
+
+    /// Whether the graphs are equal up to the labels of their blank nodes, i.e. there is a
+    /// bijection between their blank nodes that maps the triples of one onto the other.
+    ///
+    /// The blank nodes are colored by their neighbourhood until the coloring is stable, and
+    /// only blank nodes of the same color are tried as images of each other. Each triple is
+    /// checked as soon as all of its blank nodes are mapped, so that a wrong image is given up
+    /// before the blank nodes after it are tried.
+    pub fn is_isomorphic(&self, other: &Graph) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let (colors, other_colors) = (self.blank_node_colors(), other.blank_node_colors());
+        let sorted = |colors: &HashMap<&str, u64>| {
+            let mut sorted: Vec<_> = colors.values().copied().collect();
+            sorted.sort_unstable();
+            sorted
+        };
+        if sorted(&colors) != sorted(&other_colors) {
+            return false;
+        }
+        // The most distinctive blank nodes first, as they have the fewest candidates.
+        let mut blank_nodes: Vec<_> = colors.iter().map(|(node, color)| (*node, *color)).collect();
+        let frequency = |color: u64| colors.values().filter(|c| **c == color).count();
+        blank_nodes.sort_by_key(|(node, color)| (frequency(*color), *color, *node));
+        let mut candidates: HashMap<u64, Vec<&str>> = HashMap::new();
+        for (node, color) in &other_colors {
+            candidates.entry(*color).or_default().push(*node);
+        }
+        for nodes in candidates.values_mut() {
+            nodes.sort_unstable();
+        }
+        // The triples to check once the blank node at each position is mapped, those without
+        // blank nodes right away.
+        let positions: HashMap<&str, usize> = blank_nodes
+            .iter()
+            .enumerate()
+            .map(|(position, (node, _))| (*node, position))
+            .collect();
+        let mut checks = vec![Vec::new(); blank_nodes.len()];
+        for triple in &self.triples {
+            let mut last = None;
+            for_blank_nodes(triple, &mut |node| last = last.max(Some(positions[node])));
+            match last {
+                Some(last) => checks[last].push(triple),
+                None if !other.contains(triple) => return false,
+                None => {}
+            }
+        }
+        let mut bijection = HashMap::new();
+        let mut images = HashSet::new();
+        map_blank_nodes(
+            other,
+            &blank_nodes,
+            &candidates,
+            &checks,
+            &mut bijection,
+            &mut images,
+        )
+    }
+
+    /// A color for each blank node, refined by the colors of its neighbours until the number
+    /// of colors no longer grows. Blank nodes that are images of each other under an
+    /// isomorphism have the same color.
+    fn blank_node_colors(&self) -> HashMap<&str, u64> {
+        let mut colors = HashMap::new();
+        for triple in &self.triples {
+            for_blank_nodes(triple, &mut |node| {
+                colors.insert(node, 0);
+            });
+        }
+        let mut count = 1;
+        loop {
+            let mut signatures: HashMap<&str, Vec<u64>> = HashMap::new();
+            for triple in &self.triples {
+                let subject = subject_key(&triple.subject, &colors);
+                let object = object_key(&triple.object, &colors);
+                if let OwnedSubject::BlankNode(node) = &triple.subject {
+                    let signature = hash(&("subject", &triple.predicate, object));
+                    signatures.entry(node.as_str()).or_default().push(signature);
+                }
+                if let OwnedObject::BlankNode(node) = &triple.object {
+                    let signature = hash(&("object", &triple.predicate, subject));
+                    signatures.entry(node.as_str()).or_default().push(signature);
+                }
+            }
+            let refined: HashMap<_, _> = colors
+                .iter()
+                .map(|(node, color)| {
+                    let mut signature = signatures.remove(node).unwrap_or_default();
+                    signature.sort_unstable();
+                    (*node, hash(&(color, signature)))
+                })
+                .collect();
+            let refined_count = refined.values().collect::<HashSet<_>>().len();
+            colors = refined;
+            if refined_count <= count {
+                return colors;
+            }
+            count = refined_count;
+        }
+    }
+
+    /// The graph as canonical N-Quads according to
+    /// [RDFC-1.0](https://www.w3.org/TR/rdf-canon/), with the blank nodes labeled `c14n0`,
+    /// `c14n1`, …, so that isomorphic graphs have the same output, e.g. to hash or sign it.
+    ///
+    /// # Errors
+    ///
+    /// Fails for graphs with quoted triples, and for graphs whose blank nodes are so hard to
+    /// tell apart that canonicalizing them would take too long.
+    #[cfg(feature = "canonicalize")]
+    pub fn canonicalize(&self) -> Result<String> {
+        crate::canon::canonicalize(&self.triples)
+    }
+
+    /// Format the graph as Turtle, writing IRIs within the given namespaces as prefixed names.
+    pub fn to_turtle(&self, namespaces: &HashMap<String, String>) -> String {
+        Compact::new(&self.triples, namespaces).to_string()
+    }
+}
+
+/// The triples added and removed between two graphs, see [`Graph::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphDiff {
+    /// The triples of the new graph that the old one lacks, in the order of the new graph.
+    pub added: Vec<OwnedTriple>,
+    /// The triples of the old graph that the new one lacks, in the order of the old graph.
+    pub removed: Vec<OwnedTriple>,
+}
+
+impl GraphDiff {
+    /// Whether the graphs hold the same triples.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Graphs are equal if they hold the same triples, in any order.
+impl PartialEq for Graph {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl Eq for Graph {}
+
+impl Extend<OwnedTriple> for Graph {
+    fn extend<I: IntoIterator<Item = OwnedTriple>>(&mut self, triples: I) {
+        for triple in triples {
+            self.insert(triple);
+        }
+    }
+}
+
+impl FromIterator<OwnedTriple> for Graph {
+    fn from_iter<I: IntoIterator<Item = OwnedTriple>>(triples: I) -> Self {
+        let mut graph = Graph::new();
+        graph.extend(triples);
+        graph
+    }
+}
+
+impl IntoIterator for Graph {
+    type Item = OwnedTriple;
+    type IntoIter = std::vec::IntoIter<OwnedTriple>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.triples.into_iter()
+    }
+}
+
+fn hash(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A hash of the subject with its blank nodes replaced by their colors.
+fn subject_key(subject: &OwnedSubject, colors: &HashMap<&str, u64>) -> u64 {
+    match subject {
+        OwnedSubject::NamedNode(iri) => hash(&(0, iri)),
+        OwnedSubject::BlankNode(id) => hash(&(1, colors[id.as_str()])),
+        OwnedSubject::Triple(triple) => triple_key(triple, colors),
+    }
+}
+
+/// A hash of the object with its blank nodes replaced by their colors.
+fn object_key(object: &OwnedObject, colors: &HashMap<&str, u64>) -> u64 {
+    match object {
+        OwnedObject::NamedNode(iri) => hash(&(0, iri)),
+        OwnedObject::BlankNode(id) => hash(&(1, colors[id.as_str()])),
+        OwnedObject::Literal(literal) => hash(&(2, literal)),
+        OwnedObject::Triple(triple) => triple_key(triple, colors),
+    }
+}
+
+fn triple_key(triple: &OwnedTriple, colors: &HashMap<&str, u64>) -> u64 {
+    hash(&(
+        3,
+        subject_key(&triple.subject, colors),
+        &triple.predicate,
+        object_key(&triple.object, colors),
+    ))
+}
+
+/// Call `f` with the label of each blank node of the triple, including quoted triples.
+fn for_blank_nodes<'a>(triple: &'a OwnedTriple, f: &mut dyn FnMut(&'a str)) {
+    match &triple.subject {
+        OwnedSubject::BlankNode(id) => f(id),
+        OwnedSubject::Triple(quoted) => for_blank_nodes(quoted, f),
+        OwnedSubject::NamedNode(_) => {}
+    }
+    match &triple.object {
+        OwnedObject::BlankNode(id) => f(id),
+        OwnedObject::Triple(quoted) => for_blank_nodes(quoted, f),
+        OwnedObject::NamedNode(_) | OwnedObject::Literal(_) => {}
+    }
+}
+
+/// Extend the partial `bijection` of the first blank nodes to the rest of `blank_nodes`, trying
+/// the unused blank nodes of `other` with the same color as images and checking the triples
+/// whose last blank node was just mapped.
+fn map_blank_nodes<'a>(
+    other: &Graph,
+    blank_nodes: &[(&'a str, u64)],
+    candidates: &HashMap<u64, Vec<&'a str>>,
+    checks: &[Vec<&OwnedTriple>],
+    bijection: &mut HashMap<&'a str, &'a str>,
+    images: &mut HashSet<&'a str>,
+) -> bool {
+    let position = bijection.len();
+    let Some((node, color)) = blank_nodes.get(position) else {
+        return true;
+    };
+    for candidate in candidates.get(color).into_iter().flatten() {
+        if !images.insert(*candidate) {
+            continue;
+        }
+        bijection.insert(*node, *candidate);
+        let consistent = checks[position]
+            .iter()
+            .all(|triple| other.contains(&map_triple(triple, bijection)));
+        if consistent && map_blank_nodes(other, blank_nodes, candidates, checks, bijection, images)
+        {
+            return true;
+        }
+        bijection.remove(node);
+        images.remove(candidate);
+    }
+    false
+}
+
+/// The triple with its blank nodes relabeled by the bijection.
+fn map_triple(triple: &OwnedTriple, bijection: &HashMap<&str, &str>) -> OwnedTriple {
+    let label = |id: &String| {
+        bijection
+            .get(id.as_str())
+            .map_or(id.clone(), |l| l.to_string())
+    };
+    OwnedTriple {
+        subject: match &triple.subject {
+            OwnedSubject::BlankNode(id) => OwnedSubject::BlankNode(label(id)),
+            OwnedSubject::Triple(quoted) => {
+                OwnedSubject::Triple(Box::new(map_triple(quoted, bijection)))
+            }
+            subject => subject.clone(),
+        },
+        predicate: triple.predicate.clone(),
+        object: match &triple.object {
+            OwnedObject::BlankNode(id) => OwnedObject::BlankNode(label(id)),
+            OwnedObject::Triple(quoted) => {
+                OwnedObject::Triple(Box::new(map_triple(quoted, bijection)))
+            }
+            object => object.clone(),
+        },
+    }
+}
+
+/// Assert that two [`Graph`]s are isomorphic, i.e. equal up to blank node labels, printing
+/// the triples of each that the other lacks otherwise. Blank nodes are compared by label in
+/// that listing.
+///
+/// Example:
+/// ```
+/// # #[cfg(feature = "testing")] {
+/// use serde_rdf::{assert_graph_eq, Graph};
+/// let left = Graph::parse_turtle("_:a <https://example.org/p> _:b .").unwrap();
+/// let right = Graph::parse_turtle("_:x <https://example.org/p> _:y .").unwrap();
+/// assert_graph_eq!(left, right);
+/// # }
+/// ```
+#[cfg(any(test, feature = "testing"))]
+#[macro_export]
+macro_rules! assert_graph_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::Graph::is_isomorphic(left, right) {
+                    let lines = |graph: &$crate::Graph, other: &$crate::Graph| {
+                        graph
+                            .difference(other)
+                            .map(|triple| ::std::format!("  {:?}", triple))
+                            .collect::<::std::vec::Vec<_>>()
+                            .join("\n")
+                    };
+                    ::std::panic!(
+                        "assertion failed: graphs are not isomorphic\nonly in left:\n{}\nonly in right:\n{}",
+                        lines(left, right),
+                        lines(right, left)
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Serialize the given data structure into a graph, which the triples of further values can
+/// be added to with [`Extend`].
+pub fn to_graph<T>(value: &T, config: &SerializerConfig) -> Result<Graph>
+where
+    T: ?Sized + Serialize,
+{
+    Ok(to_triples(value, config)?.into_iter().collect())
+}
+
+/// Deserialize an instance of type `T` from the subject with the given IRI in a graph.
+///
+/// # Errors
+///
+/// This functions fails with an [`Error::SubjectNotFound`](crate::Error::SubjectNotFound) if
+/// the subject has no triples, or with an error if the data does not match the structure of
+/// `T`.
+pub fn from_graph<T>(graph: &Graph, subject: &str) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    from_triples(graph.triples.iter().cloned(), subject)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        from_graph, to_graph, to_triples, Graph, GraphDiff, OwnedLiteral, OwnedObject,
+        OwnedSubject, OwnedTriple, SerializerConfig, SubjectConfig,
+    };
+
+    #[test]
+    fn test_graph() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Project {
+            id: String,
+            name: String,
+        }
+
+        let config = || {
+            SerializerConfig::builder()
+                .subject(
+                    SubjectConfig::builder("Project")
+                        .rdf_type("https://ns.dasch.swiss/repository#Project")
+                        .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                        .property("name", "https://ns.dasch.swiss/repository#hasName")
+                        .build(),
+                )
+                .build()
+                .unwrap()
+        };
+        let events = Project {
+            id: "081C".to_string(),
+            name: "Events".to_string(),
+        };
+        let letters = Project {
+            id: "0803".to_string(),
+            name: "Letters".to_string(),
+        };
+
+        let mut graph = to_graph(&events, &config()).unwrap();
+        graph.extend(to_triples(&letters, &config()).unwrap());
+        // Triples that are already in the graph are not added twice.
+        graph.extend(to_triples(&events, &config()).unwrap());
+        assert_eq!(graph.len(), 4);
+        let subjects: Vec<_> = graph.subjects().cloned().collect();
+        let ark = |id: &str| {
+            OwnedSubject::NamedNode(format!("https://ark.dasch.swiss/ark:/72163/1/{id}"))
+        };
+        assert_eq!(subjects, [ark("081C"), ark("0803")]);
+        let subject = ark("0803");
+        let names: Vec<_> = graph
+            .objects_for(&subject, "https://ns.dasch.swiss/repository#hasName")
+            .map(|object| match object {
+                OwnedObject::Literal(literal) => literal.lexical_form(),
+                _ => "",
+            })
+            .collect();
+        assert_eq!(names, ["Letters"]);
+
+        let namespaces = HashMap::from([(
+            "dsp".to_string(),
+            "https://ns.dasch.swiss/repository#".to_string(),
+        )]);
+        let turtle = graph.to_turtle(&namespaces);
+        assert!(turtle.contains("dsp:hasName"), "{turtle}");
+        assert_eq!(Graph::parse_turtle(&turtle).unwrap(), graph);
+
+        let project: Project =
+            from_graph(&graph, "https://ark.dasch.swiss/ark:/72163/1/0803").unwrap();
+        assert_eq!(project.name, "Letters");
+    }
+
+    #[test]
+    fn test_graph_isomorphism() {
+        let graph = |ttl: &str| {
+            Graph::parse_turtle(&format!("@prefix ex: <https://example.org/ns#> . {ttl}")).unwrap()
+        };
+        let people = graph(
+            "_:a ex:knows _:b . _:b ex:knows _:c . _:c ex:knows _:a .
+             _:a ex:name \"Ann\" . _:d ex:knows _:d .",
+        );
+        let permuted = graph(
+            "_:z ex:knows _:y . _:x ex:knows _:z . _:y ex:knows _:x .
+             _:y ex:name \"Ann\" . _:w ex:knows _:w .",
+        );
+        assert!(people.is_isomorphic(&permuted));
+        assert!(permuted.is_isomorphic(&people));
+        crate::assert_graph_eq!(people, permuted);
+
+        // The same shape, but the name is on another node of the cycle, which is no different,
+        // and on the node knowing itself, which is.
+        let rotated = graph(
+            "_:a ex:knows _:b . _:b ex:knows _:c . _:c ex:knows _:a .
+             _:b ex:name \"Ann\" . _:d ex:knows _:d .",
+        );
+        assert!(people.is_isomorphic(&rotated));
+        let near_miss = graph(
+            "_:a ex:knows _:b . _:b ex:knows _:c . _:c ex:knows _:a .
+             _:d ex:name \"Ann\" . _:d ex:knows _:d .",
+        );
+        assert!(!people.is_isomorphic(&near_miss));
+        // Two cycles of two are not a cycle of three and a loop, though every node has the
+        // same degree.
+        let two_cycles = graph(
+            "_:a ex:knows _:b . _:b ex:knows _:a . _:c ex:knows _:d . _:d ex:knows _:c .
+             _:a ex:name \"Ann\" .",
+        );
+        assert!(!people.is_isomorphic(&two_cycles));
+        let renamed = graph(
+            "_:a ex:knows _:b . _:b ex:knows _:c . _:c ex:knows _:a .
+             _:a ex:name \"Anne\" . _:d ex:knows _:d .",
+        );
+        assert!(!people.is_isomorphic(&renamed));
+
+        // Every node of a ring has the same color, so that only pruning keeps the search from
+        // trying all orders of its nodes.
+        let ring = |labels: &[usize]| {
+            let triples: Vec<_> = labels
+                .iter()
+                .zip(labels.iter().cycle().skip(1))
+                .map(|(from, to)| format!("_:b{from:02} ex:knows _:b{to:02} ."))
+                .collect();
+            triples.join(" ")
+        };
+        let ring_of_twelve = graph(&ring(&(0..12).collect::<Vec<_>>()));
+        let shuffled = graph(&ring(&[7, 2, 11, 0, 5, 9, 1, 10, 3, 8, 4, 6]));
+        assert!(ring_of_twelve.is_isomorphic(&shuffled));
+        let rings_of_six = graph(&format!(
+            "{} {}",
+            ring(&(0..6).collect::<Vec<_>>()),
+            ring(&(6..12).collect::<Vec<_>>())
+        ));
+        assert!(!ring_of_twelve.is_isomorphic(&rings_of_six));
+
+        let panic =
+            std::panic::catch_unwind(|| crate::assert_graph_eq!(people, renamed)).unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.contains("Anne"), "{message}");
+    }
+
+    #[test]
+    fn test_graph_diff_and_merge() {
+        let before = Graph::parse_turtle(
+            r#"<https://example.org/p> <https://example.org/ns#name> "Events" ;
+                <https://example.org/ns#shortcode> "081C" ."#,
+        )
+        .unwrap();
+        let after = Graph::parse_turtle(
+            r#"<https://example.org/p> <https://example.org/ns#name> "Letters" ;
+                <https://example.org/ns#shortcode> "081C" ."#,
+        )
+        .unwrap();
+        let name = |value: &str| OwnedTriple {
+            subject: OwnedSubject::NamedNode("https://example.org/p".to_string()),
+            predicate: "https://example.org/ns#name".to_string(),
+            object: OwnedObject::Literal(OwnedLiteral::Simple {
+                value: value.to_string(),
+            }),
+        };
+        assert_eq!(
+            before.diff(&after),
+            GraphDiff {
+                added: vec![name("Letters")],
+                removed: vec![name("Events")],
+            }
+        );
+        assert!(before.diff(&before).is_empty());
+
+        // Shared triples are kept once, blank nodes of both graphs stay distinct.
+        let mut graph = Graph::parse_turtle(
+            r#"<https://example.org/p> <https://example.org/ns#shortcode> "081C" .
+                _:b0 <https://example.org/ns#street> "Main Street" ."#,
+        )
+        .unwrap();
+        let other = Graph::parse_turtle(
+            r#"<https://example.org/p> <https://example.org/ns#shortcode> "081C" .
+                _:b0 <https://example.org/ns#street> "Side Street" .
+                _:b0_1 <https://example.org/ns#street> "Back Street" ."#,
+        )
+        .unwrap();
+        graph.merge(other);
+        let expected = Graph::parse_turtle(
+            r#"<https://example.org/p> <https://example.org/ns#shortcode> "081C" .
+                _:b0 <https://example.org/ns#street> "Main Street" .
+                _:b0_2 <https://example.org/ns#street> "Side Street" .
+                _:b0_1 <https://example.org/ns#street> "Back Street" ."#,
+        )
+        .unwrap();
+        assert_eq!(graph, expected);
+        assert_eq!(graph.triples().len(), 4);
+    }
+}
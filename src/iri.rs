@@ -0,0 +1,34 @@
+//! Helpers for checking IRIs before they are written.
+
+use crate::error::{Error, Result};
+
+/// Check that the given string is an absolute IRI. Otherwise it is reported as an
+/// [`Error::InvalidIri`] together with where it came from, e.g. `rdf_property of field name`.
+pub(crate) fn check_iri(iri: &str, context: impl FnOnce() -> String) -> Result<()> {
+    match is_absolute_iri(iri) {
+        true => Ok(()),
+        false => Err(Error::InvalidIri {
+            iri: iri.to_string(),
+            context: context(),
+        }),
+    }
+}
+
+/// Check that the given string is an absolute IRI, i.e. it starts with a scheme and contains
+/// none of the characters that are not allowed in an IRI reference.
+pub(crate) fn is_absolute_iri(iri: &str) -> bool {
+    let Some((scheme, rest)) = iri.split_once(':') else {
+        return false;
+    };
+    let mut scheme_chars = scheme.chars();
+    let valid_scheme = scheme_chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && scheme_chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+
+    valid_scheme
+        && !rest.is_empty()
+        && !iri.chars().any(|c| {
+            c.is_whitespace()
+                || c.is_control()
+                || matches!(c, '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\')
+        })
+}
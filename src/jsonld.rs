@@ -0,0 +1,272 @@
+//! Read [expanded JSON-LD](https://www.w3.org/TR/json-ld11/#expanded-document-form) into
+//! triples.
+
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::iri::is_absolute_iri;
+use crate::ns::{RDF_FIRST, RDF_NIL, RDF_REST, RDF_TYPE, XSD};
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+
+/// Translate an expanded JSON-LD document, i.e. an array of node objects with full IRIs as
+/// keys, into triples. Named graphs are merged into one.
+pub(crate) fn triples(input: &str) -> Result<Vec<OwnedTriple>> {
+    let document: Value = serde_json::from_str(input).map_err(Error::new)?;
+    let mut expanded = Expanded {
+        triples: Vec::new(),
+        blank_nodes: 0,
+    };
+    for node in array(&document) {
+        expanded.node(node)?;
+    }
+    Ok(expanded.triples)
+}
+
+/// The values of a key, which are an array unless there is only one.
+fn array(value: &Value) -> &[Value] {
+    match value {
+        Value::Array(values) => values,
+        value => std::slice::from_ref(value),
+    }
+}
+
+fn error(message: &str, value: &Value) -> Error {
+    Error::Message(format!("deserialize_jsonld -> {message}, found: {value}"))
+}
+
+fn compact(term: &str) -> Error {
+    Error::Message(format!(
+        "deserialize_jsonld -> compact form not supported, expand the document first: {term}"
+    ))
+}
+
+struct Expanded {
+    triples: Vec<OwnedTriple>,
+    /// The number of blank nodes generated for node objects without `@id` and for lists.
+    blank_nodes: usize,
+}
+
+impl Expanded {
+    fn blank_node(&mut self) -> String {
+        self.blank_nodes += 1;
+        format!("jsonld{}", self.blank_nodes)
+    }
+
+    fn push(&mut self, subject: &OwnedSubject, predicate: &str, object: OwnedObject) {
+        self.triples.push(OwnedTriple {
+            subject: subject.clone(),
+            predicate: predicate.to_string(),
+            object,
+        });
+    }
+
+    /// Add the triples of a node object and return the subject it describes.
+    fn node(&mut self, node: &Value) -> Result<OwnedSubject> {
+        let Value::Object(properties) = node else {
+            return Err(error("expected a node object", node));
+        };
+        if properties.contains_key("@context") {
+            return Err(compact("@context"));
+        }
+        let subject = match properties.get("@id") {
+            Some(Value::String(id)) => match id.strip_prefix("_:") {
+                Some(label) => OwnedSubject::BlankNode(label.to_string()),
+                None => OwnedSubject::NamedNode(id.clone()),
+            },
+            Some(id) => return Err(error("expected an IRI as @id", id)),
+            None => OwnedSubject::BlankNode(self.blank_node()),
+        };
+        for (key, values) in properties {
+            match key.as_str() {
+                "@graph" => {
+                    for node in array(values) {
+                        self.node(node)?;
+                    }
+                }
+                "@type" => {
+                    for class in array(values) {
+                        let Value::String(class) = class else {
+                            return Err(error("expected an IRI as @type", class));
+                        };
+                        self.push(&subject, RDF_TYPE, OwnedObject::NamedNode(class.clone()));
+                    }
+                }
+                // Other keywords, e.g. `@index`, do not produce triples.
+                key if key.starts_with('@') => {}
+                key if !is_absolute_iri(key) => return Err(compact(key)),
+                predicate => {
+                    for value in array(values) {
+                        let object = self.object(value)?;
+                        self.push(&subject, predicate, object);
+                    }
+                }
+            }
+        }
+        Ok(subject)
+    }
+
+    /// The object of a value object, a list object or a node object, of which the triples are
+    /// added as well.
+    fn object(&mut self, value: &Value) -> Result<OwnedObject> {
+        let Value::Object(object) = value else {
+            return Err(error("expected a value, list or node object", value));
+        };
+        if let Some(literal) = object.get("@value") {
+            let lexical = match literal {
+                Value::String(lexical) => lexical.clone(),
+                Value::Bool(_) | Value::Number(_) => literal.to_string(),
+                _ => {
+                    return Err(error(
+                        "expected a string, number or boolean as @value",
+                        literal,
+                    ))
+                }
+            };
+            let datatype = match (literal, object.get("@type")) {
+                (_, Some(Value::String(datatype))) => Some(datatype.clone()),
+                (_, Some(datatype)) => return Err(error("expected an IRI as @type", datatype)),
+                (Value::Bool(_), None) => Some(format!("{XSD}boolean")),
+                (Value::Number(n), None) if n.is_f64() => Some(format!("{XSD}double")),
+                (Value::Number(_), None) => Some(format!("{XSD}integer")),
+                _ => None,
+            };
+            let literal = match (object.get("@language"), datatype) {
+                (Some(Value::String(language)), _) => OwnedLiteral::LanguageTaggedString {
+                    value: lexical,
+                    language: language.clone(),
+                },
+                (Some(language), _) => {
+                    return Err(error("expected a language tag as @language", language))
+                }
+                (None, Some(datatype)) => OwnedLiteral::Typed {
+                    value: lexical,
+                    datatype,
+                },
+                (None, None) => OwnedLiteral::Simple { value: lexical },
+            };
+            return Ok(OwnedObject::Literal(literal));
+        }
+        if let Some(items) = object.get("@list") {
+            return self.list(array(items));
+        }
+        Ok(match self.node(value)? {
+            OwnedSubject::NamedNode(iri) => OwnedObject::NamedNode(iri),
+            OwnedSubject::BlankNode(id) => OwnedObject::BlankNode(id),
+            OwnedSubject::Triple(triple) => OwnedObject::Triple(triple),
+        })
+    }
+
+    /// The head of an `rdf:List` holding the given items.
+    fn list(&mut self, items: &[Value]) -> Result<OwnedObject> {
+        let mut head = OwnedObject::NamedNode(RDF_NIL.to_string());
+        for item in items.iter().rev() {
+            let first = self.object(item)?;
+            let id = self.blank_node();
+            let node = OwnedSubject::BlankNode(id.clone());
+            self.push(&node, RDF_FIRST, first);
+            self.push(&node, RDF_REST, head);
+            head = OwnedObject::BlankNode(id);
+        }
+        Ok(head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use crate::from_jsonld_str;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Project {
+        id: String,
+        name: String,
+        description: HashMap<String, String>,
+        shortcode: String,
+        public: bool,
+        size: u32,
+        datasets: Vec<Dataset>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Dataset {
+        id: String,
+        name: String,
+        keyword: Option<String>,
+    }
+
+    #[test]
+    fn test_from_jsonld() {
+        let input = r#"[
+            {
+                "@id": "https://ark.dasch.swiss/ark:/72163/1/081C",
+                "@type": ["https://ns.dasch.swiss/repository#Project"],
+                "https://ns.dasch.swiss/repository#hasName": [
+                    {"@value": "Hôtel de Musique Bern", "@type": "http://www.w3.org/2001/XMLSchema#string"}
+                ],
+                "https://ns.dasch.swiss/repository#hasDescription": [
+                    {"@value": "Das Hôtel de Musique", "@language": "de"},
+                    {"@value": "The Hôtel de Musique", "@language": "en"}
+                ],
+                "https://ns.dasch.swiss/repository#hasShortcode": [{"@value": "081C"}],
+                "https://ns.dasch.swiss/repository#public": [{"@value": true}],
+                "https://ns.dasch.swiss/repository#hasSize": [{"@value": 42}],
+                "https://ns.dasch.swiss/repository#hasDataset": [
+                    {
+                        "@id": "https://ark.dasch.swiss/ark:/72163/1/081C/d1",
+                        "@type": ["https://ns.dasch.swiss/repository#Dataset"],
+                        "https://ns.dasch.swiss/repository#hasName": [{"@value": "Events"}]
+                    }
+                ]
+            }
+        ]"#;
+        let project: Project = from_jsonld_str(input).unwrap();
+        assert_eq!(
+            project,
+            Project {
+                id: "https://ark.dasch.swiss/ark:/72163/1/081C".to_string(),
+                name: "Hôtel de Musique Bern".to_string(),
+                description: HashMap::from([
+                    ("de".to_string(), "Das Hôtel de Musique".to_string()),
+                    ("en".to_string(), "The Hôtel de Musique".to_string()),
+                ]),
+                shortcode: "081C".to_string(),
+                public: true,
+                size: 42,
+                datasets: vec![Dataset {
+                    id: "https://ark.dasch.swiss/ark:/72163/1/081C/d1".to_string(),
+                    name: "Events".to_string(),
+                    keyword: None,
+                }],
+            }
+        );
+
+        #[derive(Debug, Deserialize)]
+        struct Keywords {
+            keyword: Vec<String>,
+        }
+        let input = r#"{
+            "https://ns.dasch.swiss/repository#hasKeyword": [
+                {"@list": [{"@value": "music"}, {"@value": "Bern"}]}
+            ]
+        }"#;
+        let keywords: Keywords = from_jsonld_str(input).unwrap();
+        assert_eq!(keywords.keyword, ["music", "Bern"]);
+
+        let input = r#"{
+            "@context": {"name": "https://ns.dasch.swiss/repository#hasName"},
+            "name": "Events"
+        }"#;
+        let error = from_jsonld_str::<Dataset>(input).unwrap_err().to_string();
+        assert!(error.contains("compact form not supported"), "{error}");
+        let error = from_jsonld_str::<Dataset>(r#"{"name": "Events"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("compact form not supported, expand the document first: name"),
+            "{error}"
+        );
+    }
+}
@@ -1,13 +1,92 @@
+// The code generated by `#[derive(RdfMapped)]` refers to `::serde_rdf`, also in the tests.
+extern crate self as serde_rdf;
+
+#[cfg(feature = "rdf-star")]
+mod annotated;
+mod binary;
+#[cfg(feature = "canonicalize")]
+mod canon;
+mod compiled;
 mod de;
+mod duration;
 mod error;
+mod format;
+mod graph;
+mod iri;
+#[cfg(feature = "jsonld")]
+mod jsonld;
+mod mapped;
+pub mod ns;
+mod ontology;
+#[cfg(feature = "oxrdf")]
+mod oxrdf;
 mod ser;
+mod shacl;
+#[cfg(feature = "sophia")]
+mod sophia;
+#[cfg(feature = "sparql")]
+mod sparql;
+#[cfg(feature = "oxigraph")]
+mod store;
 mod structure;
+mod triple;
+mod turtle;
+pub mod value;
+pub mod with;
+mod xsd;
 
+#[cfg(feature = "rdf-star")]
+#[doc(inline)]
+pub use annotated::Annotated;
+#[cfg(feature = "jsonld")]
+#[doc(inline)]
+pub use de::from_jsonld_str;
+#[cfg(feature = "sparql")]
+#[doc(inline)]
+pub use de::from_sparql_json;
+#[doc(inline)]
+pub use de::{
+    from_nquads_str, from_ntriples_reader, from_ntriples_str, from_reader, from_slice, from_str,
+    from_str_all, from_str_by_type, from_str_for_subject, from_str_lenient, from_str_with_config,
+    from_str_with_format, from_trig_str, from_triples, Deserializer, SubjectStream,
+};
+#[cfg(feature = "xml")]
+#[doc(inline)]
+pub use de::{from_rdfxml_reader, from_rdfxml_str};
+#[doc(inline)]
+pub use duration::XsdDuration;
+#[doc(inline)]
+pub use error::{Error, ErrorKind, ParseIssue, Result};
+#[doc(inline)]
+pub use format::Format;
+#[doc(inline)]
+pub use graph::{from_graph, to_graph, Graph, GraphDiff};
+#[doc(inline)]
+pub use mapped::{from_str_mapped, to_string_mapped, RdfMapped};
+#[cfg(feature = "json")]
+#[doc(inline)]
+pub use ser::json_to_rdf;
+#[cfg(feature = "rayon")]
+#[doc(inline)]
+pub use ser::to_string_par;
+#[cfg(feature = "async")]
+#[doc(inline)]
+pub use ser::{to_async_writer, to_async_writer_all};
+#[doc(inline)]
+pub use ser::{
+    to_string, to_string_collecting, to_string_with_format, to_triples, to_writer, Serializer,
+};
+#[cfg(feature = "derive")]
+pub use serde_rdf_derive::RdfMapped;
+#[cfg(feature = "oxigraph")]
 #[doc(inline)]
-pub use de::{from_str, Deserializer};
+pub use store::{from_store, to_store};
 #[doc(inline)]
-pub use error::{Error, Result};
+pub use structure::{
+    DeserializerConfig, PropertyConfig, SerializerConfig, SerializerConfigBuilder, SubjectBuilder,
+    SubjectConfig, SubjectHook, TermKind,
+};
 #[doc(inline)]
-pub use ser::{to_string, Serializer};
+pub use triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
 #[doc(inline)]
-pub use structure::{PropertyConfig, SerializerConfig, SubjectConfig};
+pub use xsd::XsdType;
@@ -0,0 +1,93 @@
+//! Types that carry their own RDF mapping, so that they can be serialized and deserialized
+//! without a hand-written config.
+
+use serde::{Deserialize, Serialize};
+
+use crate::de::from_str_with_config;
+use crate::error::Result;
+use crate::ser::to_string;
+use crate::structure::{DeserializerConfig, SerializerConfig, SubjectConfig};
+
+/// A struct that declares how it maps to RDF, either implemented by hand or with
+/// `#[derive(RdfMapped)]` of the `derive` feature:
+///
+/// ```ignore
+/// use serde::Serialize;
+/// use serde_rdf::RdfMapped;
+///
+/// #[derive(Serialize, RdfMapped)]
+/// #[rdf(
+///     type = "dsp:Project",
+///     id(field = "id", prefix = "https://ark.dasch.swiss/ark:/72163/1/"),
+///     prefix(dsp = "https://ns.dasch.swiss/repository#")
+/// )]
+/// struct Project {
+///     id: String,
+///     #[rdf(property = "dsp:hasName", lang = "en")]
+///     name: String,
+///     #[rdf(property = "dsp:hasDataset", nested = Dataset)]
+///     datasets: Vec<Dataset>,
+/// }
+///
+/// let ttl = serde_rdf::to_string_mapped(&project).unwrap();
+/// ```
+pub trait RdfMapped {
+    /// The subject config of the struct. Its names may be prefixed with the prefixes of
+    /// [`namespaces`](RdfMapped::namespaces) or the
+    /// [well-known prefixes](crate::ns::WELL_KNOWN_PREFIXES).
+    fn subject_config() -> SubjectConfig;
+
+    /// The namespaces of the prefixed names in the subject config, as prefix and namespace.
+    fn namespaces() -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// Add the configs of the structs in the fields, usually by calling their
+    /// [`collect_configs`](RdfMapped::collect_configs).
+    fn nested_configs(_config: &mut SerializerConfig) {}
+
+    /// Add the subject config and namespaces of the struct and of its nested structs to the
+    /// config. A struct that is already in the config is skipped, so that recursive types
+    /// terminate, and prefixes that are already declared are kept.
+    fn collect_configs(config: &mut SerializerConfig) {
+        let subject = Self::subject_config();
+        if config.subjects.contains_key(&subject.struct_name) {
+            return;
+        }
+        config.subjects.insert(subject.struct_name.clone(), subject);
+        for (prefix, namespace) in Self::namespaces() {
+            config.namespaces.entry(prefix).or_insert(namespace);
+        }
+        Self::nested_configs(config);
+    }
+
+    /// A config with the [well-known prefixes](crate::ns::WELL_KNOWN_PREFIXES) and the
+    /// collected configs of the struct.
+    fn serializer_config() -> SerializerConfig {
+        let mut config = SerializerConfig::with_well_known_prefixes();
+        Self::collect_configs(&mut config);
+        config
+    }
+}
+
+/// Serialize the given data structure as a String of Turtle, with the mapping declared by its
+/// type.
+pub fn to_string_mapped<T>(value: &T) -> Result<String>
+where
+    T: Serialize + RdfMapped,
+{
+    to_string(value, &T::serializer_config())
+}
+
+/// Deserialize an instance of type `T` from a string of Turtle, with the mapping declared by
+/// the type, so that it reads the output of [`to_string_mapped`].
+///
+/// # Errors
+///
+/// This functions fails with an error if the data does not match the structure of `T`.
+pub fn from_str_mapped<'de, T>(s: &'de str) -> Result<T>
+where
+    T: Deserialize<'de> + RdfMapped,
+{
+    from_str_with_config(s, &DeserializerConfig::try_from(&T::serializer_config())?)
+}
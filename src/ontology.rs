@@ -0,0 +1,209 @@
+//! Derive a [`SerializerConfig`] from the classes and properties of an RDFS or OWL ontology.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rio_api::parser::TriplesParser;
+use rio_turtle::{TurtleError, TurtleParser};
+
+use crate::error::Result;
+use crate::ns::{OWL, RDFS, RDF_LANG_STRING, RDF_TYPE, XSD};
+use crate::structure::{PropertyConfig, SerializerConfig, SubjectConfig, TermKind};
+use crate::triple::{OwnedObject, OwnedSubject, OwnedTriple};
+use crate::xsd::XsdType;
+
+/// A config with a subject per class of the ontology, identified by the field `id` and
+/// prefixed with `base_iri`, and a property per property whose `rdfs:domain` is that class.
+pub(crate) fn config(ttl: &str, base_iri: &str) -> Result<SerializerConfig> {
+    let triples = parse(ttl, base_iri)?;
+    let iri = |object: &OwnedObject| match object {
+        OwnedObject::NamedNode(iri) => Some(iri.clone()),
+        _ => None,
+    };
+    let mut classes = BTreeSet::new();
+    // The domains and ranges of each property, by IRI.
+    let mut domains: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut ranges: BTreeMap<String, String> = BTreeMap::new();
+    for triple in &triples {
+        let OwnedSubject::NamedNode(subject) = &triple.subject else {
+            continue;
+        };
+        let Some(object) = iri(&triple.object) else {
+            continue;
+        };
+        match triple.predicate.as_str() {
+            RDF_TYPE if [format!("{RDFS}Class"), format!("{OWL}Class")].contains(&object) => {
+                classes.insert(subject.clone());
+            }
+            p if p == format!("{RDFS}domain") => {
+                domains.entry(subject.clone()).or_default().push(object);
+            }
+            p if p == format!("{RDFS}range") => {
+                ranges.insert(subject.clone(), object);
+            }
+            _ => {}
+        }
+    }
+
+    let mut config = SerializerConfig {
+        base_iri: base_iri.to_string(),
+        ..Default::default()
+    };
+    for class in &classes {
+        let mut properties = Vec::new();
+        for (property, _) in domains.iter().filter(|(_, d)| d.contains(class)) {
+            let range = ranges.get(property);
+            let datatype = range
+                .filter(|range| range.starts_with(XSD) || *range == RDF_LANG_STRING)
+                .and_then(|range| range.parse::<XsdType>().ok());
+            let kind = match range {
+                Some(range) if classes.contains(range) => Some(TermKind::Subject),
+                _ if datatype.is_some() => Some(TermKind::Literal),
+                _ => None,
+            };
+            properties.push(PropertyConfig {
+                struct_field: snake_case(local_name(property)),
+                rdf_property: property.clone(),
+                datatype,
+                kind,
+                ..Default::default()
+            });
+        }
+        let struct_name = local_name(class).to_string();
+        config.subjects.insert(
+            struct_name.clone(),
+            SubjectConfig {
+                struct_name,
+                rdf_type: class.clone(),
+                identifier_field: "id".to_string(),
+                identifier_prefix: base_iri.to_string(),
+                properties,
+                ..Default::default()
+            },
+        );
+    }
+    config.validate()?;
+    Ok(config)
+}
+
+/// Parse the ontology, resolving relative IRIs against `base_iri` unless it declares its own.
+fn parse(ttl: &str, base_iri: &str) -> Result<Vec<OwnedTriple>> {
+    // The base is declared on the first line, so that the parser reports the same lines.
+    let input = match base_iri {
+        "" => ttl.to_string(),
+        base_iri => format!("@base <{base_iri}> . {ttl}"),
+    };
+    let mut triples = Vec::new();
+    TurtleParser::new(input.as_bytes(), None).parse_all(&mut |triple| -> std::result::Result<
+        (),
+        TurtleError,
+    > {
+        triples.push(OwnedTriple::from(&triple));
+        Ok(())
+    })?;
+    Ok(triples)
+}
+
+/// The part of an IRI after the last `#` or `/`.
+pub(crate) fn local_name(iri: &str) -> &str {
+    iri.rsplit(['#', '/']).next().unwrap_or(iri)
+}
+
+/// Convert a camel case name, e.g. `hasShortcode`, into snake case, e.g. `has_shortcode`.
+pub(crate) fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && previous_lower {
+            snake.push('_');
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use crate::{
+        ns, to_triples, OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple, SerializerConfig,
+        TermKind, XsdType,
+    };
+
+    #[test]
+    fn test_from_ontology() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            has_name: String,
+            has_size: u32,
+            has_dataset: Vec<Dataset>,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+            has_title: String,
+        }
+
+        let ontology = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            @prefix owl: <http://www.w3.org/2002/07/owl#> .
+            @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+            dsp:Project a rdfs:Class .
+            dsp:Dataset a owl:Class .
+            dsp:hasName rdfs:domain dsp:Project ; rdfs:range xsd:string .
+            dsp:hasSize rdfs:domain dsp:Project ; rdfs:range xsd:integer .
+            dsp:hasDataset rdfs:domain dsp:Project ; rdfs:range dsp:Dataset .
+            dsp:hasTitle rdfs:domain dsp:Dataset .
+        "#;
+        let config =
+            SerializerConfig::from_ontology(ontology, "https://ark.dasch.swiss/ark:/72163/1/")
+                .unwrap();
+        let project = &config.subjects["Project"];
+        assert_eq!(
+            project.rdf_type,
+            "https://ns.dasch.swiss/repository#Project"
+        );
+        let fields: Vec<_> = project
+            .properties
+            .iter()
+            .map(|p| (p.struct_field.as_str(), p.datatype.clone(), p.kind))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("has_dataset", None, Some(TermKind::Subject)),
+                ("has_name", Some(XsdType::String), Some(TermKind::Literal)),
+                ("has_size", Some(XsdType::Integer), Some(TermKind::Literal)),
+            ]
+        );
+
+        let project = Project {
+            id: "081C".to_string(),
+            has_name: "Hôtel de Musique Bern".to_string(),
+            has_size: 42,
+            has_dataset: vec![Dataset {
+                id: "081C/d1".to_string(),
+                has_title: "Events".to_string(),
+            }],
+        };
+        let triples = to_triples(&project, &config).unwrap();
+        assert!(triples.contains(&OwnedTriple {
+            subject: OwnedSubject::NamedNode("https://ark.dasch.swiss/ark:/72163/1/081C".into()),
+            predicate: "https://ns.dasch.swiss/repository#hasDataset".to_string(),
+            object: OwnedObject::NamedNode("https://ark.dasch.swiss/ark:/72163/1/081C/d1".into()),
+        }));
+        assert!(triples.contains(&OwnedTriple {
+            subject: OwnedSubject::NamedNode("https://ark.dasch.swiss/ark:/72163/1/081C/d1".into()),
+            predicate: "https://ns.dasch.swiss/repository#hasTitle".to_string(),
+            object: OwnedObject::Literal(OwnedLiteral::Typed {
+                value: "Events".to_string(),
+                datatype: ns::XSD_STRING.to_string(),
+            }),
+        }));
+    }
+}
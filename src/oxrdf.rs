@@ -0,0 +1,162 @@
+//! Convert triples and graphs to and from those of [oxrdf](https://docs.rs/oxrdf), the data
+//! model of Oxigraph.
+//!
+//! The terms of this crate are not checked when they are converted, just as rio does not
+//! check them when it parses them. A literal typed as `xsd:string` becomes a simple literal.
+
+use oxrdf::{BlankNode, Literal, NamedNode, Subject, Term, Triple};
+
+use crate::graph::Graph;
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+
+impl From<OwnedTriple> for Triple {
+    fn from(triple: OwnedTriple) -> Self {
+        Triple::new(
+            Subject::from(triple.subject),
+            NamedNode::new_unchecked(triple.predicate),
+            Term::from(triple.object),
+        )
+    }
+}
+
+impl From<OwnedSubject> for Subject {
+    fn from(subject: OwnedSubject) -> Self {
+        match subject {
+            OwnedSubject::NamedNode(iri) => NamedNode::new_unchecked(iri).into(),
+            OwnedSubject::BlankNode(id) => BlankNode::new_unchecked(id).into(),
+            OwnedSubject::Triple(triple) => Subject::Triple(Box::new(Triple::from(*triple))),
+        }
+    }
+}
+
+impl From<OwnedObject> for Term {
+    fn from(object: OwnedObject) -> Self {
+        match object {
+            OwnedObject::NamedNode(iri) => NamedNode::new_unchecked(iri).into(),
+            OwnedObject::BlankNode(id) => BlankNode::new_unchecked(id).into(),
+            OwnedObject::Literal(literal) => Literal::from(literal).into(),
+            OwnedObject::Triple(triple) => Term::Triple(Box::new(Triple::from(*triple))),
+        }
+    }
+}
+
+impl From<OwnedLiteral> for Literal {
+    fn from(literal: OwnedLiteral) -> Self {
+        match literal {
+            OwnedLiteral::Simple { value } => Literal::new_simple_literal(value),
+            OwnedLiteral::LanguageTaggedString { value, language } => {
+                Literal::new_language_tagged_literal_unchecked(value, language)
+            }
+            OwnedLiteral::Typed { value, datatype } => {
+                Literal::new_typed_literal(value, NamedNode::new_unchecked(datatype))
+            }
+        }
+    }
+}
+
+impl From<Triple> for OwnedTriple {
+    fn from(triple: Triple) -> Self {
+        OwnedTriple {
+            subject: triple.subject.into(),
+            predicate: triple.predicate.into_string(),
+            object: triple.object.into(),
+        }
+    }
+}
+
+impl From<Subject> for OwnedSubject {
+    fn from(subject: Subject) -> Self {
+        match subject {
+            Subject::NamedNode(iri) => OwnedSubject::NamedNode(iri.into_string()),
+            Subject::BlankNode(id) => OwnedSubject::BlankNode(id.into_string()),
+            Subject::Triple(triple) => OwnedSubject::Triple(Box::new((*triple).into())),
+        }
+    }
+}
+
+impl From<Term> for OwnedObject {
+    fn from(term: Term) -> Self {
+        match term {
+            Term::NamedNode(iri) => OwnedObject::NamedNode(iri.into_string()),
+            Term::BlankNode(id) => OwnedObject::BlankNode(id.into_string()),
+            Term::Literal(literal) => OwnedObject::Literal(literal.into()),
+            Term::Triple(triple) => OwnedObject::Triple(Box::new((*triple).into())),
+        }
+    }
+}
+
+impl From<Literal> for OwnedLiteral {
+    fn from(literal: Literal) -> Self {
+        let (value, datatype, language) = literal.destruct();
+        match (datatype, language) {
+            (_, Some(language)) => OwnedLiteral::LanguageTaggedString { value, language },
+            (Some(datatype), None) => OwnedLiteral::Typed {
+                value,
+                datatype: datatype.into_string(),
+            },
+            (None, None) => OwnedLiteral::Simple { value },
+        }
+    }
+}
+
+impl Graph {
+    /// The graph as an oxrdf graph, e.g. to load it into an Oxigraph store.
+    pub fn to_oxrdf(&self) -> oxrdf::Graph {
+        let mut graph = oxrdf::Graph::new();
+        for triple in self.triples() {
+            graph.insert(&Triple::from(triple.clone()));
+        }
+        graph
+    }
+
+    /// A graph holding the triples of an oxrdf graph, in its order.
+    pub fn from_oxrdf(graph: &oxrdf::Graph) -> Graph {
+        graph
+            .iter()
+            .map(|triple| OwnedTriple::from(triple.into_owned()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Graph, OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+
+    #[test]
+    fn test_oxrdf() {
+        let mut graph = Graph::parse_turtle(
+            r#"<https://example.org/p> <https://example.org/ns#name> "Events" ;
+                <https://example.org/ns#title> "Veranstaltungen"@de ;
+                <https://example.org/ns#size> "12"^^<http://www.w3.org/2001/XMLSchema#integer> ;
+                <https://example.org/ns#address> _:addr0 .
+                _:addr0 <https://example.org/ns#street> "Main Street" ."#,
+        )
+        .unwrap();
+        let quoted = OwnedTriple {
+            subject: OwnedSubject::NamedNode("https://example.org/p".to_string()),
+            predicate: "https://example.org/ns#name".to_string(),
+            object: OwnedObject::Literal(OwnedLiteral::Simple {
+                value: "Events".to_string(),
+            }),
+        };
+        graph.insert(OwnedTriple {
+            subject: OwnedSubject::Triple(Box::new(quoted.clone())),
+            predicate: "https://example.org/ns#source".to_string(),
+            object: OwnedObject::Triple(Box::new(quoted.clone())),
+        });
+
+        let oxrdf_graph = graph.to_oxrdf();
+        assert_eq!(oxrdf_graph.len(), 6);
+        let round_trip = Graph::from_oxrdf(&oxrdf_graph);
+        assert!(round_trip.is_isomorphic(&graph));
+        // Blank node labels survive as well.
+        assert_eq!(round_trip, graph);
+
+        let triple = oxrdf::Triple::from(quoted.clone());
+        assert_eq!(
+            triple.to_string(),
+            r#"<https://example.org/p> <https://example.org/ns#name> "Events""#
+        );
+        assert_eq!(OwnedTriple::from(triple), quoted);
+    }
+}
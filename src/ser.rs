@@ -1,437 +1,1608 @@
-#![allow(unused_variables, unused_imports, dead_code)]
-
 //! Serialize a Rust data structure into RDF data.
 
+use std::borrow::Cow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
 use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use rio_api::formatter::TriplesFormatter;
-use rio_api::model::{NamedNode as RioNamedNode, Triple};
-use rio_turtle::TurtleFormatter;
+use rio_api::model::{
+    BlankNode as RioBlankNode, Literal as RioLiteral, NamedNode as RioNamedNode,
+    Subject as RioSubject, Term as RioTerm, Triple,
+};
+use rio_turtle::{NTriplesFormatter, TurtleFormatter};
+#[cfg(feature = "xml")]
+use rio_xml::RdfXmlFormatter;
 use serde::ser::{self, Serialize};
 
+use crate::compiled::{CompiledConfig, Field};
 use crate::error::{Error, Result};
-use crate::structure::SerializerConfig;
-
-/// Serializer mapping configuration containing mappings aka instructions on how
-/// to serialize a type. There are three possible options:
-/// (1) one IRI: this denotes the name of the field containing the identifier. Further, it provides
-/// the prefix to the identifier used to build the IRI.
-/// (2) one Clazz: this denotes the name of the struct and to what RDF class it should be typed
-/// (3) one or more Property: this denotes the name of the field, it's property IRI, and the
-/// literal xsd:type (`XsdType`) it should be serialized into, if it is a literal, or (`Subject`)
-/// denoting that it is a struct that needs to be serialized as a separate subject.
-///
-/// Example:
-/// ```
-/// use std::collections::HashMap;
-/// use serde_rdf::{SerializerConfig, SubjectConfig, PropertyConfig};
-/// let _config = SerializerConfig{
-///     base_iri: "".to_string(),
-///     namespaces: Default::default(),
-///     subjects: HashMap::from([
-///         ("Project".to_string(), SubjectConfig{
-///             struct_name: "Project".to_string(),
-///             rdf_type: "https://ns.dasch.swiss/repository#Project".to_string(),
-///             identifier_field: "id".to_string(),
-///             identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
-///             properties: vec!(
-///                 PropertyConfig{struct_field: "name".to_string(), rdf_property: "https://ns.dasch.swiss/repository#hasName".to_string()},
-///                 PropertyConfig{struct_field: "description".to_string(), rdf_property: "https://ns.dasch.swiss/repository#hasDescription".to_string()},
-///                 PropertyConfig{struct_field: "shortcode".to_string(), rdf_property: "https://ns.dasch.swiss/repository#hasShortcode".to_string()},
-///                 PropertyConfig{struct_field: "datasets".to_string(), rdf_property: "https://ns.dasch.swiss/repository#hasDataset".to_string()},
-///             ),
-///         }),
-///         ("Dataset".to_string(), SubjectConfig{
-///             struct_name: "Dataset".to_string(),
-///             rdf_type: "https://ns.dasch.swiss/repository#Dataset".to_string(),
-///             identifier_field: "id".to_string(),
-///             identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
-///             properties: vec!(
-///                 PropertyConfig{struct_field: "title".to_string(), rdf_property: "https://ns.dasch.swiss/repository#hasTitle".to_string()}
-///             ),
-///         })])
-/// };
-/// ```
+use crate::format::Format;
+use crate::iri::check_iri;
+use crate::ns::{
+    RDF_OBJECT, RDF_PREDICATE, RDF_STATEMENT, RDF_SUBJECT, RDF_TYPE, XSD_BOOLEAN, XSD_DOUBLE,
+    XSD_INTEGER, XSD_STRING,
+};
+use crate::structure::{SerializerConfig, TermKind};
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedTriple};
+use crate::turtle::Compact;
+use crate::value;
+
+/// Blank node labels handed out outside of deterministic mode, shared by all serializers so that
+/// the output of separate serializations can be merged without the labels colliding.
+static BLANK_NODES: AtomicUsize = AtomicUsize::new(0);
+
+/// Distinguishes the skolem IRIs minted by this process from those of other runs, outside of
+/// deterministic mode.
+static SKOLEM_RUN: OnceLock<String> = OnceLock::new();
+
+/// The label prefix of blank nodes for which no prefix is configured.
+const DEFAULT_BLANK_NODE_PREFIX: &str = "b";
+
+/// Counters for the labels of generated blank nodes, one per label prefix.
+#[derive(Debug, Default)]
+struct BlankNodes {
+    counters: HashMap<String, usize>,
+    /// Put in front of every label, e.g. `t3` for `_:t3b0`, to keep the labels of serializers
+    /// whose output is concatenated apart, see [`to_string_par`].
+    namespace: String,
+}
+
+impl BlankNodes {
+    /// Mint the label of a new blank node. Labels with a configured prefix and, in deterministic
+    /// mode, all labels are numbered from zero for every serializer.
+    fn next(&mut self, prefix: Option<&str>, deterministic: bool) -> String {
+        let n = match prefix {
+            None if !deterministic => BLANK_NODES.fetch_add(1, Ordering::Relaxed),
+            _ => {
+                let prefix = prefix.unwrap_or(DEFAULT_BLANK_NODE_PREFIX);
+                let counter = self.counters.entry(prefix.to_owned()).or_default();
+                *counter += 1;
+                *counter - 1
+            }
+        };
+        format!(
+            "{}{}{n}",
+            self.namespace,
+            prefix.unwrap_or(DEFAULT_BLANK_NODE_PREFIX)
+        )
+    }
+
+    /// Number the blank nodes from zero again, keeping the counters of the prefixes seen.
+    fn reset(&mut self) {
+        self.counters.values_mut().for_each(|counter| *counter = 0);
+    }
+
+    /// A new blank node, or the skolem IRI standing in for it if the config has a `skolemize`
+    /// base, e.g. `https://example.org/.well-known/genid/4f0c…-b0`.
+    fn node(&mut self, prefix: Option<&str>, mapping: &CompiledConfig<'_>) -> Node {
+        let deterministic = mapping.config.deterministic;
+        let label = self.next(prefix, deterministic);
+        match mapping.skolemize() {
+            Some(base) if deterministic => Node::NamedNode(format!("{base}{label}")),
+            Some(base) => {
+                let run = SKOLEM_RUN.get_or_init(|| {
+                    let mut hasher = RandomState::new().build_hasher();
+                    hasher.write_u128(
+                        SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map_or(0, |elapsed| elapsed.as_nanos()),
+                    );
+                    format!("{:016x}", hasher.finish())
+                });
+                Node::NamedNode(format!("{base}{run}-{label}"))
+            }
+            None => Node::BlankNode(label),
+        }
+    }
+}
+
+/// The subject position of a triple.
+#[derive(Debug, Clone)]
+enum Node {
+    NamedNode(String),
+    BlankNode(String),
+}
+
+impl Node {
+    /// The IRI or blank node label.
+    fn label(&self) -> &str {
+        match self {
+            Node::NamedNode(iri) => iri,
+            Node::BlankNode(id) => id,
+        }
+    }
+
+    fn as_rio(&self) -> RioSubject<'_> {
+        match self {
+            Node::NamedNode(iri) => RioNamedNode { iri: iri.as_str() }.into(),
+            Node::BlankNode(id) => RioBlankNode { id: id.as_str() }.into(),
+        }
+    }
+}
 
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
-pub enum Literal<'a> {
-    /// A [simple literal](https://www.w3.org/TR/rdf11-concepts/#dfn-simple-literal) without datatype or language form.
+impl From<Node> for Object {
+    fn from(node: Node) -> Self {
+        match node {
+            Node::NamedNode(iri) => Object::NamedNode(iri),
+            Node::BlankNode(id) => Object::BlankNode(id),
+        }
+    }
+}
+
+/// The object position of a triple that is waiting for its subject IRI to be known.
+#[derive(Debug, Clone)]
+enum Object {
+    NamedNode(String),
+    BlankNode(String),
+    Literal(Literal),
+}
+
+impl Object {
+    /// Return the IRI of a named node or the lexical form of a literal.
+    fn lexical_form(&self) -> &str {
+        match self {
+            Object::NamedNode(iri) => iri,
+            Object::BlankNode(id) => id,
+            Object::Literal(literal) => literal.lexical_form(),
+        }
+    }
+
+    fn as_rio(&self) -> RioTerm<'_> {
+        match self {
+            Object::NamedNode(iri) => RioNamedNode { iri: iri.as_str() }.into(),
+            Object::BlankNode(id) => RioBlankNode { id: id.as_str() }.into(),
+            Object::Literal(literal) => literal.as_rio().into(),
+        }
+    }
+}
+
+/// A literal in the object position. Datatypes are borrowed from the `xsd` constants unless they
+/// are custom, and the lexical forms of booleans are borrowed as well, so that serializing a
+/// value allocates no more than its own lexical form.
+#[derive(Debug, Clone)]
+enum Literal {
     Simple {
-        /// The [lexical form](https://www.w3.org/TR/rdf11-concepts/#dfn-lexical-form).
-        value: String,
+        value: Cow<'static, str>,
     },
-    /// A [language-tagged string](https://www.w3.org/TR/rdf11-concepts/#dfn-language-tagged-string)
     LanguageTaggedString {
-        /// The [lexical form](https://www.w3.org/TR/rdf11-concepts/#dfn-lexical-form).
-        value: String,
-        /// The [language tag](https://www.w3.org/TR/rdf11-concepts/#dfn-language-tag).
-        language: String,
+        value: Cow<'static, str>,
+        language: Cow<'static, str>,
     },
-    /// A literal with an explicit datatype
     Typed {
-        /// The [lexical form](https://www.w3.org/TR/rdf11-concepts/#dfn-lexical-form).
-        value: String,
-        /// The [datatype IRI](https://www.w3.org/TR/rdf11-concepts/#dfn-datatype-iri).
-        datatype: RioNamedNode<'a>,
+        value: Cow<'static, str>,
+        datatype: Cow<'static, str>,
     },
 }
 
-impl Literal<'_> {
-    /// Return the lexical form of the literal.
-    pub fn value(&self) -> &str {
+impl Literal {
+    fn typed(value: impl Into<Cow<'static, str>>, datatype: &'static str) -> Self {
+        Literal::Typed {
+            value: value.into(),
+            datatype: Cow::Borrowed(datatype),
+        }
+    }
+
+    fn lexical_form(&self) -> &str {
+        match self {
+            Literal::Simple { value }
+            | Literal::LanguageTaggedString { value, .. }
+            | Literal::Typed { value, .. } => value,
+        }
+    }
+
+    /// The lexical form, moved out to retag it with a language or datatype.
+    fn into_lexical_form(self) -> Cow<'static, str> {
+        match self {
+            Literal::Simple { value }
+            | Literal::LanguageTaggedString { value, .. }
+            | Literal::Typed { value, .. } => value,
+        }
+    }
+
+    fn is_language_tagged(&self) -> bool {
+        matches!(self, Literal::LanguageTaggedString { .. })
+    }
+
+    fn as_rio(&self) -> RioLiteral<'_> {
         match self {
-            Literal::Simple { value } => value,
-            Literal::LanguageTaggedString { value, .. } => value,
-            Literal::Typed { value, .. } => value,
+            Literal::Simple { value } => RioLiteral::Simple { value },
+            Literal::LanguageTaggedString { value, language } => {
+                RioLiteral::LanguageTaggedString { value, language }
+            }
+            Literal::Typed { value, datatype } => RioLiteral::Typed {
+                value,
+                datatype: RioNamedNode { iri: datatype },
+            },
+        }
+    }
+}
+
+impl From<OwnedLiteral> for Literal {
+    fn from(literal: OwnedLiteral) -> Self {
+        match literal {
+            OwnedLiteral::Simple { value } => Literal::Simple {
+                value: value.into(),
+            },
+            OwnedLiteral::LanguageTaggedString { value, language } => {
+                Literal::LanguageTaggedString {
+                    value: value.into(),
+                    language: language.into(),
+                }
+            }
+            OwnedLiteral::Typed { value, datatype } => Literal::Typed {
+                value: value.into(),
+                datatype: datatype.into(),
+            },
         }
     }
 }
 
+/// A predicate / object pair belonging to the subject on top of the stack.
+#[derive(Debug)]
+struct Statement {
+    /// The index of the property in the subject config.
+    property: usize,
+    object: Object,
+    annotations: Vec<(String, String)>,
+}
+
+/// A subject that is currently being serialized. Its triples can only be written once the
+/// identifier field has been seen, which may happen after any number of other fields.
 #[derive(Debug)]
 struct Loc {
-    id: String,
-    type_name: String,
+    id: Option<String>,
+    /// The IRI given by the key of the enclosing map entry, if this subject is a map value.
+    iri: Option<String>,
+    /// The class of this instance, if it was taken from the configured type field.
+    rdf_type: Option<String>,
+    /// The name of the struct, or the one configured for a map standing in for it.
+    type_name: Cow<'static, str>,
+    /// The fields of the struct serialized so far.
+    fields: Vec<Cow<'static, str>>,
+    /// The number of objects of each field with a property mapping, by the index of the
+    /// property.
+    counts: Vec<(usize, usize)>,
+    statements: Vec<Statement>,
+    /// The objects collected by the enclosing field before this subject was entered.
+    outer: Vec<Object>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+/// An open map.
+#[derive(Debug)]
+enum Map {
+    /// The objects of each entry serialized so far.
+    Entries(Vec<(String, Vec<Object>)>),
+    /// A map standing in for a struct, e.g. a JSON object, whose entries are the fields of the
+    /// subject on top of the stack.
+    Subject,
+}
+
+/// Where the serializer hands the triples of each subject once it is complete.
+enum Sink<'w> {
+    /// Kept for [`Serializer::into_triples`], and to format them as a whole once the values
+    /// are serialized.
+    Triples(Vec<OwnedTriple>),
+    /// Written to the formatter right away, see [`to_writer`].
+    Turtle(TurtleFormatter<&'w mut dyn io::Write>),
+    /// Written to the writer right away with prefixed names, after the `@prefix` declarations
+    /// of a [`compact`](crate::SerializerConfig::compact) config.
+    Compact(Compact<'w>, &'w mut dyn io::Write),
+}
+
+impl Sink<'_> {
+    fn push(&mut self, triple: &Triple<'_>) -> Result<()> {
+        match self {
+            Sink::Triples(triples) => triples.push(OwnedTriple::from(triple)),
+            Sink::Turtle(formatter) => formatter.format(triple)?,
+            Sink::Compact(compact, writer) => {
+                compact.write_triple(&mut **writer, &OwnedTriple::from(triple))?
+            }
+        }
+        Ok(())
+    }
+
+    fn push_owned(&mut self, triple: OwnedTriple) -> Result<()> {
+        match self {
+            Sink::Triples(triples) => triples.push(triple),
+            Sink::Turtle(formatter) => triple.with_rio(&mut |triple| formatter.format(triple))?,
+            Sink::Compact(compact, writer) => compact.write_triple(&mut **writer, &triple)?,
+        }
+        Ok(())
+    }
+
+    /// Forget the triples kept so far, keeping their allocation.
+    fn clear(&mut self) {
+        if let Sink::Triples(triples) = self {
+            triples.clear();
+        }
+    }
+
+    /// The triples kept so far, none if they are written.
+    fn triples(&self) -> &[OwnedTriple] {
+        match self {
+            Sink::Triples(triples) => triples,
+            Sink::Turtle(_) | Sink::Compact(..) => &[],
+        }
+    }
 }
+
 /// Need a structure inside the serializer to hold the components of triples as they are
 /// gathered:
-/// - one IRI field holding the IRI of the subject
-/// - one field with Vec holding tuples with the predicate and literal.
+/// - one stack of subjects, each holding its IRI and the predicate / object pairs seen so far
+/// - one buffer with the objects produced by the value of the field currently being serialized
+///
+/// The triples of a subject are handed to the sink as soon as it ends, so only the subjects
+/// that are still open are buffered.
 ///
 /// The struct that we want to serialize, needs to be prepared:
 /// - those fields of a struct that contain a Vec of literals need to be flattened `serde(flatten)`
 /// - those fields of a struct that contain a Vec of structs should **not** be flattened
 ///  
-pub struct Serializer<'a, W: io::Write> {
+pub struct Serializer<'w> {
     stack: Vec<Loc>,
-    last_subject: &'a str,
-    last_key: &'a str,
-    last_literal: Option<Literal<'a>>,
-    output: String,
-    mapping: SerializerConfig,
-    formatter: TurtleFormatter<W>,
+    objects: Vec<Object>,
+    annotations: Vec<(String, String)>,
+    /// One frame per open map.
+    maps: Vec<Map>,
+    /// The struct that the maps serialized by the current field stand in for, see
+    /// [`PropertyConfig::nested_subject`](crate::PropertyConfig::nested_subject).
+    map_subject: Option<String>,
+    /// The key of the map entry whose value is being serialized.
+    map_key: Option<String>,
+    /// The key handed to the next subject, which uses it as its IRI.
+    keyed_subject: Option<String>,
+    blank_nodes: BlankNodes,
+    /// Scratch buffers the lexical forms of integers and floats are formatted in.
+    integers: itoa::Buffer,
+    floats: ryu::Buffer,
+    mapping: Arc<CompiledConfig<'w>>,
+    sink: Sink<'w>,
 }
 
-impl<'a, W> Serializer<'a, W>
-where
-    W: io::Write,
-{
-    fn new(mapping: SerializerConfig, writer: W) -> Serializer<'a, W> {
-        Serializer::with_formatter(mapping, TurtleFormatter::new(writer))
+impl<'w> Serializer<'w> {
+    /// A serializer with the given mapping, which values can be serialized into one after the
+    /// other, e.g. by [`serde_transcode`](https://docs.rs/serde-transcode), before taking the
+    /// output with [`into_string`](Serializer::into_string) or
+    /// [`into_triples`](Serializer::into_triples). To serialize many documents, e.g. one per
+    /// request of a service, one serializer can be reused with
+    /// [`take_string`](Serializer::take_string).
+    ///
+    /// # Errors
+    ///
+    /// Fails if the config includes an unknown property group, uses an unknown prefix or has a
+    /// property that is not an absolute IRI.
+    pub fn new(mapping: &'w SerializerConfig) -> Result<Serializer<'w>> {
+        let mapping = Arc::new(CompiledConfig::new(mapping)?);
+        Ok(Serializer::with_sink(mapping, Sink::Triples(Vec::new())))
     }
 
-    fn with_formatter(
-        mapping: SerializerConfig,
-        formatter: TurtleFormatter<W>,
-    ) -> Serializer<'a, W> {
+    fn with_sink(mapping: Arc<CompiledConfig<'w>>, sink: Sink<'w>) -> Serializer<'w> {
         Serializer {
             stack: Vec::new(),
-            last_subject: "",
-            last_key: "",
-            last_literal: None,
-            output: String::new(),
+            objects: Vec::new(),
+            annotations: Vec::new(),
+            maps: Vec::new(),
+            map_subject: None,
+            map_key: None,
+            keyed_subject: None,
+            blank_nodes: BlankNodes::default(),
+            integers: itoa::Buffer::new(),
+            floats: ryu::Buffer::new(),
             mapping,
-            formatter,
+            sink,
         }
     }
-}
 
-/// Serialize the given value as an RDF string.
-///
-/// # Errors
-///
-/// Serialization fails if the type cannot be represented as RDF.
-pub fn to_string<T>(value: &T, config: SerializerConfig) -> Result<String>
-where
-    T: ?Sized + Serialize,
-{
-    let mut serializer = Serializer::new(config, Vec::default());
-    value.serialize(&mut serializer)?;
-    let bytes = serializer.formatter.finish()?;
+    /// Serialize a field of the subject on top of the stack. The values of maps standing in
+    /// for a struct are `drained` even if the field has no property mapping, as a transcoder
+    /// only moves on to the next entry once the value has been serialized.
+    fn serialize_subject_field<T>(
+        &mut self,
+        key: Cow<'static, str>,
+        value: &T,
+        drained: bool,
+    ) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        #[cfg(feature = "tracing")]
+        let _entered = self.stack.last().map(|loc| loc.span.clone().entered());
+        #[cfg(feature = "tracing")]
+        tracing::trace!(key = &*key, "serialize_field");
 
-    // SAFETY: The `Formatter` never emits invalid UTF-8.
-    Ok(unsafe { String::from_utf8_unchecked(bytes) })
-}
+        // The mapping is resolved before the value is serialized, so that fields without one
+        // leave no trace, not even the subjects of nested structs.
+        let loc = self.stack.last_mut().ok_or_else(|| {
+            Error::new("serialize_struct -> serialize_field -> no subject on the stack")
+        })?;
+        loc.fields.push(key.clone());
+        let subject =
+            self.mapping
+                .subject(&loc.type_name)
+                .ok_or_else(|| Error::MissingSubjectConfig {
+                    struct_name: loc.type_name.to_string(),
+                })?;
+        let field = subject.field(&key);
 
-impl<'a, W> ser::Serializer for &mut Serializer<'a, W>
-where
-    W: io::Write,
-{
-    // The output type produced by this `Serializer` during successful
-    // serialization. Most serializers that produce text or binary output should
-    // set `Ok = ()` and serialize into an `io::Write` or buffer contained
-    // within the `Serializer` instance, as happens here. Serializers that build
-    // in-memory data structures may be simplified by using `Ok` to propagate
-    // the data structure around.
-    type Ok = ();
+        // Fields without a property mapping are not part of the RDF representation, unless the
+        // config denies them.
+        let Some(field) = field else {
+            if self.mapping.config.deny_unmapped_fields {
+                return Err(Error::MissingPropertyConfig {
+                    struct_name: loc.type_name.to_string(),
+                    field: key.to_string(),
+                });
+            }
+            if drained {
+                let mark = self.objects.len();
+                let outer = self.map_subject.take();
+                let serialized = value.serialize(&mut *self);
+                self.map_subject = outer;
+                self.objects.truncate(mark);
+                self.annotations.clear();
+                serialized?;
+            }
+            return Ok(());
+        };
 
-    // The error type when some error occurs during serialization.
-    type Error = Error;
+        // Maps in the value stand in for the struct configured for the field, if any.
+        let map_subject = match field {
+            Field::Property(index) => subject.properties[index].config.nested_subject.clone(),
+            Field::Identifier | Field::Type => None,
+        };
+        let outer = std::mem::replace(&mut self.map_subject, map_subject);
+        let serialized = value.serialize(&mut *self);
+        self.map_subject = outer;
+        serialized?;
 
-    // Associated types for keeping track of additional state while serializing
-    // compound data structures like sequences and maps. In this case no
-    // additional state is required beyond what is already stored in the
-    // Serializer struct.
-    type SerializeSeq = Self;
-    type SerializeTuple = Self;
-    type SerializeTupleStruct = Self;
-    type SerializeTupleVariant = Self;
-    type SerializeMap = Self;
-    type SerializeStruct = Self;
-    type SerializeStructVariant = Self;
+        let objects = std::mem::take(&mut self.objects);
+        let annotations = std::mem::take(&mut self.annotations);
 
-    // Here we go with the simple methods. The following 12 methods receive one
-    // of the primitive types of the data model and map it to JSON by appending
-    // into the output string.
-    fn serialize_bool(self, v: bool) -> Result<()> {
-        use crate::ser::Literal::Typed;
+        let loc = self.stack.last_mut().ok_or_else(|| {
+            Error::new("serialize_struct -> serialize_field -> no subject on the stack")
+        })?;
+        let subject =
+            self.mapping
+                .subject(&loc.type_name)
+                .ok_or_else(|| Error::MissingSubjectConfig {
+                    struct_name: loc.type_name.to_string(),
+                })?;
 
-        if v {
-            self.last_literal = Some(Typed {
-                value: "true".to_owned(),
-                datatype: RioNamedNode { iri: "xsd:boolean" },
-            });
-        } else {
-            self.last_literal = Some(Typed {
-                value: "false".to_owned(),
-                datatype: RioNamedNode { iri: "xsd:boolean" },
-            });
+        if field == Field::Identifier {
+            let id = objects.first().ok_or_else(|| {
+                Error::Message(format!(
+                    "serialize_struct -> serialize_field -> no value found for key: {}",
+                    key
+                ))
+            })?;
+            #[cfg(feature = "tracing")]
+            tracing::debug!(key = &*key, id = id.lexical_form(), "identifier_field");
+
+            loc.id = Some(id.lexical_form().to_owned());
+            return Ok(());
+        }
+
+        if field == Field::Type {
+            // An empty or absent value falls back to the configured `rdf_type`.
+            if let Some(value) = objects
+                .first()
+                .map(Object::lexical_form)
+                .filter(|v| !v.is_empty())
+            {
+                let rdf_type = self.mapping.config.expand_prefixed_name(value);
+                check_iri(&rdf_type, || format!("rdf:type in field {key}"))?;
+                loc.rdf_type = Some(rdf_type.into_owned());
+            }
+            return Ok(());
+        }
+
+        if let Field::Property(index) = field {
+            let property = subject.properties[index].config;
+            let datatype = subject.properties[index].datatype.as_ref();
+            loc.counts.push((index, objects.len()));
+            if property.inverse {
+                if let Some(object) = objects
+                    .iter()
+                    .find(|object| matches!(object, Object::Literal(_)))
+                {
+                    return Err(Error::Message(format!(
+                        "serialize_struct -> serialize_field -> {}.{} is an inverse property, \
+                         found a literal: {}",
+                        loc.type_name,
+                        key,
+                        object.lexical_form()
+                    )));
+                }
+            }
+            if let Some(kind) = property.kind {
+                let literal = kind == TermKind::Literal;
+                if let Some(object) = objects
+                    .iter()
+                    .find(|object| matches!(object, Object::Literal(_)) != literal)
+                {
+                    let (expected, found) = match kind {
+                        TermKind::Literal => ("a literal", "a subject"),
+                        TermKind::Subject => ("a subject", "a literal"),
+                    };
+                    return Err(Error::Message(format!(
+                        "serialize_struct -> serialize_field -> {}.{} is declared as {}, found \
+                         {}: {}",
+                        loc.type_name,
+                        key,
+                        expected,
+                        found,
+                        object.lexical_form()
+                    )));
+                }
+            }
+            for object in objects {
+                let object = match (object, &property.language, datatype) {
+                    (Object::Literal(literal), Some(language), _)
+                        if !literal.is_language_tagged() =>
+                    {
+                        Object::Literal(Literal::LanguageTaggedString {
+                            value: literal.into_lexical_form(),
+                            language: Cow::Owned(language.clone()),
+                        })
+                    }
+                    (Object::Literal(literal), None, Some(datatype))
+                        if !literal.is_language_tagged() =>
+                    {
+                        Object::Literal(Literal::Typed {
+                            value: literal.into_lexical_form(),
+                            datatype: datatype.clone(),
+                        })
+                    }
+                    (object, ..) => object,
+                };
+                loc.statements.push(Statement {
+                    property: index,
+                    object,
+                    annotations: annotations.clone(),
+                });
+            }
         }
         Ok(())
     }
 
-    // JSON does not distinguish between different sizes of integers, so all
-    // signed integers will be serialized the same and all unsigned integers
-    // will be serialized the same. Other formats, especially compact binary
-    // formats, may need independent logic for the different sizes.
-    fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
-        self.serialize_i64(i64::from(v))
+    /// The triples of the values serialized so far, as Turtle.
+    pub fn into_string(self) -> Result<String> {
+        self.format()
     }
 
-    fn serialize_i16(self, v: i16) -> Result<Self::Ok> {
-        self.serialize_i64(i64::from(v))
+    /// The triples of the values serialized so far as Turtle, after which the serializer
+    /// starts a new document as if it was [`reset`](Serializer::reset).
+    ///
+    /// Example:
+    /// ```
+    /// use serde::Serialize;
+    /// use serde_rdf::{Serializer, SerializerConfig, SubjectConfig};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Project {
+    ///     id: String,
+    /// }
+    ///
+    /// let config = SerializerConfig::builder()
+    ///     .subject(
+    ///         SubjectConfig::builder("Project")
+    ///             .rdf_type("https://ns.dasch.swiss/repository#Project")
+    ///             .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+    ///             .build(),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// let mut serializer = Serializer::new(&config).unwrap();
+    /// for id in ["081C", "0803"] {
+    ///     let project = Project { id: id.to_string() };
+    ///     project.serialize(&mut serializer).unwrap();
+    ///     let turtle = serializer.take_string().unwrap();
+    ///     assert!(turtle.starts_with(&format!("<https://ark.dasch.swiss/ark:/72163/1/{id}>")));
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the triples cannot be formatted, in which case they are dropped as well.
+    pub fn take_string(&mut self) -> Result<String> {
+        let output = self.format();
+        self.reset();
+        output
     }
 
-    fn serialize_i32(self, v: i32) -> Result<Self::Ok> {
-        self.serialize_i64(i64::from(v))
+    /// Format the triples serialized since the last chunk without `@prefix` declarations and
+    /// forget them. Blank nodes keep being numbered, so that the chunks form one document.
+    #[cfg(feature = "async")]
+    fn take_chunk(&mut self) -> Result<String> {
+        let config = &self.mapping.config;
+        let output = match config.compact {
+            true => Ok(Compact::new(self.sink.triples(), &config.namespaces)
+                .without_prefixes()
+                .to_string()),
+            false => self.format(),
+        };
+        self.sink.clear();
+        output
     }
 
-    // Not particularly efficient but this is example code anyway. A more
-    // performant approach would be to use the `itoa` crate.
-    fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
-        self.output += &v.to_string();
-        Ok(())
+    /// Start a new document: forget the triples and the state of the values serialized so far,
+    /// including those whose serialization failed, and number blank nodes from zero again. The
+    /// compiled config and the allocated buffers are kept.
+    pub fn reset(&mut self) {
+        self.discard_incomplete();
+        self.blank_nodes.reset();
+        self.sink.clear();
     }
 
-    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
-        self.serialize_u64(u64::from(v))
+    /// The triples of the values serialized so far.
+    pub fn into_triples(self) -> Vec<OwnedTriple> {
+        match self.sink {
+            Sink::Triples(triples) => triples,
+            Sink::Turtle(_) | Sink::Compact(..) => Vec::new(),
+        }
     }
 
-    fn serialize_u16(self, v: u16) -> Result<Self::Ok> {
-        self.serialize_u64(u64::from(v))
-    }
+    /// Format the triples as Turtle, or with prefixed names if the config is compact.
+    fn format(&self) -> Result<String> {
+        let config = &self.mapping.config;
+        if config.compact {
+            return Ok(Compact::new(self.sink.triples(), &config.namespaces).to_string());
+        }
 
-    fn serialize_u32(self, v: u32) -> Result<Self::Ok> {
-        self.serialize_u64(u64::from(v))
+        let mut formatter = TurtleFormatter::new(Vec::default());
+        for triple in self.sink.triples() {
+            triple.with_rio(&mut |triple| formatter.format(triple))?;
+        }
+        let bytes = formatter.finish()?;
+
+        // SAFETY: The `Formatter` never emits invalid UTF-8.
+        Ok(unsafe { String::from_utf8_unchecked(bytes) })
     }
 
-    fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
-        self.output += &v.to_string();
-        Ok(())
+    /// Format the triples in the given format. Turtle is written for TriG and N-Triples for
+    /// N-Quads, as the triples are in the default graph.
+    fn format_as(&self, format: Format) -> Result<String> {
+        let bytes = match format {
+            Format::Turtle | Format::TriG => return self.format(),
+            Format::NTriples | Format::NQuads => {
+                let mut formatter = NTriplesFormatter::new(Vec::default());
+                for triple in self.sink.triples() {
+                    triple.with_rio(&mut |triple| formatter.format(triple))?;
+                }
+                formatter.finish()?
+            }
+            #[cfg(feature = "xml")]
+            Format::RdfXml => {
+                let mut formatter = RdfXmlFormatter::new(Vec::default())?;
+                for triple in self.sink.triples() {
+                    triple.with_rio(&mut |triple| formatter.format(triple))?;
+                }
+                formatter.finish()?
+            }
+            #[allow(unreachable_patterns)]
+            format => {
+                return Err(Error::UnsupportedFormat {
+                    format,
+                    operation: "writing",
+                    feature: (format == Format::RdfXml).then_some("xml"),
+                })
+            }
+        };
+
+        // SAFETY: The formatters never emit invalid UTF-8.
+        Ok(unsafe { String::from_utf8_unchecked(bytes) })
     }
 
-    fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
-        self.serialize_f64(f64::from(v))
+    /// Forget the subjects, objects and map entries left over by a value, which are only
+    /// incomplete if its serialization failed.
+    fn discard_incomplete(&mut self) {
+        self.stack.clear();
+        self.objects.clear();
+        self.annotations.clear();
+        self.maps.clear();
+        self.map_subject = None;
+        self.map_key = None;
+        self.keyed_subject = None;
     }
 
-    fn serialize_f64(self, v: f64) -> Result<Self::Ok> {
-        self.output += &v.to_string();
+    /// Push a subject for the struct `name` onto the stack, whose fields follow.
+    fn begin_subject(&mut self, name: Cow<'static, str>, len: usize) -> Result<()> {
+        if self.mapping.subject(&name).is_none() {
+            return Err(Error::MissingSubjectConfig {
+                struct_name: name.into_owned(),
+            });
+        }
+        let outer = std::mem::take(&mut self.objects);
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!("subject", struct_name = &*name);
+        self.stack.push(Loc {
+            id: None,
+            iri: self.keyed_subject.take(),
+            rdf_type: None,
+            type_name: name,
+            fields: Vec::with_capacity(len),
+            counts: Vec::new(),
+            statements: Vec::new(),
+            outer,
+            #[cfg(feature = "tracing")]
+            span,
+        });
         Ok(())
     }
 
-    // Serialize a char as a single-character string. Other formats may
-    // represent this differently.
-    fn serialize_char(self, v: char) -> Result<Self::Ok> {
-        self.serialize_str(&v.to_string())
+    /// Add the subject on top of the stack and the given field to an error. The subject is
+    /// named by its IRI once the identifier is known, and by its struct otherwise.
+    fn field_context(&self, err: Error, key: &str) -> Error {
+        let Some(loc) = self.stack.last() else {
+            return err;
+        };
+        let subject = || match (&loc.iri, &loc.id) {
+            (Some(iri), _) => format!("<{iri}>"),
+            (None, Some(id)) => {
+                let prefix = self
+                    .mapping
+                    .subject(&loc.type_name)
+                    .map_or("", |subject| &subject.identifier_prefix);
+                format!("<{prefix}{id}>")
+            }
+            (None, None) => loc.type_name.to_string(),
+        };
+        err.context(
+            "serializing",
+            subject,
+            Some(format!("{}.{key}", loc.type_name)),
+        )
     }
 
-    // This only works for strings that don't require escape sequences but you
-    // get the idea. For example it would emit invalid JSON if the input string
-    // contains a '"' character.
-    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
-        println!("serialize_str");
+    /// Write all triples of the subject on top of the stack and hand its IRI to the enclosing
+    /// field as an object.
+    fn end_subject(&mut self) -> Result<()> {
+        let loc = self
+            .stack
+            .pop()
+            .ok_or_else(|| Error::new("serialize_struct -> end -> no subject on the stack"))?;
+        #[cfg(feature = "tracing")]
+        let _entered = loc.span.clone().entered();
+        // The config is shared rather than borrowed from `self`, which writes the triples.
+        let mapping = Arc::clone(&self.mapping);
+        let config = &mapping.config;
+        let subject =
+            mapping
+                .subject(&loc.type_name)
+                .ok_or_else(|| Error::MissingSubjectConfig {
+                    struct_name: loc.type_name.to_string(),
+                })?;
+        let type_predicate = mapping.type_predicate();
 
-        use crate::ser::Literal::Typed;
+        // Map values are identified by their key, other subjects without an identifier field are
+        // written as blank nodes.
+        let node = match loc.iri {
+            Some(iri) => {
+                check_iri(&iri, || format!("map key for subject {}", loc.type_name))?;
+                if let (true, Some(id)) = (config.strict, &loc.id) {
+                    let own = format!("{}{}", subject.identifier_prefix, id);
+                    if own != iri {
+                        return Err(Error::Message(format!(
+                            "serialize_struct -> end -> map key {} conflicts with identifier {}",
+                            iri, own
+                        )));
+                    }
+                }
+                Node::NamedNode(iri)
+            }
+            None if subject.config.identifier_field.is_empty() => self
+                .blank_nodes
+                .node(subject.config.blank_node_prefix.as_deref(), &mapping),
+            None => {
+                // The identifier is set whenever the field is serialized, so a struct without it
+                // is most likely configured with a misspelled field.
+                let id = loc.id.ok_or_else(|| Error::IdentifierFieldMissing {
+                    struct_name: loc.type_name.to_string(),
+                    identifier_field: subject.config.identifier_field.clone(),
+                    available_fields: loc.fields.iter().map(|f| f.to_string()).collect(),
+                })?;
+                let iri = format!("{}{}", subject.identifier_prefix, id);
+                check_iri(&iri, || format!("identifier for subject {}", loc.type_name))?;
+                Node::NamedNode(iri)
+            }
+        };
+        let subject_node = node.as_rio();
+        // Fields that were skipped, e.g. with `skip_serializing_if`, have no objects.
+        for (index, property) in subject.properties.iter().enumerate() {
+            let found = loc
+                .counts
+                .iter()
+                .filter(|(property, _)| *property == index)
+                .map(|(_, count)| count)
+                .sum();
+            property.config.check_count(
+                || subject_node.to_string(),
+                &property.rdf_property,
+                found,
+            )?;
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(subject = node.label(), "end_subject");
 
-        self.last_literal = Some(Typed {
-            value: v.to_owned(),
-            datatype: RioNamedNode { iri: "xsd:string" },
-        });
+        self.sink.push(&Triple {
+            subject: subject_node,
+            predicate: RioNamedNode {
+                iri: type_predicate,
+            },
+            object: RioNamedNode {
+                iri: loc.rdf_type.as_deref().unwrap_or(&subject.rdf_type),
+            }
+            .into(),
+        })?;
 
-        Ok(())
-    }
+        let object_node = Object::from(node.clone());
+        for statement in &loc.statements {
+            let property = &subject.properties[statement.property];
+            // The objects of inverse properties are never literals, see
+            // `serialize_subject_field`.
+            let (subject, object) = match (property.config.inverse, &statement.object) {
+                (true, Object::NamedNode(iri)) => (
+                    RioNamedNode { iri: iri.as_str() }.into(),
+                    object_node.as_rio(),
+                ),
+                (true, Object::BlankNode(id)) => (
+                    RioBlankNode { id: id.as_str() }.into(),
+                    object_node.as_rio(),
+                ),
+                _ => (subject_node, statement.object.as_rio()),
+            };
+            let triple = Triple {
+                subject,
+                predicate: RioNamedNode {
+                    iri: &property.rdf_property,
+                },
+                object,
+            };
+            self.sink.push(&triple)?;
 
-    // Serialize a byte array as an array of bytes. Could also use a base64
-    // string here. Binary formats will typically represent byte arrays more
-    // compactly.
-    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        use serde::ser::SerializeSeq;
-        let mut seq = self.serialize_seq(Some(v.len()))?;
-        for byte in v {
-            seq.serialize_element(byte)?;
-        }
-        seq.end()
-    }
+            for (predicate, value) in &statement.annotations {
+                self.sink.push(&Triple {
+                    subject: RioSubject::Triple(&triple),
+                    predicate: RioNamedNode {
+                        iri: predicate.as_str(),
+                    },
+                    object: RioLiteral::Typed {
+                        value: value.as_str(),
+                        datatype: RioNamedNode { iri: XSD_STRING },
+                    }
+                    .into(),
+                })?;
+            }
 
-    // An absent optional is represented as the JSON `null`.
-    fn serialize_none(self) -> Result<Self::Ok> {
-        self.serialize_unit()
-    }
+            if property.config.reify {
+                self.reify(&triple)?;
+            }
+        }
 
-    // A present optional is represented as just the contained value. Note that
-    // this is a lossy representation. For example the values `Some(())` and
-    // `None` both serialize as just `null`. Unfortunately this is typically
-    // what people expect when working with JSON. Other formats are encouraged
-    // to behave more intelligently if possible.
-    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
-    where
-        T: ?Sized + Serialize,
-    {
-        value.serialize(self)
-    }
+        if let Some(hook) = &config.on_subject {
+            for (predicate, object) in hook.call(node.label(), &loc.type_name) {
+                let predicate = config.expand_prefixed_name(&predicate);
+                check_iri(&predicate, || {
+                    format!("extra predicate for subject {}", loc.type_name)
+                })?;
+                self.sink.push_owned(OwnedTriple {
+                    subject: subject_node.into(),
+                    predicate: predicate.into_owned(),
+                    object: OwnedObject::Literal(object),
+                })?;
+            }
+        }
 
-    // In Serde, unit means an anonymous value containing no data. Map this to
-    // JSON as `null`.
-    fn serialize_unit(self) -> Result<Self::Ok> {
-        self.output += "null";
+        self.objects = loc.outer;
+        self.objects.push(node.into());
         Ok(())
     }
 
-    // Unit struct means a named value containing no data. Again, since there is
-    // no data, map this to JSON as `null`. There is no need to serialize the
-    // name in most formats.
-    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok> {
-        self.serialize_unit()
-    }
+    /// Describe the given triple with an `rdf:Statement` and link it from the triple's subject.
+    fn reify(&mut self, triple: &Triple<'_>) -> Result<()> {
+        let link = self
+            .mapping
+            .reification_predicate()
+            .map(str::to_owned)
+            .ok_or_else(|| {
+                Error::Message(format!(
+                    "serialize_struct -> end -> no reification predicate configured for: {}",
+                    triple.predicate.iri
+                ))
+            })?;
+        let node = self.blank_nodes.node(None, &self.mapping);
+        let statement = Object::from(node.clone());
 
-    // When serializing a unit variant (or any other kind of variant), formats
-    // can choose whether to keep track of it by index or by name. Binary
-    // formats typically use the index of the variant and human-readable formats
-    // typically use the name.
-    fn serialize_unit_variant(
-        self,
-        name: &'static str,
-        variant_index: u32,
-        variant: &'static str,
-    ) -> Result<Self::Ok, Self::Error> {
-        self.serialize_str(variant)
+        for (predicate, object) in [
+            (RDF_TYPE, RioNamedNode { iri: RDF_STATEMENT }.into()),
+            (RDF_SUBJECT, triple.subject.into()),
+            (RDF_PREDICATE, triple.predicate.into()),
+            (RDF_OBJECT, triple.object),
+        ] {
+            self.sink.push(&Triple {
+                subject: node.as_rio(),
+                predicate: RioNamedNode { iri: predicate },
+                object,
+            })?;
+        }
+        self.sink.push(&Triple {
+            subject: triple.subject,
+            predicate: RioNamedNode { iri: link.as_str() },
+            object: statement.as_rio(),
+        })?;
+        Ok(())
     }
 
-    // As is done here, serializers are encouraged to treat newtype structs as
-    // insignificant wrappers around the data they contain.
-    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok>
+    /// A [`value::Term`], [`value::Literal`] or [`value::Node`], which is handed over in
+    /// N-Triples syntax. A term or literal becomes an object of the enclosing field as it is, a
+    /// node writes its statements and becomes the object as well.
+    fn serialize_value_model<T>(&mut self, name: &'static str, ntriples: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(self)
+        let mark = self.objects.len();
+        ntriples.serialize(&mut *self)?;
+        let objects = self.objects.split_off(mark);
+        let ntriples = objects
+            .first()
+            .map(Object::lexical_form)
+            .unwrap_or_default();
+        let term = match name {
+            value::NODE => {
+                let node: value::Node = ntriples.parse()?;
+                for triple in node.triples()? {
+                    self.sink.push_owned(triple)?;
+                }
+                node.id
+            }
+            value::LITERAL => value::Term::from(ntriples.parse::<value::Literal>()?),
+            _ => ntriples.parse()?,
+        };
+        self.objects.push(match term {
+            value::Term::Iri(iri) => Object::NamedNode(iri),
+            value::Term::BlankNode(id) => Object::BlankNode(id),
+            value::Term::Literal {
+                value,
+                datatype,
+                language,
+            } => Object::Literal(Literal::from(value::literal(
+                &value,
+                datatype.as_deref(),
+                language.as_deref(),
+            ))),
+        });
+        Ok(())
     }
 
-    // Note that newtype variant (and all of the other variant serialization
-    // methods) refer exclusively to the "externally tagged" enum
-    // representation.
-    //
-    // Serialize this to JSON in externally tagged form as `{ NAME: VALUE }`.
-    fn serialize_newtype_variant<T>(
-        self,
-        name: &'static str,
-        variant_index: u32,
-        variant: &'static str,
-        value: &T,
-    ) -> Result<Self::Ok>
+    /// The fields of an [`Annotated`](crate::Annotated) value. The `value` field is serialized
+    /// as if the wrapper was not there, while the `annotations` field is collected for the
+    /// triples the enclosing field is going to produce.
+    #[cfg(feature = "rdf-star")]
+    fn serialize_annotated_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
-        self.output += "{";
-        variant.serialize(&mut *self)?;
-        self.output += ":";
-        value.serialize(&mut *self)?;
-        self.output += "}";
-        Ok(())
-    }
-
-    // Now we get to the serialization of compound types.
-    //
-    // The start of the sequence, each value, and the end are three separate
-    // method calls. This one is responsible only for serializing the start,
-    // which in JSON is `[`.
-    //
-    // The length of the sequence may or may not be known ahead of time. This
-    // doesn't make a difference in JSON because the length is not represented
-    // explicitly in the serialized form. Some serializers may only be able to
-    // support sequences for which the length is known up front.
-    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
-        self.output += "[";
-        Ok(self)
-    }
+        match key {
+            crate::annotated::VALUE => value.serialize(&mut *self),
+            crate::annotated::ANNOTATIONS => {
+                let mark = self.objects.len();
+                value.serialize(&mut *self)?;
 
-    // Tuples look just like sequences in JSON. Some formats may be able to
-    // represent tuples more efficiently by omitting the length, since tuple
-    // means that the corresponding `Deserialize implementation will know the
-    // length without needing to look at the serialized data.
-    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        self.serialize_seq(Some(len))
+                let pairs = self.objects.split_off(mark);
+                for pair in pairs.chunks(2) {
+                    if let [predicate, object] = pair {
+                        self.annotations.push((
+                            predicate.lexical_form().to_owned(),
+                            object.lexical_form().to_owned(),
+                        ));
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(Error::Message(format!(
+                "serialize_struct -> serialize_field -> unexpected annotated field: {}",
+                key
+            ))),
+        }
     }
+}
 
-    // Tuple structs look just like sequences in JSON.
-    fn serialize_tuple_struct(
-        self,
-        name: &'static str,
+/// Serialize the given value as a list of RDF triples.
+///
+/// # Errors
+///
+/// Serialization fails if the type cannot be represented as RDF.
+pub fn to_triples<T>(value: &T, config: &SerializerConfig) -> Result<Vec<OwnedTriple>>
+where
+    T: ?Sized + Serialize,
+{
+    let mut serializer = Serializer::new(config)?;
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_triples())
+}
+
+/// Serialize the given value as an RDF string.
+///
+/// # Errors
+///
+/// Serialization fails if the type cannot be represented as RDF.
+pub fn to_string<T>(value: &T, config: &SerializerConfig) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    // The triples are formatted as they are completed rather than kept as owned triples.
+    let mut output = Vec::new();
+    to_writer(&mut output, value, config)?;
+    // SAFETY: The `Formatter` never emits invalid UTF-8.
+    Ok(unsafe { String::from_utf8_unchecked(output) })
+}
+
+/// Serialize the given value as Turtle into a writer. The triples of each subject are written
+/// as soon as the subject is complete, so that the document is not held in memory. The
+/// `@prefix` declarations of a [`compact`](crate::SerializerConfig::compact) config are written
+/// first.
+///
+/// # Errors
+///
+/// Serialization fails if the type cannot be represented as RDF or if writing fails, after
+/// the triples of the subjects completed before may have been written.
+pub fn to_writer<W, T>(mut writer: W, value: &T, config: &SerializerConfig) -> Result<()>
+where
+    W: io::Write,
+    T: ?Sized + Serialize,
+{
+    let mapping = Arc::new(CompiledConfig::new(config)?);
+    let writer = &mut writer as &mut dyn io::Write;
+    let sink = match config.compact {
+        true => {
+            let compact = Compact::new(&[], &config.namespaces);
+            write!(writer, "{compact}")?;
+            Sink::Compact(compact, writer)
+        }
+        false => Sink::Turtle(TurtleFormatter::new(writer)),
+    };
+    let mut serializer = Serializer::with_sink(mapping, sink);
+    value.serialize(&mut serializer)?;
+    match serializer.sink {
+        Sink::Turtle(formatter) => formatter.finish()?.flush()?,
+        Sink::Compact(_, writer) => writer.flush()?,
+        Sink::Triples(_) => {}
+    }
+    Ok(())
+}
+
+/// Serialize the given value as an RDF string in the given format. The triples are written to
+/// the default graph of TriG and N-Quads, and the
+/// [`compact`](crate::SerializerConfig::compact) option only applies to Turtle and TriG.
+///
+/// # Errors
+///
+/// Serialization fails with an [`Error::UnsupportedFormat`] for RDF/XML without the `xml`
+/// feature and for JSON-LD, which is only read, and with an error if the type cannot be
+/// represented as RDF.
+pub fn to_string_with_format<T>(
+    value: &T,
+    config: &SerializerConfig,
+    format: Format,
+) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let mut serializer = Serializer::new(config)?;
+    value.serialize(&mut serializer)?;
+    serializer.format_as(format)
+}
+
+/// Serialize a JSON document that has the structure of the struct `root_subject` as Turtle,
+/// without a Rust type for it. JSON objects stand in for structs: the document for the
+/// `root_subject` and the objects of the fields with a
+/// [`nested_subject`](crate::PropertyConfig::nested_subject) for that struct. Other objects are
+/// language maps, and arrays hold the objects of a field.
+///
+/// Example:
+/// ```
+/// use serde_rdf::{json_to_rdf, SerializerConfig, SubjectConfig};
+/// let config = SerializerConfig::builder()
+///     .subject(
+///         SubjectConfig::builder("Project")
+///             .rdf_type("https://ns.dasch.swiss/repository#Project")
+///             .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+///             .property("name", "https://ns.dasch.swiss/repository#hasName")
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// let turtle = json_to_rdf(r#"{"id": "081C", "name": "Events"}"#, "Project", &config).unwrap();
+/// assert!(turtle.contains("<https://ark.dasch.swiss/ark:/72163/1/081C>"));
+/// ```
+///
+/// # Errors
+///
+/// Fails if the input is not JSON, or if the document cannot be represented as RDF.
+#[cfg(feature = "json")]
+pub fn json_to_rdf(json: &str, root_subject: &str, config: &SerializerConfig) -> Result<String> {
+    let mut mapping = CompiledConfig::new(config)?;
+    // Reported here, as errors of the serializer only reach the caller as text through the
+    // transcoder.
+    if mapping.subject(root_subject).is_none() {
+        return Err(Error::MissingSubjectConfig {
+            struct_name: root_subject.to_string(),
+        });
+    }
+    mapping.root_subject = Some(root_subject);
+    let mut serializer = Serializer::with_sink(Arc::new(mapping), Sink::Triples(Vec::new()));
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    serde_transcode::transcode(&mut deserializer, &mut serializer)?;
+    deserializer.end().map_err(Error::new)?;
+    serializer.into_string()
+}
+
+/// Serialize each of the given values into one RDF string, leaving out those that fail. Their
+/// errors are returned with their index instead of aborting at the first one, e.g. to report
+/// all invalid records of a batch export.
+///
+/// # Errors
+///
+/// Only formatting the document as a whole fails with an error.
+pub fn to_string_collecting<T>(
+    values: &[T],
+    config: &SerializerConfig,
+) -> Result<(String, Vec<(usize, Error)>)>
+where
+    T: Serialize,
+{
+    let mut serializer = Serializer::new(config)?;
+    let mut errors = Vec::new();
+    for (index, value) in values.iter().enumerate() {
+        let mark = serializer.sink.triples().len();
+        let result = value.serialize(&mut serializer);
+        // The objects left over are the subjects of the values, which are not linked.
+        serializer.discard_incomplete();
+        if let Err(err) = result {
+            if let Sink::Triples(triples) = &mut serializer.sink {
+                triples.truncate(mark);
+            }
+            errors.push((index, err));
+        }
+    }
+    Ok((serializer.format()?, errors))
+}
+
+/// The number of values serialized by one task of [`to_string_par`]. It does not depend on the
+/// size of the thread pool, so that the blank node labels of a deterministic config do not
+/// either.
+#[cfg(feature = "rayon")]
+const PAR_CHUNK_LEN: usize = 256;
+
+/// Serialize the given values into one Turtle string on the rayon thread pool. The values are
+/// split into chunks, which are serialized into buffers of their own and concatenated in order
+/// after the `@prefix` declarations of a [`compact`](crate::SerializerConfig::compact) config.
+/// The blank nodes of each chunk are labelled in a namespace of their own, e.g. `_:t3b0`, so
+/// that the labels of different chunks do not collide.
+///
+/// The output is isomorphic to the one of [`to_string`] for the slice, but not equal to it if
+/// blank nodes are generated.
+///
+/// # Errors
+///
+/// Serialization fails with the error of the first value in the slice that fails. The chunks
+/// are all serialized before it is known which one that is.
+#[cfg(feature = "rayon")]
+pub fn to_string_par<T>(values: &[T], config: &SerializerConfig) -> Result<String>
+where
+    T: Serialize + Sync,
+{
+    use rayon::prelude::*;
+
+    let mapping = Arc::new(CompiledConfig::new(config)?);
+    let chunks = values
+        .par_chunks(PAR_CHUNK_LEN)
+        .enumerate()
+        .map(|(index, chunk)| serialize_chunk(chunk, Arc::clone(&mapping), index))
+        .collect::<Vec<Result<String>>>()
+        .into_iter()
+        .collect::<Result<Vec<String>>>()?;
+
+    let config = &mapping.config;
+    let mut output = match config.compact {
+        true => Compact::new(&[], &config.namespaces).to_string(),
+        false => String::new(),
+    };
+    output.reserve(chunks.iter().map(String::len).sum());
+    for chunk in chunks {
+        output.push_str(&chunk);
+    }
+    Ok(output)
+}
+
+/// The Turtle of one chunk of [`to_string_par`], without `@prefix` declarations.
+#[cfg(feature = "rayon")]
+fn serialize_chunk<T>(
+    values: &[T],
+    mapping: Arc<CompiledConfig<'_>>,
+    index: usize,
+) -> Result<String>
+where
+    T: Serialize,
+{
+    let namespace = format!("t{index}");
+    if mapping.config.compact {
+        let mut serializer = Serializer::with_sink(mapping, Sink::Triples(Vec::new()));
+        serializer.blank_nodes.namespace = namespace;
+        for value in values {
+            value.serialize(&mut serializer)?;
+        }
+        let namespaces = &serializer.mapping.config.namespaces;
+        let compact = Compact::new(serializer.sink.triples(), namespaces).without_prefixes();
+        return Ok(compact.to_string());
+    }
+
+    let mut output = Vec::new();
+    {
+        let sink = Sink::Turtle(TurtleFormatter::new(&mut output as &mut dyn io::Write));
+        let mut serializer = Serializer::with_sink(mapping, sink);
+        serializer.blank_nodes.namespace = namespace;
+        for value in values {
+            value.serialize(&mut serializer)?;
+        }
+        if let Sink::Turtle(formatter) = serializer.sink {
+            formatter.finish()?;
+        }
+    }
+    // SAFETY: The `Formatter` never emits invalid UTF-8.
+    Ok(unsafe { String::from_utf8_unchecked(output) })
+}
+
+/// Serialize the given value as RDF into an asynchronous writer.
+///
+/// Serde drives serialization synchronously, so the value is serialized first and its Turtle is
+/// then written and awaited. Use [`to_async_writer_all`] to stream many values without holding
+/// all of them in memory.
+///
+/// # Errors
+///
+/// Serialization fails if the value cannot be represented as RDF or if writing fails.
+#[cfg(feature = "async")]
+pub async fn to_async_writer<W, T>(writer: W, value: &T, config: &SerializerConfig) -> Result<()>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+    T: ?Sized + Serialize,
+{
+    to_async_writer_all(writer, std::iter::once(value), config).await
+}
+
+/// Serialize the given values as one RDF document into an asynchronous writer.
+///
+/// Serde drives serialization synchronously, so the values are serialized one at a time: the
+/// Turtle of each value, with the subjects nested in it, is written and awaited before the
+/// next value is serialized, and only one value is held in memory at a time. The `@prefix`
+/// declarations of a [`compact`](crate::SerializerConfig::compact) config are written first.
+///
+/// # Errors
+///
+/// Serialization fails if a value cannot be represented as RDF or if writing fails, in which
+/// case the values before it have been written already.
+#[cfg(feature = "async")]
+pub async fn to_async_writer_all<W, I>(
+    mut writer: W,
+    values: I,
+    config: &SerializerConfig,
+) -> Result<()>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    use tokio::io::AsyncWriteExt;
+
+    let mapping = Arc::new(CompiledConfig::new(config)?);
+    if mapping.config.compact {
+        let prefixes = Compact::new(&[], &mapping.config.namespaces).to_string();
+        writer.write_all(prefixes.as_bytes()).await?;
+    }
+    let mut serializer = Serializer::with_sink(mapping, Sink::Triples(Vec::new()));
+    for value in values {
+        value.serialize(&mut serializer)?;
+        let chunk = serializer.take_chunk()?;
+        writer.write_all(chunk.as_bytes()).await?;
+    }
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Whether the map key can be the language of a literal, i.e. it matches the `LANGTAG` of
+/// Turtle: `[a-zA-Z]+('-'[a-zA-Z0-9]+)*`, e.g. `de-CH`.
+fn is_language_tag(key: &str) -> bool {
+    let mut subtags = key.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    !primary.is_empty()
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags
+            .all(|subtag| !subtag.is_empty() && subtag.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// The shortest lexical form of an `xsd:double` that reads back as the same float. ryu writes
+/// exponents as `1e16` or `1e-7`, which are in the lexical space, but spells the infinities
+/// differently from XSD.
+fn double_lexical<F: ryu::Float>(buffer: &mut ryu::Buffer, v: F) -> Cow<'static, str> {
+    match buffer.format(v) {
+        "inf" => Cow::Borrowed("INF"),
+        "-inf" => Cow::Borrowed("-INF"),
+        "NaN" => Cow::Borrowed("NaN"),
+        lexical => Cow::Owned(lexical.to_owned()),
+    }
+}
+
+impl<'a, 'w> ser::Serializer for &'a mut Serializer<'w> {
+    // Values produce objects for the field being serialized and subjects hand their triples
+    // to the sink, so nothing is passed back up.
+    type Ok = ();
+
+    // The error type when some error occurs during serialization.
+    type Error = Error;
+
+    // The open subjects and maps of compound values are tracked on the stacks of the
+    // serializer, so no state is needed beyond it.
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Compound<'a, 'w>;
+    type SerializeStructVariant = Compound<'a, 'w>;
+
+    // The primitive types of the data model become literals with the XSD datatype of the type,
+    // which are objects of the field being serialized.
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        let value = if v { "true" } else { "false" };
+        self.objects
+            .push(Object::Literal(Literal::typed(value, XSD_BOOLEAN)));
+        Ok(())
+    }
+
+    // Integers of all sizes are written as `xsd:integer`, which is unbounded.
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
+        self.serialize_i128(i128::from(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok> {
+        let value = self.integers.format(v).to_owned();
+        self.objects
+            .push(Object::Literal(Literal::typed(value, XSD_INTEGER)));
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
+        self.serialize_u128(u128::from(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok> {
+        let value = self.integers.format(v).to_owned();
+        self.objects
+            .push(Object::Literal(Literal::typed(value, XSD_INTEGER)));
+        Ok(())
+    }
+
+    // An `f32` is written in its shortest form, e.g. `0.1` rather than the digits of the `f64`
+    // it widens to.
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
+        let value = double_lexical(&mut self.floats, v);
+        self.objects
+            .push(Object::Literal(Literal::typed(value, XSD_DOUBLE)));
+        Ok(())
+    }
+
+    // Floats are written as `xsd:double`.
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok> {
+        let value = double_lexical(&mut self.floats, v);
+        self.objects
+            .push(Object::Literal(Literal::typed(value, XSD_DOUBLE)));
+        Ok(())
+    }
+
+    // A char is a single-character `xsd:string`.
+    fn serialize_char(self, v: char) -> Result<Self::Ok> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    // Serde lends the string for the duration of the call only, while the literal is held
+    // until its subject is complete, so it is copied.
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(value = v, "serialize_str");
+
+        self.objects
+            .push(Object::Literal(Literal::typed(v.to_owned(), XSD_STRING)));
+
+        Ok(())
+    }
+
+    // Bytes are written like a `Vec<u8>`, as one `xsd:integer` object per byte.
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = self.serialize_seq(Some(v.len()))?;
+        for byte in v {
+            seq.serialize_element(byte)?;
+        }
+        seq.end()
+    }
+
+    // An absent optional contributes no object, so the field produces no triple.
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Ok(())
+    }
+
+    // A present optional is represented as just the contained value. Nested
+    // optionals collapse, and `Some` of an empty sequence produces no triple
+    // just like `None`.
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    // Unit has no representation, like `None`, e.g. a JSON `null` that is transcoded.
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        Ok(())
+    }
+
+    // Unit structs are markers for well-known individuals and are represented
+    // by the IRI configured for their name.
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok> {
+        let iri = self.mapping.individual(name).ok_or_else(|| {
+            Error::Message(format!(
+                "serialize_unit_struct -> no individual configured for unit struct: {}",
+                name
+            ))
+        })?;
+        check_iri(iri, || format!("individual of unit struct {name}"))?;
+        self.objects.push(Object::NamedNode(iri.to_owned()));
+        Ok(())
+    }
+
+    // A unit variant is written as its name, an `xsd:string`.
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    // Newtype structs are transparent, except for the types of the `value` module, which
+    // carry a term of their own.
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        match name {
+            value::TERM | value::LITERAL | value::NODE => self.serialize_value_model(name, value),
+            _ => value.serialize(self),
+        }
+    }
+
+    // A newtype variant is represented by its value, as the deserializer picks the variant by
+    // the class of the subject the value refers to.
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    // A sequence has no representation of its own in RDF. Every element is
+    // collected as a separate object of the field currently being serialized,
+    // which results in the predicate being repeated once per element.
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(self)
+    }
+
+    // Tuples look just like sequences.
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    // Tuple structs look just like sequences.
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
         self.serialize_seq(Some(len))
     }
 
-    // Tuple variants are represented in JSON as `{ NAME: [DATA...] }`. Again
-    // this method is only responsible for the externally tagged representation.
+    // Tuple variants have no representation in RDF, as their fields have no names to map to
+    // properties.
     fn serialize_tuple_variant(
         self,
-        name: &'static str,
-        variant_index: u32,
-        variant: &'static str,
-        len: usize,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        self.output += "{";
-        variant.serialize(&mut *self)?;
-        self.output += ":[";
-        Ok(self)
+        Err(Error::CannotSerializePrimitive(
+            "serialize_tuple_variant -> tuple variants cannot be represented as RDF",
+        ))
     }
 
-    // Maps are represented in JSON as `{ K: V, K: V, ... }`.
+    // The entries of a map become objects of the enclosing field, see `SerializeMap`, unless
+    // the map stands in for a struct: at the top level with a `root_subject`, or as the value
+    // of a field with a `nested_subject`.
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
-        self.output += "{";
+        let subject = match self.stack.is_empty() {
+            true => self.mapping.root_subject.map(str::to_owned),
+            false => self.map_subject.clone(),
+        };
+        match subject {
+            Some(name) => {
+                self.begin_subject(Cow::Owned(name), len.unwrap_or_default())?;
+                self.maps.push(Map::Subject);
+            }
+            None => self.maps.push(Map::Entries(Vec::new())),
+        }
         Ok(self)
     }
 
     // Structs represent subjects, where the name is the "type".
     fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
-        println!("serialize_struct");
-        println!("name: {}", name);
-        self.last_subject = name;
-        Ok(self)
+        #[cfg(feature = "rdf-star")]
+        if name == crate::annotated::NAME {
+            return Ok(Compound::Annotated(self));
+        }
+
+        self.begin_subject(Cow::Borrowed(name), len)?;
+        Ok(Compound::Subject(self))
     }
 
-    // Struct variants are represented in JSON as `{ NAME: { K: V, ... } }`.
-    // This is the externally tagged representation.
+    // Struct variants are subjects of the struct named after the variant, which is read back
+    // into the variant by its class.
     fn serialize_struct_variant(
         self,
-        name: &'static str,
-        variant_index: u32,
+        _name: &'static str,
+        _variant_index: u32,
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeStructVariant> {
-        self.output += "{";
-        variant.serialize(&mut *self)?;
-        self.output += ":{";
-        Ok(self)
+        self.serialize_struct(variant, len)
     }
 }
 
@@ -442,7 +1613,7 @@ where
 //
 // This impl is SerializeSeq so these methods are called after `serialize_seq`
 // is called on the Serializer.
-impl<'a, W: io::Write> ser::SerializeSeq for &mut Serializer<'a, W> {
+impl ser::SerializeSeq for &mut Serializer<'_> {
     // Must match the `Ok` type of the serializer.
     type Ok = ();
     // Must match the `Error` type of the serializer.
@@ -453,21 +1624,17 @@ impl<'a, W: io::Write> ser::SerializeSeq for &mut Serializer<'a, W> {
     where
         T: ?Sized + Serialize,
     {
-        if !self.output.ends_with('[') {
-            self.output += ",";
-        }
         value.serialize(&mut **self)
     }
 
     // Close the sequence.
     fn end(self) -> Result<Self::Ok> {
-        self.output += "]";
         Ok(())
     }
 }
 
 // Same thing but for tuples.
-impl<'a, W: io::Write> ser::SerializeTuple for &mut Serializer<'a, W> {
+impl ser::SerializeTuple for &mut Serializer<'_> {
     type Ok = ();
     type Error = Error;
 
@@ -475,20 +1642,16 @@ impl<'a, W: io::Write> ser::SerializeTuple for &mut Serializer<'a, W> {
     where
         T: ?Sized + Serialize,
     {
-        if !self.output.ends_with('[') {
-            self.output += ",";
-        }
         value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<()> {
-        self.output += "]";
         Ok(())
     }
 }
 
 // Same thing but for tuple structs.
-impl<'a, W: io::Write> ser::SerializeTupleStruct for &mut Serializer<'a, W> {
+impl ser::SerializeTupleStruct for &mut Serializer<'_> {
     type Ok = ();
     type Error = Error;
 
@@ -496,28 +1659,16 @@ impl<'a, W: io::Write> ser::SerializeTupleStruct for &mut Serializer<'a, W> {
     where
         T: ?Sized + Serialize,
     {
-        if !self.output.ends_with('[') {
-            self.output += ",";
-        }
         value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<()> {
-        self.output += "]";
         Ok(())
     }
 }
 
-// Tuple variants are a little different. Refer back to the
-// `serialize_tuple_variant` method above:
-//
-//    self.output += "{";
-//    variant.serialize(&mut *self)?;
-//    self.output += ":[";
-//
-// So the `end` method in this impl is responsible for closing both the `]` and
-// the `}`.
-impl<'a, W: io::Write> ser::SerializeTupleVariant for &mut Serializer<'a, W> {
+// Never started, see `serialize_tuple_variant`.
+impl ser::SerializeTupleVariant for &mut Serializer<'_> {
     type Ok = ();
     type Error = Error;
 
@@ -525,68 +1676,108 @@ impl<'a, W: io::Write> ser::SerializeTupleVariant for &mut Serializer<'a, W> {
     where
         T: ?Sized + Serialize,
     {
-        if !self.output.ends_with('[') {
-            self.output += ",";
-        }
         value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<()> {
-        self.output += "]}";
         Ok(())
     }
 }
 
-// Some `Serialize` types are not able to hold a key and value in memory at the
-// same time so `SerializeMap` implementations are required to support
-// `serialize_key` and `serialize_value` individually.
-//
-// There is a third optional method on the `SerializeMap` trait. The
-// `serialize_entry` method allows serializers to optimize for the case where
-// key and value are both available simultaneously. In JSON it doesn't make a
-// difference so the default behavior for `serialize_entry` is fine.
-impl<'a, W: io::Write> ser::SerializeMap for &mut Serializer<'a, W> {
+// Map keys identify their values: a struct value becomes a subject named by the key, any other
+// value becomes a literal tagged with the key as its language. The keys of a map standing in
+// for a struct are its field names instead.
+impl ser::SerializeMap for &mut Serializer<'_> {
     type Ok = ();
     type Error = Error;
 
-    // The Serde data model allows map keys to be any serializable type. JSON
-    // only allows string keys so the implementation below will produce invalid
-    // JSON if the key serializes as something other than a string.
-    //
-    // A real JSON serializer would need to validate that map keys are strings.
-    // This can be done by using a different Serializer to serialize the key
-    // (instead of `&mut **self`) and having that other serializer only
-    // implement `serialize_str` and return an error on any other data type.
     fn serialize_key<T>(&mut self, key: &T) -> Result<Self::Ok>
     where
         T: ?Sized + Serialize,
     {
-        if !self.output.ends_with('{') {
-            self.output += ",";
-        }
-        key.serialize(&mut **self)
+        let mark = self.objects.len();
+        key.serialize(&mut **self)?;
+        let key = self.objects.split_off(mark);
+        let key = key.first().ok_or_else(|| {
+            Error::new("serialize_map -> serialize_key -> key has no lexical form")
+        })?;
+        self.map_key = Some(key.lexical_form().to_owned());
+        Ok(())
     }
 
-    // It doesn't make a difference whether the colon is printed at the end of
-    // `serialize_key` or at the beginning of `serialize_value`. In this case
-    // the code is a bit simpler having it here.
     fn serialize_value<T>(&mut self, value: &T) -> Result<Self::Ok>
     where
         T: ?Sized + Serialize,
     {
-        self.output += ":";
-        value.serialize(&mut **self)
+        let key = self
+            .map_key
+            .take()
+            .ok_or_else(|| Error::new("serialize_map -> serialize_value -> no key for value"))?;
+        if let Some(Map::Subject) = self.maps.last() {
+            return self
+                .serialize_subject_field(Cow::Owned(key.clone()), value, true)
+                .map_err(|err| self.field_context(err, &key));
+        }
+
+        let mark = self.objects.len();
+        self.keyed_subject = Some(key.clone());
+        value.serialize(&mut **self)?;
+        // A value that is not a subject leaves its key unused.
+        self.keyed_subject = None;
+
+        let objects = self
+            .objects
+            .split_off(mark)
+            .into_iter()
+            .map(|object| match object {
+                Object::Literal(_) if !is_language_tag(&key) => {
+                    Err(Error::InvalidLanguageTag(key.clone()))
+                }
+                Object::Literal(literal) => Ok(Object::Literal(Literal::LanguageTaggedString {
+                    value: literal.into_lexical_form(),
+                    language: Cow::Owned(key.to_lowercase()),
+                })),
+                object => Ok(object),
+            })
+            .collect::<Result<_>>()?;
+        match self.maps.last_mut() {
+            Some(Map::Entries(entries)) => entries.push((key, objects)),
+            _ => {
+                return Err(Error::new(
+                    "serialize_map -> serialize_value -> no map on the stack",
+                ))
+            }
+        }
+        Ok(())
     }
 
     fn end(self) -> Result<()> {
-        self.output += "}";
+        let mut entries = match self.maps.pop() {
+            Some(Map::Entries(entries)) => entries,
+            Some(Map::Subject) => return self.end_subject(),
+            None => return Err(Error::new("serialize_map -> end -> no map on the stack")),
+        };
+        if self.mapping.config.sort_map_entries {
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        }
+        for (_, objects) in entries {
+            self.objects.extend(objects);
+        }
         Ok(())
     }
 }
 
 // Structs are like maps in which the keys are constrained to be compile-time
-// constant strings.
-impl<'a, W: io::Write> ser::SerializeStruct for &mut Serializer<'a, W> {
+// constant strings. Most structs are subjects, but a few wrapper types provided
+// by this crate are recognized by their name and handled differently.
+#[doc(hidden)]
+pub enum Compound<'a, 'w> {
+    Subject(&'a mut Serializer<'w>),
+    #[cfg(feature = "rdf-star")]
+    Annotated(&'a mut Serializer<'w>),
+}
+
+impl ser::SerializeStruct for Compound<'_, '_> {
     type Ok = ();
     type Error = Error;
 
@@ -594,54 +1785,26 @@ impl<'a, W: io::Write> ser::SerializeStruct for &mut Serializer<'a, W> {
     where
         T: ?Sized + Serialize,
     {
-        println!("serialize_struct -> serialize_field");
-
-        value.serialize(&mut **self)?;
-
-        let subject = self.mapping.subjects.get(self.last_subject).unwrap();
-
-        if subject.identifier_field == key {
-            println!(
-                "serialize_struct -> serialize_field -> identifier_field: {}",
-                key
-            );
+        match self {
+            Compound::Subject(ser) => ser
+                .serialize_subject_field(Cow::Borrowed(key), value, false)
+                .map_err(|err| ser.field_context(err, key)),
+            #[cfg(feature = "rdf-star")]
+            Compound::Annotated(ser) => ser.serialize_annotated_field(key, value),
         }
-
-        let value = match self.last_literal.take() {
-            Some(v) => v,
-            None => {
-                return Err(Error::Message(format!(
-                    "serialize_struct -> serialize_field -> no value found for key: {}",
-                    key
-                )))
-            }
-        };
-
-        self.formatter.format(&Triple {
-            subject: RioNamedNode {
-                iri: format!("{}{}", &subject.identifier_prefix, value.value()).as_str(),
-            }
-            .into(),
-            predicate: RioNamedNode {
-                iri: "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
-            },
-            object: RioNamedNode {
-                iri: subject.rdf_type.as_str(),
-            }
-            .into(),
-        })?;
-        Ok(())
     }
 
     fn end(self) -> Result<()> {
-        println!("serialize_struct -> end");
-        Ok(())
+        match self {
+            Compound::Subject(ser) => ser.end_subject(),
+            #[cfg(feature = "rdf-star")]
+            Compound::Annotated(_) => Ok(()),
+        }
     }
 }
 
-// Similar to `SerializeTupleVariant`, here the `end` method is responsible for
-// closing both of the curly braces opened by `serialize_struct_variant`.
-impl<'a, W: io::Write> ser::SerializeStructVariant for &mut Serializer<'a, W> {
+// Struct variants are serialized like structs.
+impl ser::SerializeStructVariant for Compound<'_, '_> {
     type Ok = ();
     type Error = Error;
 
@@ -649,17 +1812,11 @@ impl<'a, W: io::Write> ser::SerializeStructVariant for &mut Serializer<'a, W> {
     where
         T: ?Sized + Serialize,
     {
-        if !self.output.ends_with('{') {
-            self.output += ",";
-        }
-        key.serialize(&mut **self)?;
-        self.output += ":";
-        value.serialize(&mut **self)
+        ser::SerializeStruct::serialize_field(self, key, value)
     }
 
     fn end(self) -> Result<()> {
-        self.output += "}}";
-        Ok(())
+        ser::SerializeStruct::end(self)
     }
 }
 
@@ -671,7 +1828,11 @@ mod tests {
 
     use serde::Serialize;
 
-    use crate::{to_string, SerializerConfig, SubjectConfig};
+    use crate::{
+        ns, to_string, to_string_collecting, to_triples, Error, OwnedLiteral, OwnedObject,
+        OwnedSubject, OwnedTriple, PropertyConfig, SerializerConfig, SubjectConfig, SubjectHook,
+        TermKind, XsdType,
+    };
 
     #[test]
     fn test_simple_struct() {
@@ -691,14 +1852,3033 @@ mod tests {
                     identifier_field: "id".to_string(),
                     identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
                     properties: Vec::new(),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let test = Test {
+            id: "my-id".to_string(),
+        };
+        let expected = "<https://ark.dasch.swiss/ark:/72163/1/my-id> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/ns#Test> .\n";
+        assert_eq!(to_string(&test, &config).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_to_triples() {
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+        }
+
+        let config = SerializerConfig {
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "https://example.org/ns#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let test = Test {
+            id: "my-id".to_string(),
+        };
+        let expected = vec![OwnedTriple {
+            subject: OwnedSubject::NamedNode(
+                "https://ark.dasch.swiss/ark:/72163/1/my-id".to_string(),
+            ),
+            predicate: "http://www.w3.org/1999/02/22-rdf-syntax-ns#type".to_string(),
+            object: OwnedObject::NamedNode("https://example.org/ns#Test".to_string()),
+        }];
+        assert_eq!(to_triples(&test, &config).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_shared_config() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            name: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            shortcode: Option<String>,
+        }
+
+        let mut config = SerializerConfig {
+            namespaces: HashMap::from([(
+                "dsp".to_string(),
+                "https://ns.dasch.swiss/repository#".to_string(),
+            )]),
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    rdf_type: "dsp:Project".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    include_properties: vec!["named".to_string()],
+                    ..Default::default()
+                },
+            )]),
+            property_groups: HashMap::from([(
+                "named".to_string(),
+                vec![PropertyConfig {
+                    struct_field: "name".to_string(),
+                    rdf_property: "dsp:hasName".to_string(),
+                    ..Default::default()
+                }],
+            )]),
+            ..Default::default()
+        };
+        let projects: Vec<Project> = ["081C", "0803", "0804"]
+            .iter()
+            .map(|id| Project {
+                id: id.to_string(),
+                name: format!("Project {id}"),
+                shortcode: None,
+            })
+            .collect();
+
+        // Serializing with the config leaves it as it is, so that it serves every record.
+        let triples: Vec<OwnedTriple> = projects
+            .iter()
+            .flat_map(|project| to_triples(project, &config).unwrap())
+            .collect();
+        assert_eq!(triples, to_triples(&projects, &config).unwrap());
+        assert_eq!(triples.len(), 6);
+        assert!(triples
+            .iter()
+            .any(|t| t.predicate == "https://ns.dasch.swiss/repository#hasName"));
+        assert_eq!(config.subjects["Project"].rdf_type, "dsp:Project");
+        assert_eq!(config.subjects["Project"].include_properties, ["named"]);
+
+        // The properties are checked up front, also those of fields that are not serialized.
+        let project = config.subjects.get_mut("Project").unwrap();
+        project.properties.push(PropertyConfig {
+            struct_field: "shortcode".to_string(),
+            rdf_property: "has shortcode".to_string(),
+            ..Default::default()
+        });
+        let error = to_string(&projects[0], &config).unwrap_err();
+        assert!(
+            matches!(&error, Error::InvalidIri { iri, .. } if iri == "has shortcode"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_type_predicate() {
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+            name: String,
+        }
+
+        let config = |type_predicate: &str| SerializerConfig {
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "https://example.org/ns#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "https://example.org/ns#hasName".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            type_predicate: Some(type_predicate.to_string()),
+            ..Default::default()
+        };
+
+        let test = Test {
+            id: "my-id".to_string(),
+            name: "My Name".to_string(),
+        };
+        let output = to_string(&test, &config("http://purl.org/dc/terms/type")).unwrap();
+
+        assert!(output.starts_with("<https://ark.dasch.swiss/ark:/72163/1/my-id> <http://purl.org/dc/terms/type> <https://example.org/ns#Test>"), "{output}");
+        assert!(output.contains("<https://example.org/ns#hasName> \"My Name\"^^<http://www.w3.org/2001/XMLSchema#string>"), "{output}");
+        assert!(
+            !output.contains("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+            "{output}"
+        );
+
+        assert!(to_string(&test, &config("type")).is_err());
+    }
+
+    #[test]
+    fn test_blank_node_prefix() {
+        #[derive(Serialize)]
+        struct Person {
+            id: String,
+            address: Address,
+            location: Geo,
+            // Has no property mapping.
+            origin: Geo,
+        }
+
+        #[derive(Serialize)]
+        struct Address {
+            street: String,
+        }
+
+        #[derive(Serialize)]
+        struct Geo {
+            lat: String,
+        }
+
+        let subject = |name: &str, fields: &[&str], prefix: Option<&str>| {
+            let (identifier_field, identifier_prefix) = match prefix {
+                Some(_) => ("", ""),
+                None => ("id", "https://ark.dasch.swiss/ark:/72163/1/"),
+            };
+            (
+                name.to_string(),
+                SubjectConfig {
+                    struct_name: name.to_string(),
+                    rdf_type: format!("https://example.org/ns#{name}"),
+                    identifier_field: identifier_field.to_string(),
+                    identifier_prefix: identifier_prefix.to_string(),
+                    properties: fields
+                        .iter()
+                        .map(|field| PropertyConfig {
+                            struct_field: field.to_string(),
+                            rdf_property: format!("https://example.org/ns#{field}"),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    blank_node_prefix: prefix.map(str::to_string),
+                    ..Default::default()
+                },
+            )
+        };
+        let config = SerializerConfig {
+            subjects: HashMap::from([
+                subject("Address", &["street"], Some("addr")),
+                subject("Geo", &["lat"], Some("geo")),
+                subject("Person", &["address", "location"], None),
+            ]),
+            ..Default::default()
+        };
+
+        let person = Person {
+            id: "my-id".to_string(),
+            address: Address {
+                street: "Main Street".to_string(),
+            },
+            location: Geo {
+                lat: "46.9".to_string(),
+            },
+            origin: Geo {
+                lat: "47.4".to_string(),
+            },
+        };
+        let output = to_string(&person, &config).unwrap();
+
+        assert!(output.contains("_:addr0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/ns#Address>"), "{output}");
+        assert!(output.contains("_:geo0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/ns#Geo>"), "{output}");
+        assert!(
+            output.contains("<https://example.org/ns#address> _:addr0"),
+            "{output}"
+        );
+        assert!(
+            output.contains("<https://example.org/ns#location> _:geo0"),
+            "{output}"
+        );
+        // The unmapped field leaves no subject behind.
+        assert!(!output.contains("_:geo1"), "{output}");
+        assert!(!output.contains("47.4"), "{output}");
+    }
+
+    #[test]
+    fn test_reified_property() {
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+            name: String,
+        }
+
+        let config = SerializerConfig {
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "https://example.org/ns#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "https://example.org/ns#hasName".to_string(),
+                        reify: true,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
                 },
             )]),
+            reification_predicate: Some("https://example.org/ns#hasStatement".to_string()),
+            deterministic: true,
+            ..Default::default()
         };
 
         let test = Test {
             id: "my-id".to_string(),
+            name: "My Name".to_string(),
+        };
+        let output = to_string(&test, &config).unwrap();
+
+        let expected = r#"
+            @prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+            @prefix ex: <https://example.org/ns#> .
+
+            <https://ark.dasch.swiss/ark:/72163/1/my-id> a ex:Test ;
+                ex:hasName "My Name"^^xsd:string ;
+                ex:hasStatement _:statement .
+            _:statement a rdf:Statement ;
+                rdf:subject <https://ark.dasch.swiss/ark:/72163/1/my-id> ;
+                rdf:predicate ex:hasName ;
+                rdf:object "My Name"^^xsd:string .
+        "#;
+        crate::assert_graph_eq!(
+            crate::Graph::parse_turtle(&output).unwrap(),
+            crate::Graph::parse_turtle(expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_type_field() {
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+            resource_type: String,
+        }
+
+        let config = || SerializerConfig {
+            namespaces: HashMap::from([("ex".to_string(), "https://example.org/ns#".to_string())]),
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "https://example.org/ns#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "resource_type".to_string(),
+                        rdf_property: "https://example.org/ns#hasResourceType".to_string(),
+                        ..Default::default()
+                    }],
+                    type_field: Some("resource_type".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let typed = Test {
+            id: "my-id".to_string(),
+            resource_type: "ex:Book".to_string(),
+        };
+        let expected = "<https://ark.dasch.swiss/ark:/72163/1/my-id> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/ns#Book> .\n";
+        assert_eq!(to_string(&typed, &config()).unwrap(), expected);
+
+        let untyped = Test {
+            id: "my-id".to_string(),
+            resource_type: "".to_string(),
         };
         let expected = "<https://ark.dasch.swiss/ark:/72163/1/my-id> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/ns#Test> .\n";
-        assert_eq!(to_string(&test, config).unwrap(), expected);
+        assert_eq!(to_string(&untyped, &config()).unwrap(), expected);
+
+        let invalid = Test {
+            id: "my-id".to_string(),
+            resource_type: "not an iri".to_string(),
+        };
+        assert!(to_string(&invalid, &config()).is_err());
+    }
+
+    #[test]
+    fn test_keyed_subjects() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            datasets: HashMap<String, Dataset>,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+            title: String,
+        }
+
+        let config = |strict| SerializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    SubjectConfig {
+                        struct_name: "Project".to_string(),
+                        rdf_type: "https://example.org/ns#Project".to_string(),
+                        identifier_field: "id".to_string(),
+                        identifier_prefix: "https://example.org/".to_string(),
+                        properties: vec![PropertyConfig {
+                            struct_field: "datasets".to_string(),
+                            rdf_property: "https://example.org/ns#hasDataset".to_string(),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "Dataset".to_string(),
+                    SubjectConfig {
+                        struct_name: "Dataset".to_string(),
+                        rdf_type: "https://example.org/ns#Dataset".to_string(),
+                        identifier_field: "id".to_string(),
+                        identifier_prefix: "https://example.org/".to_string(),
+                        properties: vec![PropertyConfig {
+                            struct_field: "title".to_string(),
+                            rdf_property: "https://example.org/ns#hasTitle".to_string(),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            strict,
+            ..Default::default()
+        };
+
+        let dataset = |id: &str| Dataset {
+            id: id.to_string(),
+            title: format!("Dataset {id}"),
+        };
+        let project = Project {
+            id: "p".to_string(),
+            datasets: HashMap::from([
+                ("https://example.org/d1".to_string(), dataset("d1")),
+                ("https://example.org/d2".to_string(), dataset("d2")),
+            ]),
+        };
+        let output = to_string(&project, &config(true)).unwrap();
+        assert!(
+            output.contains("<https://example.org/ns#hasDataset>"),
+            "{output}"
+        );
+        for id in ["d1", "d2"] {
+            assert!(output.contains(&format!("<https://example.org/{id}> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/ns#Dataset>")), "{output}");
+            assert!(output.contains(&format!("<https://example.org/ns#hasTitle> \"Dataset {id}\"^^<http://www.w3.org/2001/XMLSchema#string>")), "{output}");
+            // Once as the subject of its own triples and once as the object of the link.
+            assert_eq!(
+                output
+                    .matches(&format!("<https://example.org/{id}>"))
+                    .count(),
+                2,
+                "{output}"
+            );
+        }
+
+        let conflicting = Project {
+            id: "p".to_string(),
+            datasets: HashMap::from([("https://example.org/d1".to_string(), dataset("d2"))]),
+        };
+        assert!(to_string(&conflicting, &config(true)).is_err());
+        let output = to_string(&conflicting, &config(false)).unwrap();
+        assert!(
+            output.contains("<https://example.org/ns#hasDataset> <https://example.org/d1>"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_unmapped_fields() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            name: String,
+            shortcode: String,
+        }
+
+        let config = |deny_unmapped_fields| SerializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    rdf_type: "https://example.org/ns#Project".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "https://example.org/ns#hasName".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            deny_unmapped_fields,
+            ..Default::default()
+        };
+        let project = Project {
+            id: "p".to_string(),
+            name: "Events".to_string(),
+            shortcode: "081C".to_string(),
+        };
+
+        let output = to_string(&project, &config(false)).unwrap();
+        assert!(!output.contains("081C"), "{output}");
+
+        let error = to_string(&project, &config(true)).unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::MissingPropertyConfig { struct_name, field }
+                    if struct_name == "Project" && field == "shortcode"
+            ),
+            "{error}"
+        );
+        assert_eq!(
+            error.inner().to_string(),
+            "no RDF property configured for Project.shortcode"
+        );
+
+        let errors = config(false).validate_fields("Project", &["id", "name", "shortcode"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "no RDF property configured for Project.shortcode"
+        );
+        let errors = config(false).validate_fields("Dataset", &["id", "title"]);
+        let fields: Vec<_> = errors
+            .iter()
+            .filter_map(|error| match error {
+                Error::MissingPropertyConfig { struct_name, field } if struct_name == "Dataset" => {
+                    Some(field.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fields, ["id", "title"]);
+    }
+
+    #[test]
+    fn test_identifier_field_missing() {
+        #[derive(Serialize)]
+        struct Project {
+            shortcode: String,
+            name: String,
+        }
+
+        let config = SerializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    rdf_type: "https://example.org/ns#Project".to_string(),
+                    identifier_field: "shortCode".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "https://example.org/ns#hasName".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let project = Project {
+            shortcode: "081C".to_string(),
+            name: "Events".to_string(),
+        };
+
+        let error = to_string(&project, &config).unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::IdentifierFieldMissing { struct_name, identifier_field, available_fields }
+                    if struct_name == "Project"
+                        && identifier_field == "shortCode"
+                        && available_fields == &["shortcode", "name"]
+            ),
+            "{error}"
+        );
+        assert!(error.is_config_error());
+        assert_eq!(
+            error.inner().to_string(),
+            "identifier field shortCode not found in struct Project, serialized fields: \
+             shortcode, name"
+        );
+    }
+
+    #[test]
+    fn test_config_builder() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            name: String,
+        }
+
+        let project = Project {
+            id: "081C".to_string(),
+            name: "Events".to_string(),
+        };
+        let config = SerializerConfig::builder()
+            .prefix("dsp", "https://ns.dasch.swiss/repository#")
+            .subject(
+                SubjectConfig::builder("Project")
+                    .rdf_type("https://ns.dasch.swiss/repository#Project")
+                    .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                    .property("name", "https://ns.dasch.swiss/repository#hasName")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.namespaces["dsp"],
+            "https://ns.dasch.swiss/repository#"
+        );
+        let literal = SerializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    rdf_type: "https://ns.dasch.swiss/repository#Project".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "https://ns.dasch.swiss/repository#hasName".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_string(&project, &config).unwrap(),
+            to_string(&project, &literal).unwrap()
+        );
+
+        let error = SerializerConfig::builder()
+            .subject(
+                SubjectConfig::builder("Project")
+                    .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                    .build(),
+            )
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "validate -> no rdf_type configured for subject: Project"
+        );
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+            kind: String,
+            address: Address,
+        }
+
+        #[derive(Serialize)]
+        struct Address {
+            street: String,
+        }
+
+        let config = SerializerConfig::builder()
+            .prefix("dsp", "https://ns.dasch.swiss/repository#")
+            .subject(
+                SubjectConfig::builder("Dataset")
+                    .rdf_type("dsp:Dataset")
+                    .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                    .type_field("kind")
+                    .property("address", "dsp:hasAddress")
+                    .build(),
+            )
+            .subject(
+                SubjectConfig::builder("Address")
+                    .rdf_type("dsp:Address")
+                    .blank_node_prefix("addr")
+                    .property("street", "dsp:hasStreet")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.subjects["Dataset"].type_field.as_deref(),
+            Some("kind")
+        );
+        assert_eq!(
+            config.subjects["Address"].blank_node_prefix.as_deref(),
+            Some("addr")
+        );
+        let dataset = Dataset {
+            id: "081C/d1".to_string(),
+            kind: "dsp:Collection".to_string(),
+            address: Address {
+                street: "Main Street".to_string(),
+            },
+        };
+        let output = to_string(&dataset, &config).unwrap();
+        assert!(output.contains("<https://ark.dasch.swiss/ark:/72163/1/081C/d1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://ns.dasch.swiss/repository#Collection>"), "{output}");
+        assert!(
+            output.contains("<https://ns.dasch.swiss/repository#hasAddress> _:addr0"),
+            "{output}"
+        );
+        assert!(!output.contains("repository#Dataset"), "{output}");
+
+        let error = SerializerConfig::builder()
+            .subject(
+                SubjectConfig::builder("Project")
+                    .rdf_type("https://ns.dasch.swiss/repository#Project")
+                    .property("name", "has name")
+                    .build(),
+            )
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(&error, Error::InvalidIri { iri, .. } if iri == "has name"),
+            "{error}"
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_config_from_yaml() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            name: String,
+            size: String,
+        }
+
+        let project = Project {
+            id: "081C".to_string(),
+            name: "Events".to_string(),
+            size: "42".to_string(),
+        };
+        let input = r#"
+            subjects:
+              Project:
+                rdf_type: https://ns.dasch.swiss/repository#Project
+                identifier_field: id
+                identifier_prefix: https://ark.dasch.swiss/ark:/72163/1/
+                properties:
+                  - struct_field: name
+                    rdf_property: https://ns.dasch.swiss/repository#hasName
+                  - struct_field: size
+                    rdf_property: https://ns.dasch.swiss/repository#hasSize
+                    datatype: http://www.w3.org/2001/XMLSchema#integer
+            deterministic: true
+        "#;
+        let config = SerializerConfig::from_yaml_str(input).unwrap();
+        assert_eq!(config.subjects["Project"].struct_name, "Project");
+        assert!(config.deterministic);
+
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        let reloaded = SerializerConfig::from_reader(yaml.as_bytes()).unwrap();
+        let output = to_string(&project, &config).unwrap();
+        assert!(
+            output.contains(r#""42"^^<http://www.w3.org/2001/XMLSchema#integer>"#),
+            "{output}"
+        );
+        assert_eq!(to_string(&project, &reloaded).unwrap(), output);
+
+        let error =
+            SerializerConfig::from_yaml_str("subjects: { Project: { identifier_field: id } }")
+                .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "validate -> no rdf_type configured for subject: Project"
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_config_from_toml() {
+        let input = r#"
+            [subjects.Project]
+            rdf_type = "https://ns.dasch.swiss/repository#Project"
+            identifier_field = "id"
+            identifier_prefix = "https://ark.dasch.swiss/ark:/72163/1/"
+
+            [[subjects.Project.properties]]
+            struct_field = "name"
+            rdf_property = "https://ns.dasch.swiss/repository#hasName"
+        "#;
+        let config = SerializerConfig::from_toml_str(input).unwrap();
+        let subject = &config.subjects["Project"];
+        assert_eq!(subject.properties[0].struct_field, "name");
+
+        let error = SerializerConfig::from_toml_str(
+            "[subjects.Project]\nrdf_type = \"https://example.org/ns#Project\"\n\
+             [[subjects.Project.properties]]\nstruct_field = \"name\"\nrdf_property = \"has name\"",
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&error, Error::InvalidIri { iri, .. } if iri == "has name"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_prefixed_names() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            name: String,
+            shortcode: String,
+        }
+
+        let config = |rdf_type: &str| SerializerConfig {
+            namespaces: HashMap::from([
+                (
+                    "dsp".to_string(),
+                    "https://ns.dasch.swiss/repository#".to_string(),
+                ),
+                (
+                    "ark".to_string(),
+                    "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                ),
+            ]),
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    rdf_type: rdf_type.to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "ark:".to_string(),
+                    properties: vec![
+                        PropertyConfig {
+                            struct_field: "name".to_string(),
+                            rdf_property: "dsp:hasName".to_string(),
+                            ..Default::default()
+                        },
+                        PropertyConfig {
+                            struct_field: "shortcode".to_string(),
+                            rdf_property: "https://ns.dasch.swiss/repository#hasShortcode"
+                                .to_string(),
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let project = Project {
+            id: "081C".to_string(),
+            name: "Events".to_string(),
+            shortcode: "081C".to_string(),
+        };
+
+        let triples = to_triples(&project, &config("dsp:Project")).unwrap();
+        let subject = OwnedSubject::NamedNode("https://ark.dasch.swiss/ark:/72163/1/081C".into());
+        let predicates: Vec<_> = triples
+            .iter()
+            .inspect(|triple| assert_eq!(triple.subject, subject))
+            .map(|triple| triple.predicate.as_str())
+            .collect();
+        assert_eq!(
+            predicates,
+            [
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
+                "https://ns.dasch.swiss/repository#hasName",
+                "https://ns.dasch.swiss/repository#hasShortcode",
+            ]
+        );
+        assert_eq!(
+            triples[0].object,
+            OwnedObject::NamedNode("https://ns.dasch.swiss/repository#Project".into())
+        );
+
+        let mut expanded = config("urn:example:Project");
+        expanded.expand_prefixed_names().unwrap();
+        assert_eq!(expanded.subjects["Project"].rdf_type, "urn:example:Project");
+
+        // An unknown prefix that is a valid scheme makes an absolute IRI.
+        let mut expanded = config("dcterms:Project");
+        expanded.expand_prefixed_names().unwrap();
+        assert_eq!(expanded.subjects["Project"].rdf_type, "dcterms:Project");
+
+        let error = to_string(&project, &config("dc_terms:Project")).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::UnknownPrefix { prefix, known, .. }
+                    if prefix == "dc_terms" && known == &["ark", "dsp"]
+            ),
+            "{error}"
+        );
+        assert_eq!(
+            error.to_string(),
+            "unknown prefix dc_terms in dc_terms:Project, known prefixes: ark, dsp"
+        );
+    }
+
+    #[test]
+    fn test_well_known_prefixes() {
+        #[derive(Serialize)]
+        struct Invoice {
+            id: String,
+            amount: String,
+        }
+
+        let mut config = SerializerConfig::with_well_known_prefixes();
+        config.subjects.insert(
+            "Invoice".to_string(),
+            SubjectConfig::builder("Invoice")
+                .rdf_type("https://example.org/ns#Invoice")
+                .identifier("id", "https://example.org/")
+                .property("amount", "https://example.org/ns#amount")
+                .build(),
+        );
+        config.subjects.get_mut("Invoice").unwrap().properties[0].datatype =
+            Some(XsdType::Custom("xsd:gYear".to_string()));
+        let invoice = Invoice {
+            id: "i1".to_string(),
+            amount: "2024".to_string(),
+        };
+        let triples = to_triples(&invoice, &config).unwrap();
+        assert_eq!(
+            triples[1].object,
+            OwnedObject::Literal(OwnedLiteral::Typed {
+                value: "2024".to_string(),
+                datatype: format!("{}gYear", ns::XSD),
+            })
+        );
+
+        // Declared prefixes take precedence over the well-known ones.
+        let config = SerializerConfig::builder()
+            .prefix("dcterms", "https://example.org/terms/")
+            .well_known_prefixes()
+            .build()
+            .unwrap();
+        assert_eq!(config.namespaces["dcterms"], "https://example.org/terms/");
+        assert_eq!(config.namespaces["rdfs"], ns::RDFS);
+        let mut config = SerializerConfig::with_well_known_prefixes();
+        config
+            .namespaces
+            .insert("prov".to_string(), "https://example.org/prov#".to_string());
+        assert_eq!(config.namespaces["prov"], "https://example.org/prov#");
+    }
+
+    #[test]
+    fn test_xsd_type() {
+        for (name, datatype) in [
+            ("string", XsdType::String),
+            ("boolean", XsdType::Boolean),
+            ("integer", XsdType::Integer),
+            ("decimal", XsdType::Decimal),
+            ("double", XsdType::Double),
+            ("date", XsdType::Date),
+            ("dateTime", XsdType::DateTime),
+            ("anyURI", XsdType::AnyUri),
+            ("base64Binary", XsdType::Base64Binary),
+        ] {
+            let iri = format!("{}{name}", ns::XSD);
+            assert_eq!(datatype.as_iri(), iri);
+            assert_eq!(name.parse::<XsdType>().unwrap(), datatype);
+            assert_eq!(format!("xsd:{name}").parse::<XsdType>().unwrap(), datatype);
+            assert_eq!(iri.parse::<XsdType>().unwrap(), datatype);
+            assert_eq!(datatype.to_string().parse::<XsdType>().unwrap(), datatype);
+        }
+        assert_eq!(XsdType::LangString.as_iri(), ns::RDF_LANG_STRING);
+        assert_eq!(
+            ns::RDF_LANG_STRING.parse::<XsdType>().unwrap(),
+            XsdType::LangString
+        );
+
+        let wkt = "http://www.opengis.net/ont/geosparql#wktLiteral";
+        let custom = wkt.parse::<XsdType>().unwrap();
+        assert_eq!(custom, XsdType::Custom(wkt.to_string()));
+        assert_eq!(custom.as_iri(), wkt);
+        assert_eq!(
+            "geo:wktLiteral".parse::<XsdType>().unwrap(),
+            XsdType::Custom("geo:wktLiteral".to_string())
+        );
+        let error = "intger".parse::<XsdType>().unwrap_err();
+        assert_eq!(error.to_string(), "unknown XSD datatype: intger");
+
+        #[derive(Serialize)]
+        struct Place {
+            id: String,
+            location: String,
+        }
+
+        let mut config = SerializerConfig::builder()
+            .prefix("geo", "http://www.opengis.net/ont/geosparql#")
+            .subject(
+                SubjectConfig::builder("Place")
+                    .rdf_type("https://example.org/ns#Place")
+                    .identifier("id", "https://example.org/")
+                    .property("location", "https://example.org/ns#location")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        config.subjects.get_mut("Place").unwrap().properties[0].datatype =
+            Some(XsdType::Custom("geo:wktLiteral".to_string()));
+        let place = Place {
+            id: "bern".to_string(),
+            location: "POINT(7.44 46.95)".to_string(),
+        };
+        let output = to_string(&place, &config).unwrap();
+        assert!(
+            output.contains(&format!(r#""POINT(7.44 46.95)"^^<{wkt}>"#)),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_property_counts() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            shortcode: Option<String>,
+            names: Vec<String>,
+        }
+
+        let property = |field: &str, min_count, max_count| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("https://ns.dasch.swiss/repository#{field}"),
+            min_count,
+            max_count,
+            ..Default::default()
+        };
+        let config = || SerializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    rdf_type: "https://ns.dasch.swiss/repository#Project".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    properties: vec![
+                        property("shortcode", Some(1), Some(1)),
+                        property("names", Some(1), Some(2)),
+                    ],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let project = |shortcode: Option<&str>, names: &[&str]| Project {
+            id: "081C".to_string(),
+            shortcode: shortcode.map(str::to_string),
+            names: names.iter().map(|name| name.to_string()).collect(),
+        };
+
+        let output = to_string(&project(Some("081C"), &["Events", "Ereignisse"]), &config());
+        assert!(output.is_ok(), "{output:?}");
+
+        let error = to_string(&project(Some("081C"), &[]), &config()).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::CardinalityViolation { subject, predicate, expected: 1, found: 0 }
+                    if subject == "<https://ark.dasch.swiss/ark:/72163/1/081C>"
+                        && predicate == "https://ns.dasch.swiss/repository#names"
+            ),
+            "{error}"
+        );
+
+        let error = to_string(&project(Some("081C"), &["a", "b", "c"]), &config()).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::CardinalityViolation {
+                    expected: 2,
+                    found: 3,
+                    ..
+                }
+            ),
+            "{error}"
+        );
+
+        let error = to_string(&project(None, &["Events"]), &config()).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::CardinalityViolation { predicate, expected: 1, found: 0, .. }
+                    if predicate == "https://ns.dasch.swiss/repository#shortcode"
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_property_kind() {
+        #[derive(Serialize)]
+        struct Project<D> {
+            id: String,
+            dataset: D,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+        }
+
+        let subject = |name: &str, properties| SubjectConfig {
+            struct_name: name.to_string(),
+            rdf_type: format!("https://example.org/ns#{name}"),
+            identifier_field: "id".to_string(),
+            identifier_prefix: "https://example.org/".to_string(),
+            properties,
+            ..Default::default()
+        };
+        let config = |kind| SerializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    subject(
+                        "Project",
+                        vec![PropertyConfig {
+                            struct_field: "dataset".to_string(),
+                            rdf_property: "https://example.org/ns#hasDataset".to_string(),
+                            kind: Some(kind),
+                            ..Default::default()
+                        }],
+                    ),
+                ),
+                ("Dataset".to_string(), subject("Dataset", Vec::new())),
+            ]),
+            ..Default::default()
+        };
+        let linked = Project {
+            id: "p".to_string(),
+            dataset: Dataset {
+                id: "d".to_string(),
+            },
+        };
+        let literal = Project {
+            id: "p".to_string(),
+            dataset: "d".to_string(),
+        };
+
+        assert!(to_string(&linked, &config(TermKind::Subject)).is_ok());
+        assert!(to_string(&literal, &config(TermKind::Literal)).is_ok());
+
+        let error = to_string(&literal, &config(TermKind::Subject)).unwrap_err();
+        assert_eq!(
+            error.inner().to_string(),
+            "serialize_struct -> serialize_field -> Project.dataset is declared as a subject, \
+             found a literal: d"
+        );
+        let error = to_string(&linked, &config(TermKind::Literal)).unwrap_err();
+        assert_eq!(
+            error.inner().to_string(),
+            "serialize_struct -> serialize_field -> Project.dataset is declared as a literal, \
+             found a subject: https://example.org/d"
+        );
+    }
+
+    #[test]
+    fn test_property_groups() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            name: String,
+            created_by: String,
+        }
+
+        let audit = vec![PropertyConfig {
+            struct_field: "created_by".to_string(),
+            rdf_property: "https://example.org/ns#createdBy".to_string(),
+            ..Default::default()
+        }];
+        let built = |own: &str| {
+            SerializerConfig::builder()
+                .property_group("audit", audit.clone())
+                .subject(
+                    SubjectConfig::builder("Project")
+                        .rdf_type("https://example.org/ns#Project")
+                        .identifier("id", "https://example.org/")
+                        .property(own, "https://example.org/ns#hasName")
+                        .include("audit")
+                        .build(),
+                )
+                .build()
+        };
+        let project = Project {
+            id: "p".to_string(),
+            name: "Events".to_string(),
+            created_by: "admin".to_string(),
+        };
+
+        let config = built("name").unwrap();
+        assert!(config.subjects["Project"].include_properties.is_empty());
+        let triples = to_triples(&project, &config).unwrap();
+        assert!(triples.contains(&OwnedTriple {
+            subject: OwnedSubject::NamedNode("https://example.org/p".to_string()),
+            predicate: "https://example.org/ns#createdBy".to_string(),
+            object: OwnedObject::Literal(OwnedLiteral::Typed {
+                value: "admin".to_string(),
+                datatype: ns::XSD_STRING.to_string(),
+            }),
+        }));
+
+        // Groups are also included when serializing with a config that was not built.
+        let config = SerializerConfig {
+            property_groups: HashMap::from([("audit".to_string(), audit.clone())]),
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    rdf_type: "https://example.org/ns#Project".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    include_properties: vec!["audit".to_string()],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let output = to_string(&project, &config).unwrap();
+        assert!(
+            output.contains("<https://example.org/ns#createdBy>"),
+            "{output}"
+        );
+
+        let error = built("created_by").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "validate -> field created_by of subject Project is also in property group: audit"
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let project = || {
+            SubjectConfig::builder("Project")
+                .rdf_type("https://ns.dasch.swiss/repository#Project")
+                .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                .property("name", "https://ns.dasch.swiss/repository#hasName")
+                .build()
+        };
+        let core = || {
+            SerializerConfig::builder()
+                .base_iri("https://ark.dasch.swiss/")
+                .prefix("dsp", "https://ns.dasch.swiss/repository#")
+                .subject(project())
+                .build()
+                .unwrap()
+        };
+        let plugin = SerializerConfig::builder()
+            .prefix("dsp", "https://ns.dasch.swiss/repository#")
+            .prefix("geo", "http://www.opengis.net/ont/geosparql#")
+            .subject(project())
+            .subject(
+                SubjectConfig::builder("Place")
+                    .rdf_type("https://example.org/ns#Place")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        let merged = core().merge(plugin).unwrap();
+        assert_eq!(merged.base_iri, "https://ark.dasch.swiss/");
+        assert_eq!(merged.namespaces.len(), 2);
+        let mut subjects: Vec<_> = merged.subjects.keys().map(String::as_str).collect();
+        subjects.sort_unstable();
+        assert_eq!(subjects, ["Place", "Project"]);
+        assert_eq!(merged.subjects["Project"], project());
+
+        let conflicting = SerializerConfig::builder()
+            .prefix("dsp", "https://example.org/dsp#")
+            .build()
+            .unwrap();
+        let error = core().merge(conflicting).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "merge -> conflicting namespaces for prefix: dsp"
+        );
+
+        let mut renamed = project();
+        renamed.properties[0].rdf_property = "https://example.org/ns#name".to_string();
+        let error = core()
+            .merge(
+                SerializerConfig::builder()
+                    .subject(renamed)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "merge -> conflicting subject configs for struct: Project"
+        );
+
+        let other = SerializerConfig::builder()
+            .base_iri("https://example.org/")
+            .build()
+            .unwrap();
+        assert!(core().merge(other).is_err());
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_rdf_mapped() {
+        use crate::{to_string_mapped, RdfMapped};
+
+        #[derive(Serialize, RdfMapped)]
+        #[rdf(
+            type = "dsp:Project",
+            id(field = "id", prefix = "https://ark.dasch.swiss/ark:/72163/1/"),
+            prefix(dsp = "https://ns.dasch.swiss/repository#")
+        )]
+        struct Project {
+            id: String,
+            #[rdf(property = "dsp:hasName", lang = "en")]
+            name: String,
+            #[rdf(property = "dsp:hasShortcode")]
+            shortcode: String,
+            internal: bool,
+        }
+
+        let expected = SubjectConfig {
+            struct_name: "Project".to_string(),
+            rdf_type: "dsp:Project".to_string(),
+            identifier_field: "id".to_string(),
+            identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+            properties: vec![
+                PropertyConfig {
+                    struct_field: "name".to_string(),
+                    rdf_property: "dsp:hasName".to_string(),
+                    language: Some("en".to_string()),
+                    ..Default::default()
+                },
+                PropertyConfig {
+                    struct_field: "shortcode".to_string(),
+                    rdf_property: "dsp:hasShortcode".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(Project::subject_config(), expected);
+        assert_eq!(
+            Project::namespaces(),
+            [(
+                "dsp".to_string(),
+                "https://ns.dasch.swiss/repository#".to_string()
+            )]
+        );
+
+        let project = Project {
+            id: "081C".to_string(),
+            name: "Hôtel de Musique".to_string(),
+            shortcode: "081C".to_string(),
+            internal: true,
+        };
+        let output = to_string_mapped(&project).unwrap();
+        assert!(
+            output.contains("<https://ns.dasch.swiss/repository#hasName> \"Hôtel de Musique\"@en"),
+            "{output}"
+        );
+        assert!(!output.contains("internal"), "{output}");
+    }
+
+    #[test]
+    fn test_rdf_mapped() {
+        use serde::Deserialize;
+
+        use crate::{from_str_mapped, to_string_mapped, RdfMapped};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Project {
+            id: String,
+            name: String,
+            datasets: Vec<Dataset>,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Dataset {
+            id: String,
+            title: String,
+        }
+
+        impl RdfMapped for Project {
+            fn subject_config() -> SubjectConfig {
+                SubjectConfig::builder("Project")
+                    .rdf_type("dsp:Project")
+                    .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                    .property("name", "dsp:hasName")
+                    .property("datasets", "dsp:hasDataset")
+                    .build()
+            }
+
+            fn namespaces() -> Vec<(String, String)> {
+                vec![(
+                    "dsp".to_string(),
+                    "https://ns.dasch.swiss/repository#".to_string(),
+                )]
+            }
+
+            fn nested_configs(config: &mut SerializerConfig) {
+                Dataset::collect_configs(config);
+            }
+        }
+
+        impl RdfMapped for Dataset {
+            fn subject_config() -> SubjectConfig {
+                SubjectConfig::builder("Dataset")
+                    .rdf_type("dsp:Dataset")
+                    .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                    .property("title", "dcterms:title")
+                    .build()
+            }
+        }
+
+        let config = Project::serializer_config();
+        assert!(config.subjects.contains_key("Project"));
+        assert!(config.subjects.contains_key("Dataset"));
+        assert_eq!(
+            config.namespaces["dsp"],
+            "https://ns.dasch.swiss/repository#"
+        );
+
+        let project = Project {
+            id: "081C".to_string(),
+            name: "Hôtel de Musique".to_string(),
+            datasets: vec![Dataset {
+                id: "081C-1".to_string(),
+                title: "Letters".to_string(),
+            }],
+        };
+        let output = to_string_mapped(&project).unwrap();
+        assert!(
+            output.contains("<http://purl.org/dc/terms/title> \"Letters\""),
+            "{output}"
+        );
+        let round_trip: Project = from_str_mapped(&output).unwrap();
+        assert_eq!(round_trip, project);
+    }
+
+    #[test]
+    fn test_default_identifier_prefix() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            dataset: Dataset,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+        }
+
+        let builder = |dataset_prefix: &str| {
+            SerializerConfig::builder()
+                .prefix("dsp", "https://ns.dasch.swiss/repository#")
+                .subject(
+                    SubjectConfig::builder("Project")
+                        .rdf_type("dsp:Project")
+                        .identifier("id", "")
+                        .property("dataset", "dsp:hasDataset")
+                        .build(),
+                )
+                .subject(
+                    SubjectConfig::builder("Dataset")
+                        .rdf_type("dsp:Dataset")
+                        .identifier("id", dataset_prefix)
+                        .build(),
+                )
+        };
+        let project = Project {
+            id: "081C".to_string(),
+            dataset: Dataset {
+                id: "081C-1".to_string(),
+            },
+        };
+
+        // The default is used by subjects without a prefix of their own, which wins otherwise.
+        let config = builder("https://example.org/datasets/")
+            .default_identifier_prefix("https://ark.dasch.swiss/ark:/72163/1/")
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.subjects["Project"].identifier_prefix,
+            "https://ark.dasch.swiss/ark:/72163/1/"
+        );
+        let output = to_string(&project, &config).unwrap();
+        assert!(
+            output.contains("<https://ark.dasch.swiss/ark:/72163/1/081C>"),
+            "{output}"
+        );
+        assert!(
+            output.contains("<https://example.org/datasets/081C-1>"),
+            "{output}"
+        );
+
+        let error = builder("").build().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "validate -> no identifier_prefix configured for subject: Dataset, and no \
+             default_identifier_prefix"
+        );
+    }
+
+    #[test]
+    fn test_inverse_property() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            datasets: Vec<Dataset>,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+            title: String,
+        }
+
+        let mut config = SerializerConfig::builder()
+            .prefix("dsp", "https://ns.dasch.swiss/repository#")
+            .default_identifier_prefix("https://ark.dasch.swiss/ark:/72163/1/")
+            .subject(
+                SubjectConfig::builder("Project")
+                    .rdf_type("dsp:Project")
+                    .identifier("id", "")
+                    .property("datasets", "dsp:isPartOf")
+                    .build(),
+            )
+            .subject(
+                SubjectConfig::builder("Dataset")
+                    .rdf_type("dsp:Dataset")
+                    .identifier("id", "")
+                    .property("title", "dsp:hasTitle")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        config.subjects.get_mut("Project").unwrap().properties[0].inverse = true;
+
+        let project = Project {
+            id: "081C".to_string(),
+            datasets: vec![Dataset {
+                id: "081C-1".to_string(),
+                title: "Letters".to_string(),
+            }],
+        };
+        let triples = to_triples(&project, &config).unwrap();
+        let ark = |id: &str| format!("https://ark.dasch.swiss/ark:/72163/1/{id}");
+        let is_part_of = "https://ns.dasch.swiss/repository#isPartOf";
+        assert!(triples.contains(&OwnedTriple {
+            subject: OwnedSubject::NamedNode(ark("081C-1")),
+            predicate: is_part_of.to_string(),
+            object: OwnedObject::NamedNode(ark("081C")),
+        }));
+        // The dataset still has its own triples, and the project none with the property.
+        assert!(triples
+            .iter()
+            .any(|t| t.subject == OwnedSubject::NamedNode(ark("081C-1"))
+                && t.predicate == "https://ns.dasch.swiss/repository#hasTitle"));
+        assert!(!triples.iter().any(
+            |t| t.subject == OwnedSubject::NamedNode(ark("081C")) && t.predicate == is_part_of
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_to_rdf() {
+        use crate::{json_to_rdf, to_graph, Graph};
+
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            name: String,
+            description: HashMap<String, String>,
+            shortcode: String,
+            datasets: Vec<Dataset>,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+            title: String,
+        }
+
+        let config = || {
+            SerializerConfig::builder()
+                .subject(
+                    SubjectConfig::builder("Project")
+                        .rdf_type("https://ns.dasch.swiss/repository#Project")
+                        .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                        .property("name", "https://ns.dasch.swiss/repository#hasName")
+                        .property(
+                            "description",
+                            "https://ns.dasch.swiss/repository#hasDescription",
+                        )
+                        .property(
+                            "shortcode",
+                            "https://ns.dasch.swiss/repository#hasShortcode",
+                        )
+                        .build(),
+                )
+                .subject(
+                    SubjectConfig::builder("Dataset")
+                        .rdf_type("https://ns.dasch.swiss/repository#Dataset")
+                        .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                        .property("title", "https://ns.dasch.swiss/repository#hasTitle")
+                        .build(),
+                )
+                .build()
+                .map(|mut config| {
+                    let project = config.subjects.get_mut("Project").unwrap();
+                    project.properties.push(PropertyConfig {
+                        struct_field: "datasets".to_string(),
+                        rdf_property: "https://ns.dasch.swiss/repository#hasDataset".to_string(),
+                        nested_subject: Some("Dataset".to_string()),
+                        ..Default::default()
+                    });
+                    config
+                })
+                .unwrap()
+        };
+        let json = r#"{
+            "id": "081C",
+            "name": "Hôtel de Musique Bern",
+            "description": {"EN": "The Hôtel de Musique", "DE": "Das Hôtel de Musique"},
+            "shortcode": "081C",
+            "datasets": [{"id": "dataset-0", "title": "Events"}, {"id": "dataset-1", "title": "Letters"}],
+            "notes": null
+        }"#;
+        let project = Project {
+            id: "081C".to_string(),
+            name: "Hôtel de Musique Bern".to_string(),
+            description: HashMap::from([
+                ("EN".to_string(), "The Hôtel de Musique".to_string()),
+                ("DE".to_string(), "Das Hôtel de Musique".to_string()),
+            ]),
+            shortcode: "081C".to_string(),
+            datasets: vec![
+                Dataset {
+                    id: "dataset-0".to_string(),
+                    title: "Events".to_string(),
+                },
+                Dataset {
+                    id: "dataset-1".to_string(),
+                    title: "Letters".to_string(),
+                },
+            ],
+        };
+
+        let turtle = json_to_rdf(json, "Project", &config()).unwrap();
+        assert_eq!(
+            Graph::parse_turtle(&turtle).unwrap(),
+            to_graph(&project, &config()).unwrap()
+        );
+
+        // An array of documents holds several subjects.
+        let turtle = json_to_rdf(
+            r#"[{"id": "081C", "name": "Events"}, {"id": "0803", "name": "Letters"}]"#,
+            "Project",
+            &config(),
+        )
+        .unwrap();
+        assert_eq!(Graph::parse_turtle(&turtle).unwrap().subjects().count(), 2);
+
+        let error = json_to_rdf(json, "Collection", &config()).unwrap_err();
+        assert!(
+            matches!(error, Error::MissingSubjectConfig { .. }),
+            "{error}"
+        );
+        let error = json_to_rdf(r#"{"id": "081C""#, "Project", &config()).unwrap_err();
+        assert!(error.to_string().contains("EOF"), "{error}");
+    }
+
+    #[test]
+    fn test_skolemize() {
+        #[derive(Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Person {
+            id: String,
+            addresses: Vec<Address>,
+        }
+
+        #[derive(Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Address {
+            #[serde(default, skip_serializing)]
+            id: Option<String>,
+            street: String,
+        }
+
+        let config = |deterministic| SerializerConfig {
+            namespaces: HashMap::from([(
+                "genid".to_string(),
+                "https://example.org/.well-known/genid/".to_string(),
+            )]),
+            subjects: HashMap::from([
+                (
+                    "Person".to_string(),
+                    SubjectConfig {
+                        struct_name: "Person".to_string(),
+                        rdf_type: "https://example.org/ns#Person".to_string(),
+                        identifier_field: "id".to_string(),
+                        identifier_prefix: "https://example.org/".to_string(),
+                        properties: vec![PropertyConfig {
+                            struct_field: "addresses".to_string(),
+                            rdf_property: "https://example.org/ns#address".to_string(),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "Address".to_string(),
+                    SubjectConfig {
+                        struct_name: "Address".to_string(),
+                        rdf_type: "https://example.org/ns#Address".to_string(),
+                        properties: vec![PropertyConfig {
+                            struct_field: "street".to_string(),
+                            rdf_property: "https://example.org/ns#street".to_string(),
+                            ..Default::default()
+                        }],
+                        blank_node_prefix: Some("addr".to_string()),
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            skolemize: Some("genid:".to_string()),
+            deterministic,
+            ..Default::default()
+        };
+        let person = Person {
+            id: "p1".to_string(),
+            addresses: vec![
+                Address {
+                    id: None,
+                    street: "Main Street".to_string(),
+                },
+                Address {
+                    id: None,
+                    street: "Side Street".to_string(),
+                },
+            ],
+        };
+
+        let triples = to_triples(&person, &config(true)).unwrap();
+        assert!(triples.iter().all(|triple| {
+            !matches!(triple.subject, OwnedSubject::BlankNode(_))
+                && !matches!(triple.object, OwnedObject::BlankNode(_))
+        }));
+        for (label, street) in [("addr0", "Main Street"), ("addr1", "Side Street")] {
+            let iri = format!("https://example.org/.well-known/genid/{label}");
+            assert!(triples.iter().any(|triple| {
+                triple.predicate == "https://example.org/ns#address"
+                    && triple.object == OwnedObject::NamedNode(iri.clone())
+            }));
+            assert!(triples.iter().any(|triple| {
+                triple.subject == OwnedSubject::NamedNode(iri.clone())
+                    && matches!(&triple.object, OwnedObject::Literal(literal) if literal.lexical_form() == street)
+            }));
+        }
+
+        // Outside of deterministic mode, the IRIs are unique to the run but stay the same for
+        // each blank node.
+        let triples = to_triples(&person, &config(false)).unwrap();
+        let addresses: Vec<_> = triples
+            .iter()
+            .filter(|triple| triple.predicate == "https://example.org/ns#address")
+            .map(|triple| match &triple.object {
+                OwnedObject::NamedNode(iri) => iri.clone(),
+                object => panic!("expected a skolem IRI, found: {object:?}"),
+            })
+            .collect();
+        assert_eq!(addresses.len(), 2);
+        assert_ne!(addresses[0], addresses[1]);
+        for iri in &addresses {
+            assert!(
+                iri.starts_with("https://example.org/.well-known/genid/"),
+                "{iri}"
+            );
+            assert!(
+                !iri.ends_with("/addr0") && !iri.ends_with("/addr1"),
+                "{iri}"
+            );
+            assert!(triples
+                .iter()
+                .any(|triple| triple.subject == OwnedSubject::NamedNode(iri.clone())));
+        }
+
+        // The deserializer reads the skolem IRIs back as blank nodes, whose identifier fields
+        // are `None`, or the label with `blank_node_labels`.
+        let output = to_string(&person, &config(true)).unwrap();
+        let mut de_config = crate::DeserializerConfig::try_from(&config(true)).unwrap();
+        de_config
+            .subjects
+            .get_mut("Address")
+            .unwrap()
+            .identifier_field = "id".to_string();
+        let round_trip: Person = crate::from_str_with_config(&output, &de_config).unwrap();
+        assert_eq!(round_trip, person);
+        de_config.blank_node_labels = true;
+        let labeled: Person = crate::from_str_with_config(&output, &de_config).unwrap();
+        assert_eq!(labeled.addresses[0].id.as_deref(), Some("_:addr0"));
+        de_config.skolem_base = None;
+        let unskolemized: Person = crate::from_str_with_config(&output, &de_config).unwrap();
+        assert_eq!(
+            unskolemized.addresses[0].id.as_deref(),
+            Some("https://example.org/.well-known/genid/addr0")
+        );
+    }
+
+    #[test]
+    fn test_invalid_iris() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            name: String,
+        }
+
+        let config = |prefix: &str, property: &str| SerializerConfig {
+            subjects: HashMap::from([(
+                "Project".to_string(),
+                SubjectConfig {
+                    struct_name: "Project".to_string(),
+                    rdf_type: "https://example.org/ns#Project".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: prefix.to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: property.to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let project = |id: &str| Project {
+            id: id.to_string(),
+            name: "Events".to_string(),
+        };
+        let context = |error: Error| match error.inner() {
+            Error::InvalidIri { iri, context } => (iri.clone(), context.clone()),
+            _ => panic!("{error}"),
+        };
+
+        let error = to_string(
+            &project("my id"),
+            &config("https://example.org/", "https://example.org/ns#hasName"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "identifier for subject Project is not an absolute IRI: https://example.org/my id"
+        );
+        assert_eq!(
+            context(error),
+            (
+                "https://example.org/my id".to_string(),
+                "identifier for subject Project".to_string()
+            )
+        );
+
+        let error =
+            to_string(&project("p"), &config("https://example.org/", "hasName")).unwrap_err();
+        assert_eq!(
+            context(error),
+            (
+                "hasName".to_string(),
+                "rdf_property of field name".to_string()
+            )
+        );
+
+        let mut hooked = config("https://example.org/", "https://example.org/ns#hasName");
+        hooked.on_subject = Some(SubjectHook::new(|_, _| {
+            vec![(
+                "generated at".to_string(),
+                OwnedLiteral::Simple {
+                    value: "2024".to_string(),
+                },
+            )]
+        }));
+        let error = to_string(&project("p"), &hooked).unwrap_err();
+        assert_eq!(
+            context(error),
+            (
+                "generated at".to_string(),
+                "extra predicate for subject Project".to_string()
+            )
+        );
+
+        let mut errors = config("https://example.org/", "https://example.org/ns#has name")
+            .validate_fields("Project", &["id", "name"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            context(errors.remove(0)),
+            (
+                "https://example.org/ns#has name".to_string(),
+                "rdf_property of field name".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_error_context() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            datasets: Vec<Dataset>,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+            kind: String,
+        }
+
+        let subject = |name: &str, properties, type_field: Option<&str>| SubjectConfig {
+            struct_name: name.to_string(),
+            rdf_type: format!("https://example.org/ns#{name}"),
+            identifier_field: "id".to_string(),
+            identifier_prefix: "https://example.org/".to_string(),
+            properties,
+            type_field: type_field.map(str::to_string),
+            ..Default::default()
+        };
+        let config = SerializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    subject(
+                        "Project",
+                        vec![PropertyConfig {
+                            struct_field: "datasets".to_string(),
+                            rdf_property: "https://example.org/ns#hasDataset".to_string(),
+                            ..Default::default()
+                        }],
+                        None,
+                    ),
+                ),
+                (
+                    "Dataset".to_string(),
+                    subject("Dataset", Vec::new(), Some("kind")),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let project = Project {
+            id: "p".to_string(),
+            datasets: (0..20)
+                .map(|i| Dataset {
+                    id: format!("dataset-{i:04}"),
+                    kind: match i {
+                        17 => "not an iri".to_string(),
+                        _ => "https://example.org/ns#Dataset".to_string(),
+                    },
+                })
+                .collect(),
+        };
+
+        let error = to_string(&project, &config).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "while serializing <https://example.org/dataset-0017> (Dataset.kind): rdf:type in \
+             field kind is not an absolute IRI: not an iri"
+        );
+        assert!(
+            matches!(
+                &error,
+                Error::Contextual { operation: "serializing", subject, field: Some(field), .. }
+                    if subject == "<https://example.org/dataset-0017>" && field == "Dataset.kind"
+            ),
+            "{error}"
+        );
+        assert!(matches!(error.inner(), Error::InvalidIri { .. }), "{error}");
+    }
+
+    #[test]
+    fn test_to_string_collecting() {
+        use crate::Graph;
+
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            datasets: Vec<Dataset>,
+            owner: Option<Person>,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+        }
+
+        // Has no subject config.
+        #[derive(Serialize)]
+        struct Person {
+            name: String,
+        }
+
+        let subject = |name: &str, properties| SubjectConfig {
+            struct_name: name.to_string(),
+            rdf_type: format!("https://example.org/ns#{name}"),
+            identifier_field: "id".to_string(),
+            identifier_prefix: "https://example.org/".to_string(),
+            properties,
+            ..Default::default()
+        };
+        let property = |field: &str, predicate: &str| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("https://example.org/ns#{predicate}"),
+            ..Default::default()
+        };
+        let config = SerializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    subject(
+                        "Project",
+                        vec![
+                            property("datasets", "hasDataset"),
+                            property("owner", "hasOwner"),
+                        ],
+                    ),
+                ),
+                ("Dataset".to_string(), subject("Dataset", Vec::new())),
+            ]),
+            ..Default::default()
+        };
+        let project = |id: &str, owner: Option<&str>| Project {
+            id: format!("project-{id}"),
+            datasets: vec![Dataset {
+                id: format!("dataset-{id}"),
+            }],
+            owner: owner.map(|name| Person {
+                name: name.to_string(),
+            }),
+        };
+        let projects = [
+            project("a", None),
+            project("b", Some("Alice")),
+            project("c", None),
+        ];
+
+        let (output, errors) = to_string_collecting(&projects, &config).unwrap();
+        let graph = Graph::parse_turtle(&output).unwrap();
+        for id in ["a", "c"] {
+            let project = OwnedSubject::NamedNode(format!("https://example.org/project-{id}"));
+            let datasets: Vec<_> = graph
+                .objects_for(&project, "https://example.org/ns#hasDataset")
+                .collect();
+            let dataset = OwnedObject::NamedNode(format!("https://example.org/dataset-{id}"));
+            assert_eq!(datasets, [&dataset], "{output}");
+        }
+        // Not even the dataset of the failed project, which was complete, is written.
+        assert!(!output.contains("-b>"), "{output}");
+        assert_eq!(errors.len(), 1);
+        let (index, error) = &errors[0];
+        assert_eq!(*index, 1);
+        assert!(error.to_string().contains("Person"), "{error}");
+    }
+
+    #[test]
+    fn test_reused_serializer() {
+        use crate::Serializer;
+
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            name: HashMap<String, String>,
+            addresses: Vec<Address>,
+        }
+
+        // Blank nodes, whose labels are numbered per document.
+        #[derive(Serialize)]
+        struct Address {
+            city: String,
+        }
+
+        // Has no subject config.
+        #[derive(Serialize)]
+        struct Person {
+            name: String,
+        }
+
+        let property = |field: &str| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("https://example.org/ns#{field}"),
+            ..Default::default()
+        };
+        let config = SerializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    SubjectConfig {
+                        struct_name: "Project".to_string(),
+                        rdf_type: "https://example.org/ns#Project".to_string(),
+                        identifier_field: "id".to_string(),
+                        identifier_prefix: "https://example.org/".to_string(),
+                        properties: vec![property("name"), property("addresses")],
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "Address".to_string(),
+                    SubjectConfig {
+                        struct_name: "Address".to_string(),
+                        rdf_type: "https://example.org/ns#Address".to_string(),
+                        properties: vec![property("city")],
+                        blank_node_prefix: Some("addr".to_string()),
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            deterministic: true,
+            sort_map_entries: true,
+            ..Default::default()
+        };
+        let events = Project {
+            id: "081C".to_string(),
+            name: HashMap::from([
+                ("en".to_string(), "Events".to_string()),
+                ("de".to_string(), "Ereignisse".to_string()),
+            ]),
+            addresses: vec![
+                Address {
+                    city: "Bern".to_string(),
+                },
+                Address {
+                    city: "Basel".to_string(),
+                },
+            ],
+        };
+        let letters = Project {
+            id: "0801".to_string(),
+            name: HashMap::from([("fr".to_string(), "Lettres".to_string())]),
+            addresses: vec![Address {
+                city: "Genève".to_string(),
+            }],
+        };
+
+        let mut serializer = Serializer::new(&config).unwrap();
+        events.serialize(&mut serializer).unwrap();
+        let first = serializer.take_string().unwrap();
+        assert_eq!(first, to_string(&events, &config).unwrap());
+        assert!(first.contains("_:addr1"), "{first}");
+
+        // A value that fails half way leaves no subject, blank node or map entry behind.
+        let failing = (
+            &letters,
+            Person {
+                name: "Alice".to_string(),
+            },
+        );
+        assert!(failing.serialize(&mut serializer).is_err());
+        serializer.reset();
+
+        letters.serialize(&mut serializer).unwrap();
+        let second = serializer.take_string().unwrap();
+        assert_eq!(second, to_string(&letters, &config).unwrap());
+        assert!(second.contains("_:addr0"), "{second}");
+        assert!(!second.contains("_:addr1"), "{second}");
+        assert!(!second.contains("Events"), "{second}");
+
+        // Nothing is left over after taking the output.
+        assert_eq!(serializer.take_string().unwrap(), "");
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_to_string_par() {
+        use crate::{to_string_par, Graph};
+
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            shortcode: usize,
+            addresses: Vec<Address>,
+        }
+
+        // Blank nodes, whose labels must not collide between chunks.
+        #[derive(Serialize)]
+        struct Address {
+            city: String,
+        }
+
+        // Has no subject config.
+        #[derive(Serialize)]
+        struct Person {
+            name: String,
+        }
+
+        let property = |field: &str| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("https://example.org/ns#{field}"),
+            ..Default::default()
+        };
+        let mut config = SerializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    SubjectConfig {
+                        struct_name: "Project".to_string(),
+                        rdf_type: "https://example.org/ns#Project".to_string(),
+                        identifier_field: "id".to_string(),
+                        identifier_prefix: "https://example.org/".to_string(),
+                        properties: vec![property("shortcode"), property("addresses")],
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "Address".to_string(),
+                    SubjectConfig {
+                        struct_name: "Address".to_string(),
+                        rdf_type: "https://example.org/ns#Address".to_string(),
+                        properties: vec![property("city")],
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            deterministic: true,
+            ..Default::default()
+        };
+        let records: Vec<Project> = (0..1000)
+            .map(|n| Project {
+                id: format!("{n:04X}"),
+                shortcode: n,
+                addresses: (0..n % 3)
+                    .map(|m| Address {
+                        city: format!("City {}", n * m),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        for compact in [false, true] {
+            config.compact = compact;
+            let sequential = to_string(&records, &config).unwrap();
+            let parallel = to_string_par(&records, &config).unwrap();
+            assert!(parallel.contains("_:t0b0"), "{parallel}");
+            assert!(parallel.contains("_:t3b0"), "{parallel}");
+            let sequential = Graph::parse_turtle(&sequential).unwrap();
+            let parallel = Graph::parse_turtle(&parallel).unwrap();
+            assert!(parallel.is_isomorphic(&sequential), "compact: {compact}");
+        }
+        config.compact = false;
+        assert_eq!(to_string_par::<Project>(&[], &config).unwrap(), "");
+
+        // The error of a value fails the whole slice.
+        let people: Vec<Person> = (0..1000)
+            .map(|n| Person {
+                name: format!("Person {n}"),
+            })
+            .collect();
+        let error = to_string_par(&people, &config).unwrap_err();
+        assert!(error.to_string().contains("Person"), "{error}");
+
+        // It is the error of the first value in the slice that fails.
+        struct Failing(usize);
+
+        impl Serialize for Failing {
+            fn serialize<S>(&self, _serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                Err(serde::ser::Error::custom(format!("value {}", self.0)))
+            }
+        }
+
+        let failing: Vec<Failing> = (0..1000).map(Failing).collect();
+        for _ in 0..10 {
+            let error = to_string_par(&failing, &config).unwrap_err().to_string();
+            assert!(error.contains("value 0"), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_sort_map_entries() {
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+            name: HashMap<String, String>,
+        }
+
+        let config = SerializerConfig {
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "https://example.org/ns#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "https://example.org/ns#hasName".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            sort_map_entries: true,
+            ..Default::default()
+        };
+
+        let test = Test {
+            id: "my-id".to_string(),
+            name: HashMap::from([
+                ("fr".to_string(), "Nom".to_string()),
+                ("en".to_string(), "Name".to_string()),
+                ("de".to_string(), "Name".to_string()),
+            ]),
+        };
+        let output = to_string(&test, &config).unwrap();
+        let positions: Vec<usize> = ["\"Name\"@de", "\"Name\"@en", "\"Nom\"@fr"]
+            .iter()
+            .map(|literal| output.find(literal).expect(&output))
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{output}");
+
+        // Keys become the languages of the literals, so they have to be language tags.
+        for key in ["en US", "en-", "de--CH", "", "1en", "fr@"] {
+            let test = Test {
+                id: "my-id".to_string(),
+                name: HashMap::from([(key.to_string(), "Name".to_string())]),
+            };
+            let error = to_string(&test, &config).unwrap_err();
+            assert!(
+                matches!(&error, Error::Contextual { source, .. }
+                    if matches!(&**source, Error::InvalidLanguageTag(tag) if tag == key)),
+                "{error:?}"
+            );
+            assert!(error.is_data_error(), "{error}");
+        }
+        let test = Test {
+            id: "my-id".to_string(),
+            name: HashMap::from([("de-CH-1901".to_string(), "Name".to_string())]),
+        };
+        let output = to_string(&test, &config).unwrap();
+        assert!(output.contains("\"Name\"@de-ch-1901"), "{output}");
+    }
+
+    #[test]
+    fn test_optional_sequences() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            keywords: Option<Vec<String>>,
+            datasets: Option<Vec<Dataset>>,
+            note: Option<Option<String>>,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+        }
+
+        let subject = |name: &str, properties| SubjectConfig {
+            struct_name: name.to_string(),
+            rdf_type: format!("https://example.org/ns#{name}"),
+            identifier_field: "id".to_string(),
+            identifier_prefix: "https://example.org/".to_string(),
+            properties,
+            ..Default::default()
+        };
+        let property = |field: &str| PropertyConfig {
+            struct_field: field.to_string(),
+            rdf_property: format!("https://example.org/ns#{field}"),
+            ..Default::default()
+        };
+        let config = || SerializerConfig {
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    subject(
+                        "Project",
+                        vec![property("keywords"), property("datasets"), property("note")],
+                    ),
+                ),
+                ("Dataset".to_string(), subject("Dataset", Vec::new())),
+            ]),
+            ..Default::default()
+        };
+        let count = |project: &Project, field: &str| {
+            let predicate = format!("https://example.org/ns#{field}");
+            to_triples(project, &config())
+                .unwrap()
+                .iter()
+                .filter(|t| t.predicate == predicate)
+                .count()
+        };
+        let dataset = |id: &str| Dataset { id: id.to_string() };
+
+        let shapes = [
+            (None, None, None, 0),
+            (Some(vec![]), Some(vec![]), Some(None), 0),
+            (
+                Some(vec!["a".to_string()]),
+                Some(vec![dataset("d1")]),
+                Some(Some("a".to_string())),
+                1,
+            ),
+            (
+                Some(vec!["a".to_string(), "b".to_string()]),
+                Some(vec![dataset("d1"), dataset("d2")]),
+                None,
+                2,
+            ),
+        ];
+        for (keywords, datasets, note, expected) in shapes {
+            let project = Project {
+                id: "p".to_string(),
+                keywords,
+                datasets,
+                note,
+            };
+            assert_eq!(count(&project, "keywords"), expected);
+            assert_eq!(count(&project, "datasets"), expected);
+            assert_eq!(
+                count(&project, "note"),
+                usize::from(matches!(project.note, Some(Some(_))))
+            );
+        }
+    }
+
+    #[test]
+    fn test_individuals() {
+        #[derive(Serialize)]
+        struct PublicLicense;
+
+        #[derive(Serialize)]
+        struct UnknownLicense;
+
+        #[derive(Serialize)]
+        struct Dataset<L> {
+            id: String,
+            license: L,
+        }
+
+        let config = || SerializerConfig {
+            namespaces: HashMap::from([("ex".to_string(), "https://example.org/".to_string())]),
+            subjects: HashMap::from([(
+                "Dataset".to_string(),
+                SubjectConfig {
+                    struct_name: "Dataset".to_string(),
+                    rdf_type: "https://example.org/ns#Dataset".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "license".to_string(),
+                        rdf_property: "https://example.org/ns#hasLicense".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            individuals: HashMap::from([(
+                "PublicLicense".to_string(),
+                "ex:licenses/public".to_string(),
+            )]),
+            ..Default::default()
+        };
+
+        let mapped = Dataset {
+            id: "d".to_string(),
+            license: PublicLicense,
+        };
+        assert!(to_triples(&mapped, &config())
+            .unwrap()
+            .contains(&OwnedTriple {
+                subject: OwnedSubject::NamedNode("https://example.org/d".to_string()),
+                predicate: "https://example.org/ns#hasLicense".to_string(),
+                object: OwnedObject::NamedNode("https://example.org/licenses/public".to_string()),
+            }));
+
+        let unmapped = Dataset {
+            id: "d".to_string(),
+            license: UnknownLicense,
+        };
+        let error = to_string(&unmapped, &config()).unwrap_err();
+        assert!(error.to_string().contains("UnknownLicense"), "{error}");
+    }
+
+    #[test]
+    fn test_on_subject() {
+        #[derive(Serialize)]
+        struct Project {
+            id: String,
+            datasets: Vec<Dataset>,
+        }
+
+        #[derive(Serialize)]
+        struct Dataset {
+            id: String,
+        }
+
+        let subject = |name: &str, properties| SubjectConfig {
+            struct_name: name.to_string(),
+            rdf_type: format!("https://example.org/ns#{name}"),
+            identifier_field: "id".to_string(),
+            identifier_prefix: "https://example.org/".to_string(),
+            properties,
+            ..Default::default()
+        };
+        let config = SerializerConfig {
+            namespaces: HashMap::from([(
+                "prov".to_string(),
+                "http://www.w3.org/ns/prov#".to_string(),
+            )]),
+            subjects: HashMap::from([
+                (
+                    "Project".to_string(),
+                    subject(
+                        "Project",
+                        vec![PropertyConfig {
+                            struct_field: "datasets".to_string(),
+                            rdf_property: "https://example.org/ns#hasDataset".to_string(),
+                            ..Default::default()
+                        }],
+                    ),
+                ),
+                ("Dataset".to_string(), subject("Dataset", Vec::new())),
+            ]),
+            on_subject: Some(SubjectHook::new(|_, _| {
+                vec![(
+                    "prov:generatedAtTime".to_string(),
+                    OwnedLiteral::Typed {
+                        value: "2024-01-01T00:00:00Z".to_string(),
+                        datatype: "http://www.w3.org/2001/XMLSchema#dateTime".to_string(),
+                    },
+                )]
+            })),
+            ..Default::default()
+        };
+
+        let project = Project {
+            id: "p".to_string(),
+            datasets: vec![
+                Dataset {
+                    id: "d1".to_string(),
+                },
+                Dataset {
+                    id: "d2".to_string(),
+                },
+            ],
+        };
+        let triples = to_triples(&project, &config).unwrap();
+        let timestamps: Vec<_> = triples
+            .iter()
+            .filter(|t| t.predicate == "http://www.w3.org/ns/prov#generatedAtTime")
+            .map(|t| &t.subject)
+            .collect();
+        assert_eq!(
+            timestamps,
+            ["d1", "d2", "p"]
+                .map(|id| OwnedSubject::NamedNode(format!("https://example.org/{id}")))
+                .iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+            name: String,
+            shortcode: String,
+        }
+
+        let config = SerializerConfig {
+            namespaces: HashMap::from([
+                (
+                    "dsp".to_string(),
+                    "http://ns.dasch.swiss/repository#".to_string(),
+                ),
+                (
+                    "xsd".to_string(),
+                    "http://www.w3.org/2001/XMLSchema#".to_string(),
+                ),
+            ]),
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "http://ns.dasch.swiss/repository#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    properties: vec![
+                        PropertyConfig {
+                            struct_field: "name".to_string(),
+                            rdf_property: "http://ns.dasch.swiss/repository#hasName".to_string(),
+                            ..Default::default()
+                        },
+                        PropertyConfig {
+                            struct_field: "shortcode".to_string(),
+                            rdf_property: "http://ns.dasch.swiss/repository#has~Shortcode"
+                                .to_string(),
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+            )]),
+            compact: true,
+            ..Default::default()
+        };
+
+        let test = Test {
+            id: "my-id".to_string(),
+            name: "My Name".to_string(),
+            shortcode: "0001".to_string(),
+        };
+        let output = to_string(&test, &config).unwrap();
+        let expected = r#"@prefix dsp: <http://ns.dasch.swiss/repository#> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+<https://ark.dasch.swiss/ark:/72163/1/my-id> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> dsp:Test .
+<https://ark.dasch.swiss/ark:/72163/1/my-id> dsp:hasName "My Name"^^xsd:string .
+<https://ark.dasch.swiss/ark:/72163/1/my-id> <http://ns.dasch.swiss/repository#has~Shortcode> "0001"^^xsd:string .
+"#;
+        assert_eq!(output, expected);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    #[tracing_test::traced_test]
+    fn test_tracing_events() {
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+            name: String,
+            shortcode: String,
+        }
+
+        let config = SerializerConfig {
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "https://example.org/ns#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let test = Test {
+            id: "my-id".to_string(),
+            name: "My Name".to_string(),
+            shortcode: "0001".to_string(),
+        };
+        to_string(&test, &config).unwrap();
+
+        logs_assert(|lines| {
+            match lines
+                .iter()
+                .filter(|line| line.contains("serialize_field"))
+                .count()
+            {
+                3 => Ok(()),
+                n => Err(format!("expected 3 serialize_field events, found {n}")),
+            }
+        });
+        assert!(logs_contain("key=\"shortcode\""));
+        assert!(logs_contain("struct_name=\"Test\""));
+    }
+
+    #[test]
+    fn test_numeric_literals() {
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+            integers: Vec<i128>,
+            unsigned: u128,
+            doubles: Vec<f64>,
+            single: f32,
+        }
+
+        let config = || SerializerConfig {
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "https://example.org/ns#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: ["integers", "unsigned", "doubles", "single"]
+                        .map(|field| PropertyConfig {
+                            struct_field: field.to_string(),
+                            rdf_property: format!("https://example.org/ns#{field}"),
+                            ..Default::default()
+                        })
+                        .to_vec(),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let doubles = [
+            0.0,
+            -0.0,
+            1.5,
+            1e3,
+            1e16,
+            1e-7,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        let test = Test {
+            id: "n".to_string(),
+            integers: vec![0, -1, i64::MIN.into(), i128::MIN, i128::MAX],
+            unsigned: u128::MAX,
+            doubles: doubles.to_vec(),
+            single: 0.1,
+        };
+        let lexical = |test: &Test, field: &str, datatype: &str| -> Vec<String> {
+            let predicate = format!("https://example.org/ns#{field}");
+            to_triples(test, &config())
+                .unwrap()
+                .into_iter()
+                .filter(|triple| triple.predicate == predicate)
+                .map(|triple| match triple.object {
+                    OwnedObject::Literal(OwnedLiteral::Typed { value, datatype: d })
+                        if d == datatype =>
+                    {
+                        value
+                    }
+                    object => panic!("unexpected object {object:?}"),
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            lexical(&test, "integers", ns::XSD_INTEGER),
+            [
+                "0",
+                "-1",
+                "-9223372036854775808",
+                "-170141183460469231731687303715884105728",
+                "170141183460469231731687303715884105727",
+            ]
+        );
+        assert_eq!(
+            lexical(&test, "unsigned", ns::XSD_INTEGER),
+            ["340282366920938463463374607431768211455"]
+        );
+        let forms = lexical(&test, "doubles", ns::XSD_DOUBLE);
+        assert_eq!(
+            forms,
+            [
+                "0.0",
+                "-0.0",
+                "1.5",
+                "1000.0",
+                "1e16",
+                "1e-7",
+                "1.7976931348623157e308",
+                "2.2250738585072014e-308",
+                "5e-324",
+                "INF",
+                "-INF",
+            ]
+        );
+        // Every form is in the lexical space of `xsd:double` and reads back as the same value.
+        for (form, expected) in forms.iter().zip(doubles) {
+            let value: f64 = match form.as_str() {
+                "INF" => f64::INFINITY,
+                "-INF" => f64::NEG_INFINITY,
+                form => {
+                    assert!(form
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || "-.e".contains(c)));
+                    form.parse().unwrap()
+                }
+            };
+            assert_eq!(value.to_bits(), expected.to_bits(), "{form}");
+        }
+        assert_eq!(lexical(&test, "single", ns::XSD_DOUBLE), ["0.1"]);
+
+        let nan = Test {
+            id: "nan".to_string(),
+            integers: Vec::new(),
+            unsigned: 0,
+            doubles: vec![f64::NAN],
+            single: f32::NAN,
+        };
+        assert_eq!(lexical(&nan, "doubles", ns::XSD_DOUBLE), ["NaN"]);
+        assert_eq!(lexical(&nan, "single", ns::XSD_DOUBLE), ["NaN"]);
+    }
+
+    #[test]
+    fn test_to_writer() {
+        use crate::{to_writer, ErrorKind, Serializer};
+
+        #[derive(Serialize)]
+        struct Station {
+            id: String,
+            name: String,
+            elevation: f64,
+            readings: Vec<Reading>,
+        }
+
+        #[derive(Serialize)]
+        enum Reading {
+            Temperature(Measurement),
+            Rain { id: String, value: f64 },
+            Wind(f64, f64),
+        }
+
+        #[derive(Serialize)]
+        struct Measurement {
+            id: String,
+            value: f64,
+        }
+
+        struct Offline;
+
+        impl Serialize for Offline {
+            fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("station offline"))
+            }
+        }
+
+        let subject = |name: &str, fields: &[&str]| {
+            (
+                name.to_string(),
+                SubjectConfig {
+                    struct_name: name.to_string(),
+                    rdf_type: format!("https://example.org/ns#{name}"),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://example.org/".to_string(),
+                    properties: fields
+                        .iter()
+                        .map(|field| PropertyConfig {
+                            struct_field: field.to_string(),
+                            rdf_property: format!("https://example.org/ns#{field}"),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    ..Default::default()
+                },
+            )
+        };
+        let config = || SerializerConfig {
+            subjects: HashMap::from([
+                subject("Station", &["name", "elevation", "readings"]),
+                subject("Measurement", &["value"]),
+                subject("Rain", &["value"]),
+            ]),
+            ..Default::default()
+        };
+
+        let station = Station {
+            id: "bern".to_string(),
+            name: "Bern".to_string(),
+            elevation: 553.5,
+            readings: vec![
+                Reading::Temperature(Measurement {
+                    id: "t1".to_string(),
+                    value: f64::NAN,
+                }),
+                Reading::Rain {
+                    id: "r1".to_string(),
+                    value: f64::NEG_INFINITY,
+                },
+            ],
+        };
+        let mut buf = Vec::new();
+        to_writer(&mut buf, &station, &config()).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert_eq!(output, to_string(&station, &config()).unwrap());
+        for expected in [
+            r#"<https://example.org/ns#elevation> "553.5"^^<http://www.w3.org/2001/XMLSchema#double>"#,
+            r#"<https://example.org/t1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/ns#Measurement>"#,
+            r#"<https://example.org/ns#value> "NaN"^^<http://www.w3.org/2001/XMLSchema#double>"#,
+            r#"<https://example.org/r1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.org/ns#Rain>"#,
+            r#"<https://example.org/ns#value> "-INF"^^<http://www.w3.org/2001/XMLSchema#double>"#,
+        ] {
+            assert!(output.contains(expected), "{output}");
+        }
+
+        // The station is written before the value after it fails.
+        let mut buf = Vec::new();
+        let err = to_writer(&mut buf, &(&station, Offline), &config()).unwrap_err();
+        assert_eq!(err.to_string(), "station offline");
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.starts_with("<https://example.org/t1> "), "{output}");
+        assert!(output.contains("<https://example.org/bern> "), "{output}");
+
+        // Compact output is streamed after the prefixes as well.
+        let compact = SerializerConfig {
+            namespaces: HashMap::from([("ex".to_string(), "https://example.org/".to_string())]),
+            compact: true,
+            ..config()
+        };
+        let mut buf = Vec::new();
+        to_writer(&mut buf, &station, &compact).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        let mut serializer = Serializer::new(&compact).unwrap();
+        station.serialize(&mut serializer).unwrap();
+        assert_eq!(output, serializer.into_string().unwrap());
+        let mut buf = Vec::new();
+        to_writer(&mut buf, &(&station, Offline), &compact).unwrap_err();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(
+            output.starts_with("@prefix ex: <https://example.org/> .\nex:t1 "),
+            "{output}"
+        );
+        assert!(output.contains("ex:bern "), "{output}");
+
+        let wind = Station {
+            readings: vec![Reading::Wind(3.5, 270.0)],
+            ..station
+        };
+        let err = to_string(&wind, &config()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_to_async_writer() {
+        use tokio::io::AsyncReadExt;
+
+        use crate::{to_async_writer, to_async_writer_all, Graph};
+
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+            name: String,
+        }
+
+        let config = || SerializerConfig {
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "https://example.org/ns#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    properties: vec![PropertyConfig {
+                        struct_field: "name".to_string(),
+                        rdf_property: "https://example.org/ns#hasName".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let tests: Vec<Test> = (0..100)
+            .map(|n| Test {
+                id: format!("my-id-{n}"),
+                name: format!("My Name {n}"),
+            })
+            .collect();
+        // The buffer is smaller than the output of a single value, so every value is written
+        // while the reader runs.
+        let records = &tests;
+        let write = |config: SerializerConfig| async move {
+            let (writer, mut reader) = tokio::io::duplex(16);
+            let (written, read) =
+                tokio::join!(to_async_writer_all(writer, records, &config), async {
+                    let mut buf = Vec::new();
+                    reader.read_to_end(&mut buf).await.map(|_| buf)
+                });
+            written.unwrap();
+            String::from_utf8(read.unwrap()).unwrap()
+        };
+
+        let output = write(config()).await;
+        assert_eq!(
+            Graph::parse_turtle(&output).unwrap(),
+            Graph::parse_turtle(&to_string(&tests, &config()).unwrap()).unwrap()
+        );
+
+        let compact = SerializerConfig {
+            namespaces: HashMap::from([("ex".to_string(), "https://example.org/ns#".to_string())]),
+            compact: true,
+            ..config()
+        };
+        let output = write(compact.clone()).await;
+        assert_eq!(output.matches("@prefix ex:").count(), 1, "{output}");
+        assert_eq!(
+            Graph::parse_turtle(&output).unwrap(),
+            Graph::parse_turtle(&to_string(&tests, &compact).unwrap()).unwrap()
+        );
+
+        for config in [config(), compact] {
+            let mut output = Vec::new();
+            to_async_writer(&mut output, &tests[0], &config)
+                .await
+                .unwrap();
+            assert_eq!(
+                String::from_utf8(output).unwrap(),
+                to_string(&tests[0], &config).unwrap()
+            );
+        }
+    }
+
+    #[cfg(feature = "rdf-star")]
+    #[test]
+    fn test_annotated_property() {
+        use crate::Annotated;
+
+        #[derive(Serialize)]
+        struct Test {
+            id: String,
+            name: Annotated<String>,
+            shortcode: String,
+        }
+
+        let config = SerializerConfig {
+            base_iri: "".to_string(),
+            namespaces: Default::default(),
+            subjects: HashMap::from([(
+                "Test".to_string(),
+                SubjectConfig {
+                    struct_name: "Test".to_string(),
+                    rdf_type: "https://example.org/ns#Test".to_string(),
+                    identifier_field: "id".to_string(),
+                    identifier_prefix: "https://ark.dasch.swiss/ark:/72163/1/".to_string(),
+                    properties: vec![
+                        PropertyConfig {
+                            struct_field: "name".to_string(),
+                            rdf_property: "https://example.org/ns#hasName".to_string(),
+                            ..Default::default()
+                        },
+                        PropertyConfig {
+                            struct_field: "shortcode".to_string(),
+                            rdf_property: "https://example.org/ns#hasShortcode".to_string(),
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let test = Test {
+            id: "my-id".to_string(),
+            name: Annotated {
+                value: "My Name".to_string(),
+                annotations: HashMap::from([(
+                    "https://example.org/ns#confidence".to_string(),
+                    "0.9".to_string(),
+                )]),
+            },
+            shortcode: "0001".to_string(),
+        };
+        let output = to_string(&test, &config).unwrap();
+
+        let quoted = "<< <https://ark.dasch.swiss/ark:/72163/1/my-id> <https://example.org/ns#hasName> \"My Name\"^^<http://www.w3.org/2001/XMLSchema#string> >> <https://example.org/ns#confidence> \"0.9\"^^<http://www.w3.org/2001/XMLSchema#string>";
+        assert!(output.contains(quoted), "{output}");
+        assert!(output.contains("<https://example.org/ns#hasShortcode> \"0001\"^^<http://www.w3.org/2001/XMLSchema#string>"), "{output}");
+        assert!(!output.contains("<< <https://ark.dasch.swiss/ark:/72163/1/my-id> <https://example.org/ns#hasShortcode>"), "{output}");
     }
 }
@@ -0,0 +1,449 @@
+//! Translate between a [`SerializerConfig`] and [SHACL](https://www.w3.org/TR/shacl/) shapes.
+
+use rio_api::parser::TriplesParser;
+use rio_turtle::{TurtleError, TurtleParser};
+
+use crate::error::Result;
+use crate::ns::{RDF_TYPE, SH, XSD, XSD_INTEGER};
+use crate::ontology::{local_name, snake_case};
+use crate::structure::{PropertyConfig, SerializerConfig, SubjectConfig, TermKind};
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+use crate::turtle::Compact;
+
+/// The shapes of the subjects of the config as Turtle: a `sh:NodeShape` per subject, ordered by
+/// struct name, with a `sh:property` per property.
+pub(crate) fn to_shacl(config: &SerializerConfig) -> Result<String> {
+    // The groups and prefixed names are resolved on a copy, as when serializing.
+    let mut resolved = SerializerConfig {
+        namespaces: config.namespaces.clone(),
+        subjects: config.subjects.clone(),
+        default_identifier_prefix: config.default_identifier_prefix.clone(),
+        property_groups: config.property_groups.clone(),
+        ..Default::default()
+    };
+    resolved.include_property_groups()?;
+    resolved.expand_prefixed_names()?;
+
+    let sh = |local: &str| format!("{SH}{local}");
+    let mut triples = Vec::new();
+    let mut push = |subject: &OwnedSubject, predicate: String, object: OwnedObject| {
+        triples.push(OwnedTriple {
+            subject: subject.clone(),
+            predicate,
+            object,
+        });
+    };
+    let iri = |iri: &str| OwnedObject::NamedNode(iri.to_string());
+    let string = |value: &str| {
+        OwnedObject::Literal(OwnedLiteral::Simple {
+            value: value.to_string(),
+        })
+    };
+    let count = |count: u32| {
+        OwnedObject::Literal(OwnedLiteral::Typed {
+            value: count.to_string(),
+            datatype: XSD_INTEGER.to_string(),
+        })
+    };
+
+    let mut subjects: Vec<_> = resolved.subjects.values().collect();
+    subjects.sort_by(|a, b| a.struct_name.cmp(&b.struct_name));
+    for subject in subjects {
+        let shape = OwnedSubject::BlankNode(format!("{}Shape", subject.struct_name));
+        push(&shape, RDF_TYPE.to_string(), iri(&sh("NodeShape")));
+        push(&shape, sh("name"), string(&subject.struct_name));
+        push(&shape, sh("targetClass"), iri(&subject.rdf_type));
+        // Subjects without an identifier field are written as blank nodes.
+        let node_kind = match subject.identifier_field.as_str() {
+            "" => "BlankNode",
+            _ => "IRI",
+        };
+        push(&shape, sh("nodeKind"), iri(&sh(node_kind)));
+        // The IRIs of the subjects start with the identifier prefix.
+        if !subject.identifier_field.is_empty() && !subject.identifier_prefix.is_empty() {
+            let pattern = format!("^{}", escape_pattern(&subject.identifier_prefix));
+            push(&shape, sh("pattern"), string(&pattern));
+        }
+        for property in &subject.properties {
+            let id = format!("{}Shape_{}", subject.struct_name, property.struct_field);
+            push(&shape, sh("property"), OwnedObject::BlankNode(id.clone()));
+            let node = OwnedSubject::BlankNode(id.clone());
+            if property.inverse {
+                let path = OwnedSubject::BlankNode(format!("{id}_path"));
+                push(
+                    &node,
+                    sh("path"),
+                    OwnedObject::BlankNode(format!("{id}_path")),
+                );
+                push(&path, sh("inversePath"), iri(&property.rdf_property));
+            } else {
+                push(&node, sh("path"), iri(&property.rdf_property));
+            }
+            push(&node, sh("name"), string(&property.struct_field));
+            if let Some(datatype) = &property.datatype {
+                push(&node, sh("datatype"), iri(datatype.as_iri()));
+            }
+            if let Some(kind) = property.kind {
+                let node_kind = match kind {
+                    TermKind::Literal => "Literal",
+                    TermKind::Subject => "BlankNodeOrIRI",
+                };
+                push(&node, sh("nodeKind"), iri(&sh(node_kind)));
+            }
+            if let Some(min_count) = property.min_count {
+                push(&node, sh("minCount"), count(min_count));
+            }
+            if let Some(max_count) = property.max_count {
+                push(&node, sh("maxCount"), count(max_count));
+            }
+        }
+    }
+
+    let mut namespaces = resolved.namespaces;
+    for (prefix, namespace) in [("sh", SH), ("xsd", XSD)] {
+        namespaces
+            .entry(prefix.to_string())
+            .or_insert_with(|| namespace.to_string());
+    }
+    Ok(Compact::new(&triples, &namespaces).to_string())
+}
+
+/// A config with a subject per node shape with a `sh:targetClass`, and the unsupported SHACL
+/// features that were ignored.
+pub(crate) fn from_shacl(ttl: &str) -> Result<(SerializerConfig, Vec<String>)> {
+    let mut triples = Vec::new();
+    TurtleParser::new(ttl.as_bytes(), None).parse_all(&mut |triple| -> std::result::Result<
+        (),
+        TurtleError,
+    > {
+        triples.push(OwnedTriple::from(&triple));
+        Ok(())
+    })?;
+    let statements = |subject: &OwnedSubject| {
+        triples
+            .iter()
+            .filter(|triple| triple.subject == *subject)
+            .map(|triple| (triple.predicate.as_str(), &triple.object))
+            .collect::<Vec<_>>()
+    };
+    let label = |subject: &OwnedSubject| match subject {
+        OwnedSubject::NamedNode(iri) => format!("<{iri}>"),
+        OwnedSubject::BlankNode(id) => format!("_:{id}"),
+        OwnedSubject::Triple(triple) => format!("{triple:?}"),
+    };
+
+    let mut shapes: Vec<&OwnedSubject> = Vec::new();
+    for triple in &triples {
+        let is_shape = (triple.predicate == RDF_TYPE
+            && triple.object == OwnedObject::NamedNode(format!("{SH}NodeShape")))
+            || triple.predicate == format!("{SH}targetClass");
+        if is_shape && !shapes.contains(&&triple.subject) {
+            shapes.push(&triple.subject);
+        }
+    }
+
+    let mut config = SerializerConfig::default();
+    let mut warnings = Vec::new();
+    for shape in shapes {
+        let mut name = None;
+        let mut target_class = None;
+        let mut node_kind = None;
+        let mut identifier_prefix = String::new();
+        let mut property_shapes = Vec::new();
+        for (predicate, object) in statements(shape) {
+            match (predicate.strip_prefix(SH), object) {
+                (Some("name"), OwnedObject::Literal(literal)) => {
+                    name = Some(literal.lexical_form().to_string());
+                }
+                (Some("targetClass"), OwnedObject::NamedNode(class)) => {
+                    target_class = Some(class.clone());
+                }
+                (Some("nodeKind"), OwnedObject::NamedNode(kind)) => node_kind = Some(kind.clone()),
+                (Some("pattern"), OwnedObject::Literal(literal)) => {
+                    match unescape_prefix(literal.lexical_form()) {
+                        Some(prefix) => identifier_prefix = prefix,
+                        None => warnings.push(format!(
+                            "sh:pattern of node shape {} ignored, it does not match an \
+                             identifier prefix: {}",
+                            label(shape),
+                            literal.lexical_form()
+                        )),
+                    }
+                }
+                (Some("property"), OwnedObject::NamedNode(iri)) => {
+                    property_shapes.push(OwnedSubject::NamedNode(iri.clone()));
+                }
+                (Some("property"), OwnedObject::BlankNode(id)) => {
+                    property_shapes.push(OwnedSubject::BlankNode(id.clone()));
+                }
+                _ if predicate == RDF_TYPE => {}
+                _ => warnings.push(format!(
+                    "unsupported <{predicate}> of node shape {} ignored",
+                    label(shape)
+                )),
+            }
+        }
+        let Some(target_class) = target_class else {
+            warnings.push(format!(
+                "node shape {} without sh:targetClass ignored",
+                label(shape)
+            ));
+            continue;
+        };
+        let struct_name = name.unwrap_or_else(|| local_name(&target_class).to_string());
+
+        let mut properties = Vec::new();
+        for node in &property_shapes {
+            let mut property = PropertyConfig::default();
+            let mut name = None;
+            for (predicate, object) in statements(node) {
+                let count = || match object {
+                    OwnedObject::Literal(literal) => literal.lexical_form().parse().ok(),
+                    _ => None,
+                };
+                match (predicate.strip_prefix(SH), object) {
+                    (Some("path"), OwnedObject::NamedNode(path)) => {
+                        property.rdf_property = path.clone();
+                    }
+                    (Some("path"), OwnedObject::BlankNode(id)) => {
+                        let inverse = statements(&OwnedSubject::BlankNode(id.clone()))
+                            .into_iter()
+                            .find(|(predicate, _)| *predicate == format!("{SH}inversePath"))
+                            .map(|(_, object)| object);
+                        if let Some(OwnedObject::NamedNode(path)) = inverse {
+                            property.rdf_property = path.clone();
+                            property.inverse = true;
+                        }
+                    }
+                    // Other paths, e.g. sequence paths, are reported below.
+                    (Some("path"), _) => {}
+                    (Some("name"), OwnedObject::Literal(literal)) => {
+                        name = Some(literal.lexical_form().to_string());
+                    }
+                    (Some("datatype"), OwnedObject::NamedNode(datatype)) => {
+                        property.datatype = datatype.parse().ok();
+                    }
+                    (Some("nodeKind"), OwnedObject::NamedNode(kind)) => {
+                        property.kind = match kind.strip_prefix(SH) {
+                            Some("Literal") => Some(TermKind::Literal),
+                            Some("IRI" | "BlankNode" | "BlankNodeOrIRI") => Some(TermKind::Subject),
+                            _ => None,
+                        };
+                    }
+                    (Some("minCount"), _) if count().is_some() => property.min_count = count(),
+                    (Some("maxCount"), _) if count().is_some() => property.max_count = count(),
+                    _ if predicate == RDF_TYPE => {}
+                    _ => warnings.push(format!(
+                        "unsupported <{predicate}> of property shape {} of {struct_name} ignored",
+                        label(node)
+                    )),
+                }
+            }
+            if property.rdf_property.is_empty() {
+                warnings.push(format!(
+                    "property shape {} of {struct_name} without an IRI or inverse path as \
+                     sh:path ignored",
+                    label(node)
+                ));
+                continue;
+            }
+            property.struct_field =
+                name.unwrap_or_else(|| snake_case(local_name(&property.rdf_property)));
+            properties.push(property);
+        }
+
+        // Subjects without an identifier are blank nodes, which only an IRI node kind rules
+        // out. Their IRIs are made from the identifier prefix, so it has to be known.
+        let iri_kind = node_kind.as_deref() == Some(&*format!("{SH}IRI"));
+        let identifier_field = match (iri_kind, identifier_prefix.is_empty()) {
+            (true, false) => "id".to_string(),
+            (true, true) => {
+                warnings.push(format!(
+                    "node shape {} without a sh:pattern of the identifier prefix, its \
+                     subjects are blank nodes",
+                    label(shape)
+                ));
+                String::new()
+            }
+            (false, _) => {
+                identifier_prefix.clear();
+                String::new()
+            }
+        };
+        config.subjects.insert(
+            struct_name.clone(),
+            SubjectConfig {
+                struct_name,
+                rdf_type: target_class,
+                identifier_field,
+                identifier_prefix,
+                properties,
+                ..Default::default()
+            },
+        );
+    }
+    config.validate()?;
+    Ok((config, warnings))
+}
+
+/// The characters with a meaning in a SHACL pattern, a regular expression.
+const PATTERN_SPECIAL: &str = "\\.+*?()|[]{}^$";
+
+/// Escape the characters of `value` that have a meaning in a pattern.
+fn escape_pattern(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 8);
+    for c in value.chars() {
+        if PATTERN_SPECIAL.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// The prefix matched by a pattern of the form `^prefix`, if that is all it matches.
+fn unescape_prefix(pattern: &str) -> Option<String> {
+    let mut prefix = String::with_capacity(pattern.len());
+    let mut chars = pattern.strip_prefix('^')?.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => prefix.push(chars.next().filter(|c| PATTERN_SPECIAL.contains(*c))?),
+            c if PATTERN_SPECIAL.contains(c) => return None,
+            c => prefix.push(c),
+        }
+    }
+    Some(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use rio_api::parser::TriplesParser;
+    use rio_turtle::{TurtleError, TurtleParser};
+
+    use crate::{
+        ns, OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple, SerializerConfig, SubjectConfig,
+        TermKind, XsdType,
+    };
+
+    #[test]
+    fn test_to_shacl() {
+        let mut config = SerializerConfig::builder()
+            .prefix("dsp", "https://ns.dasch.swiss/repository#")
+            .subject(
+                SubjectConfig::builder("Project")
+                    .rdf_type("dsp:Project")
+                    .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                    .property("shortcode", "dsp:hasShortcode")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let shortcode = &mut config.subjects.get_mut("Project").unwrap().properties[0];
+        shortcode.datatype = Some(XsdType::String);
+        shortcode.min_count = Some(1);
+        shortcode.max_count = Some(1);
+
+        let shapes = config.to_shacl().unwrap();
+        assert!(
+            shapes.contains("@prefix sh: <http://www.w3.org/ns/shacl#> ."),
+            "{shapes}"
+        );
+        let mut triples = Vec::new();
+        TurtleParser::new(shapes.as_bytes(), None)
+            .parse_all(&mut |triple| -> Result<(), TurtleError> {
+                triples.push(OwnedTriple::from(&triple));
+                Ok(())
+            })
+            .unwrap();
+        let sh = |local: &str| format!("{}{local}", ns::SH);
+        let objects = |subject: &str, predicate: &str| -> Vec<OwnedObject> {
+            triples
+                .iter()
+                .filter(|t| {
+                    t.subject == OwnedSubject::BlankNode(subject.to_string())
+                        && t.predicate == sh(predicate)
+                })
+                .map(|t| t.object.clone())
+                .collect()
+        };
+        let iri = |iri: &str| OwnedObject::NamedNode(iri.to_string());
+        let node = "ProjectShape";
+        assert_eq!(
+            objects(node, "targetClass"),
+            [iri("https://ns.dasch.swiss/repository#Project")]
+        );
+        let property = "ProjectShape_shortcode";
+        assert_eq!(
+            objects(node, "property"),
+            [OwnedObject::BlankNode(property.to_string())]
+        );
+        assert_eq!(
+            objects(property, "path"),
+            [iri("https://ns.dasch.swiss/repository#hasShortcode")]
+        );
+        assert_eq!(objects(property, "datatype"), [iri(ns::XSD_STRING)]);
+        assert_eq!(
+            objects(property, "minCount"),
+            [OwnedObject::Literal(OwnedLiteral::Typed {
+                value: "1".to_string(),
+                datatype: ns::XSD_INTEGER.to_string(),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_from_shacl() {
+        let mut config = SerializerConfig::builder()
+            .prefix("dsp", "https://ns.dasch.swiss/repository#")
+            .subject(
+                SubjectConfig::builder("Project")
+                    .rdf_type("dsp:Project")
+                    .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                    .property("shortcode", "dsp:hasShortcode")
+                    .property("lead", "dsp:hasLead")
+                    .build(),
+            )
+            .subject(
+                SubjectConfig::builder("Person")
+                    .rdf_type("dsp:Person")
+                    .property("name", "dsp:hasName")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let project = config.subjects.get_mut("Project").unwrap();
+        project.properties[0].datatype = Some(XsdType::String);
+        project.properties[0].kind = Some(TermKind::Literal);
+        project.properties[0].min_count = Some(1);
+        project.properties[0].max_count = Some(1);
+        project.properties[1].kind = Some(TermKind::Subject);
+
+        let (parsed, warnings) = SerializerConfig::from_shacl(&config.to_shacl().unwrap()).unwrap();
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(parsed.subjects, config.subjects);
+
+        // Names are taken from the local names without `sh:name`, other features are ignored.
+        let shapes = r#"
+            @prefix sh: <http://www.w3.org/ns/shacl#> .
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            dsp:ProjectShape a sh:NodeShape ;
+                sh:targetClass dsp:Project ;
+                sh:closed true ;
+                sh:property [ sh:path dsp:hasShortcode ; sh:pattern "^[0-9A-F]{4}$" ] ;
+                sh:property [ sh:path ( dsp:hasLead dsp:hasName ) ] .
+        "#;
+        let (parsed, warnings) = SerializerConfig::from_shacl(shapes).unwrap();
+        let project = &parsed.subjects["Project"];
+        assert_eq!(
+            project.rdf_type,
+            "https://ns.dasch.swiss/repository#Project"
+        );
+        assert_eq!(project.identifier_field, "");
+        assert_eq!(project.properties.len(), 1);
+        assert_eq!(project.properties[0].struct_field, "has_shortcode");
+        assert_eq!(warnings.len(), 3, "{warnings:?}");
+        assert!(warnings[0].contains("closed"), "{warnings:?}");
+        assert!(warnings[1].contains("pattern"), "{warnings:?}");
+        assert!(warnings[2].contains("sh:path"), "{warnings:?}");
+    }
+}
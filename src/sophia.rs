@@ -0,0 +1,237 @@
+//! Implement the graph traits of [sophia](https://docs.rs/sophia_api) for [`Graph`], and
+//! convert between the owned terms of this crate and sophia's [`SimpleTerm`].
+
+use std::convert::Infallible;
+
+use sophia_api::graph::{GResult, Graph as SophiaGraph, MgResult, MutableGraph};
+use sophia_api::term::{BnodeId, IriRef, LanguageTag, SimpleTerm, Term};
+
+use crate::error::{Error, Result};
+use crate::graph::Graph;
+use crate::ns::XSD_STRING;
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+
+impl SophiaGraph for Graph {
+    type Triple<'x>
+        = [SimpleTerm<'x>; 3]
+    where
+        Self: 'x;
+    type Error = Infallible;
+
+    fn triples(&self) -> impl Iterator<Item = GResult<Self, Self::Triple<'_>>> + '_ {
+        Graph::triples(self).iter().map(|triple| Ok(spo(triple)))
+    }
+}
+
+/// Triples are inserted and removed with terms of any sophia implementation. Variables, and
+/// predicates that are not IRIs, are rejected.
+impl MutableGraph for Graph {
+    type MutationError = Error;
+
+    fn insert<TS, TP, TO>(&mut self, s: TS, p: TP, o: TO) -> MgResult<Self, bool>
+    where
+        TS: Term,
+        TP: Term,
+        TO: Term,
+    {
+        Ok(Graph::insert(self, triple(s, p, o)?))
+    }
+
+    fn remove<TS, TP, TO>(&mut self, s: TS, p: TP, o: TO) -> MgResult<Self, bool>
+    where
+        TS: Term,
+        TP: Term,
+        TO: Term,
+    {
+        Ok(Graph::remove(self, &triple(s, p, o)?))
+    }
+}
+
+fn spo(triple: &OwnedTriple) -> [SimpleTerm<'_>; 3] {
+    [
+        SimpleTerm::from(&triple.subject),
+        iri(&triple.predicate),
+        SimpleTerm::from(&triple.object),
+    ]
+}
+
+fn triple(s: impl Term, p: impl Term, o: impl Term) -> Result<OwnedTriple> {
+    Ok(OwnedTriple {
+        subject: OwnedSubject::try_from(s.into_term::<SimpleTerm>())?,
+        predicate: predicate(p.into_term())?,
+        object: OwnedObject::try_from(o.into_term::<SimpleTerm>())?,
+    })
+}
+
+fn iri(iri: &str) -> SimpleTerm<'_> {
+    SimpleTerm::Iri(IriRef::new_unchecked(iri.into()))
+}
+
+fn predicate(term: SimpleTerm<'_>) -> Result<String> {
+    match term {
+        SimpleTerm::Iri(iri) => Ok(iri.as_str().to_string()),
+        term => Err(unsupported("predicate", &term)),
+    }
+}
+
+fn unsupported(position: &str, term: &SimpleTerm<'_>) -> Error {
+    Error::Message(format!(
+        "sophia -> unsupported term as {position}: {term:?}"
+    ))
+}
+
+impl<'a> From<&'a OwnedSubject> for SimpleTerm<'a> {
+    fn from(subject: &'a OwnedSubject) -> Self {
+        match subject {
+            OwnedSubject::NamedNode(iri) => self::iri(iri),
+            OwnedSubject::BlankNode(id) => {
+                SimpleTerm::BlankNode(BnodeId::new_unchecked(id.as_str().into()))
+            }
+            OwnedSubject::Triple(triple) => SimpleTerm::Triple(Box::new(spo(triple))),
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedObject> for SimpleTerm<'a> {
+    fn from(object: &'a OwnedObject) -> Self {
+        match object {
+            OwnedObject::NamedNode(iri) => self::iri(iri),
+            OwnedObject::BlankNode(id) => {
+                SimpleTerm::BlankNode(BnodeId::new_unchecked(id.as_str().into()))
+            }
+            OwnedObject::Literal(literal) => SimpleTerm::from(literal),
+            OwnedObject::Triple(triple) => SimpleTerm::Triple(Box::new(spo(triple))),
+        }
+    }
+}
+
+/// Simple literals become `xsd:string` literals, as sophia has no term without datatype.
+impl<'a> From<&'a OwnedLiteral> for SimpleTerm<'a> {
+    fn from(literal: &'a OwnedLiteral) -> Self {
+        match literal {
+            OwnedLiteral::Simple { value } => SimpleTerm::LiteralDatatype(
+                value.as_str().into(),
+                IriRef::new_unchecked(XSD_STRING.into()),
+            ),
+            OwnedLiteral::LanguageTaggedString { value, language } => SimpleTerm::LiteralLanguage(
+                value.as_str().into(),
+                LanguageTag::new_unchecked(language.as_str().into()),
+            ),
+            OwnedLiteral::Typed { value, datatype } => SimpleTerm::LiteralDatatype(
+                value.as_str().into(),
+                IriRef::new_unchecked(datatype.as_str().into()),
+            ),
+        }
+    }
+}
+
+/// Fails for literals and variables.
+impl TryFrom<SimpleTerm<'_>> for OwnedSubject {
+    type Error = Error;
+
+    fn try_from(term: SimpleTerm<'_>) -> Result<Self> {
+        match term {
+            SimpleTerm::Iri(iri) => Ok(OwnedSubject::NamedNode(iri.as_str().to_string())),
+            SimpleTerm::BlankNode(id) => Ok(OwnedSubject::BlankNode(id.as_str().to_string())),
+            SimpleTerm::Triple(spo) => Ok(OwnedSubject::Triple(Box::new(quoted(*spo)?))),
+            term => Err(unsupported("subject", &term)),
+        }
+    }
+}
+
+/// Fails for variables. `xsd:string` literals become simple literals.
+impl TryFrom<SimpleTerm<'_>> for OwnedObject {
+    type Error = Error;
+
+    fn try_from(term: SimpleTerm<'_>) -> Result<Self> {
+        match term {
+            SimpleTerm::Iri(iri) => Ok(OwnedObject::NamedNode(iri.as_str().to_string())),
+            SimpleTerm::BlankNode(id) => Ok(OwnedObject::BlankNode(id.as_str().to_string())),
+            SimpleTerm::LiteralDatatype(value, datatype) if datatype.as_str() == XSD_STRING => {
+                Ok(OwnedObject::Literal(OwnedLiteral::Simple {
+                    value: value.to_string(),
+                }))
+            }
+            SimpleTerm::LiteralDatatype(value, datatype) => {
+                Ok(OwnedObject::Literal(OwnedLiteral::Typed {
+                    value: value.to_string(),
+                    datatype: datatype.as_str().to_string(),
+                }))
+            }
+            SimpleTerm::LiteralLanguage(value, language) => {
+                Ok(OwnedObject::Literal(OwnedLiteral::LanguageTaggedString {
+                    value: value.to_string(),
+                    language: language.as_str().to_string(),
+                }))
+            }
+            SimpleTerm::Triple(spo) => Ok(OwnedObject::Triple(Box::new(quoted(*spo)?))),
+            term => Err(unsupported("object", &term)),
+        }
+    }
+}
+
+fn quoted([s, p, o]: [SimpleTerm<'_>; 3]) -> Result<OwnedTriple> {
+    Ok(OwnedTriple {
+        subject: OwnedSubject::try_from(s)?,
+        predicate: predicate(p)?,
+        object: OwnedObject::try_from(o)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use sophia_api::graph::{Graph as _, MutableGraph};
+    use sophia_api::term::{IriRef, SimpleTerm, Term};
+
+    use crate::{Graph, OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+
+    #[test]
+    fn test_sophia_graph() {
+        let p = IriRef::new_unchecked("https://example.org/p");
+        let name = IriRef::new_unchecked("https://example.org/ns#name");
+        let mut graph = Graph::new();
+        assert!(MutableGraph::insert(&mut graph, &p, &name, "Events").unwrap());
+        assert!(!MutableGraph::insert(&mut graph, &p, &name, "Events").unwrap());
+        assert_eq!(
+            graph.triples(),
+            [OwnedTriple {
+                subject: OwnedSubject::NamedNode("https://example.org/p".to_string()),
+                predicate: "https://example.org/ns#name".to_string(),
+                object: OwnedObject::Literal(OwnedLiteral::Simple {
+                    value: "Events".to_string()
+                }),
+            }]
+        );
+        // Literals are not predicates.
+        assert!(MutableGraph::insert(&mut graph, &p, "name", "Events").is_err());
+        assert!(MutableGraph::remove(&mut graph, &p, &name, "Events").unwrap());
+        assert!(graph.is_empty());
+
+        let graph = Graph::parse_turtle(
+            r#"<https://example.org/p> <https://example.org/ns#title> "Events"@en ;
+                <https://example.org/ns#address> _:a .
+                _:a <https://example.org/ns#street> "Main Street" ."#,
+        )
+        .unwrap();
+        let triples: Vec<_> = sophia_api::graph::Graph::triples(&graph)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(triples.len(), 3);
+        let [s, p, o] = &triples[0];
+        assert_eq!(s.iri().unwrap().as_str(), "https://example.org/p");
+        assert_eq!(p.iri().unwrap().as_str(), "https://example.org/ns#title");
+        assert_eq!(o.language_tag().unwrap().as_str(), "en");
+        assert!(sophia_api::graph::Graph::contains(&graph, s, p, o).unwrap());
+        assert_eq!(triples[2][0].bnode_id().unwrap().as_str(), "a");
+
+        // Single terms convert both ways.
+        for object in graph.triples().iter().map(|triple| &triple.object) {
+            assert_eq!(
+                &OwnedObject::try_from(SimpleTerm::from(object)).unwrap(),
+                object
+            );
+        }
+        let variable = SimpleTerm::Variable(sophia_api::term::VarName::new_unchecked("x".into()));
+        assert!(OwnedObject::try_from(variable).is_err());
+    }
+}
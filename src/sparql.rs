@@ -0,0 +1,205 @@
+//! Read the solutions of a SPARQL SELECT query in the
+//! [JSON results format](https://www.w3.org/TR/sparql11-results-json/) as subjects, one per
+//! solution, with a property per bound variable.
+
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::ns::XSD_STRING;
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+
+/// The namespace of the predicates standing for the variables. Their local name is the name of
+/// the variable, which is matched with the struct fields like any other predicate.
+const VARIABLE_NAMESPACE: &str = "urn:x-serde-rdf:sparql-variable#";
+
+/// The variable identifying the subject of a solution, which populates the `id` field.
+const ID_VARIABLE: &str = "id";
+
+/// The subject of each solution with its triples. The subject is the binding of the variable
+/// `id` if it is bound, so that `id` fields receive it, and a blank node otherwise.
+pub(crate) fn solutions(input: &str) -> Result<Vec<(OwnedObject, Vec<OwnedTriple>)>> {
+    let document: Value = serde_json::from_str(input).map_err(Error::new)?;
+    let Some(Value::Array(bindings)) = document.pointer("/results/bindings") else {
+        return Err(error("expected results with bindings", &document));
+    };
+    let mut solutions = Vec::with_capacity(bindings.len());
+    for (n, solution) in bindings.iter().enumerate() {
+        let Value::Object(solution) = solution else {
+            return Err(error("expected an object of bindings", solution));
+        };
+        let subject = match solution.get(ID_VARIABLE).map(term).transpose()? {
+            Some(OwnedObject::NamedNode(iri)) => OwnedSubject::NamedNode(iri),
+            Some(OwnedObject::BlankNode(id)) => OwnedSubject::BlankNode(id),
+            // A literal identifier is handed to the `id` field as it is.
+            Some(OwnedObject::Literal(literal)) => {
+                OwnedSubject::NamedNode(literal.lexical_form().to_string())
+            }
+            Some(OwnedObject::Triple(triple)) => OwnedSubject::Triple(triple),
+            None => OwnedSubject::BlankNode(format!("solution{n}")),
+        };
+        let mut triples = Vec::with_capacity(solution.len());
+        for (variable, value) in solution {
+            if variable == ID_VARIABLE {
+                continue;
+            }
+            triples.push(OwnedTriple {
+                subject: subject.clone(),
+                predicate: format!("{VARIABLE_NAMESPACE}{variable}"),
+                object: term(value)?,
+            });
+        }
+        let subject = match subject {
+            OwnedSubject::NamedNode(iri) => OwnedObject::NamedNode(iri),
+            OwnedSubject::BlankNode(id) => OwnedObject::BlankNode(id),
+            OwnedSubject::Triple(triple) => OwnedObject::Triple(triple),
+        };
+        solutions.push((subject, triples));
+    }
+    Ok(solutions)
+}
+
+fn error(message: &str, value: &Value) -> Error {
+    Error::Message(format!(
+        "deserialize_sparql_json -> {message}, found: {value}"
+    ))
+}
+
+/// The RDF term of a binding, e.g. `{"type": "uri", "value": "https://example.org/p"}`.
+fn term(binding: &Value) -> Result<OwnedObject> {
+    let Value::Object(binding) = binding else {
+        return Err(error("expected a binding object", binding));
+    };
+    let string = |key: &str| match binding.get(key) {
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(value) => Err(error(&format!("expected a string as {key}"), value)),
+        None => Ok(None),
+    };
+    let value = string("value")?;
+    let kind = string("type")?;
+    match (kind.as_deref(), value) {
+        (Some("uri"), Some(iri)) => Ok(OwnedObject::NamedNode(iri)),
+        (Some("bnode"), Some(id)) => Ok(OwnedObject::BlankNode(id)),
+        // `typed-literal` is the type of typed literals in an older version of the format.
+        (Some("literal" | "typed-literal"), Some(value)) => {
+            let literal = match (string("xml:lang")?, string("datatype")?) {
+                (Some(language), _) => OwnedLiteral::LanguageTaggedString { value, language },
+                (None, Some(datatype)) if datatype != XSD_STRING => {
+                    OwnedLiteral::Typed { value, datatype }
+                }
+                (None, _) => OwnedLiteral::Simple { value },
+            };
+            Ok(OwnedObject::Literal(literal))
+        }
+        (Some("triple"), _) => Ok(OwnedObject::Triple(Box::new(quoted(binding)?))),
+        _ => Err(error(
+            "expected a uri, literal, bnode or triple binding with a value",
+            &Value::Object(binding.clone()),
+        )),
+    }
+}
+
+/// The triple of a SPARQL-star `triple` binding, whose value holds the bindings of its subject,
+/// predicate and object.
+fn quoted(binding: &Map<String, Value>) -> Result<OwnedTriple> {
+    let value = binding.get("value").unwrap_or(&Value::Null);
+    let part = |key: &str| {
+        value
+            .get(key)
+            .ok_or_else(|| error(&format!("expected a {key} in the triple"), value))
+            .and_then(term)
+    };
+    let subject = match part("subject")? {
+        OwnedObject::NamedNode(iri) => OwnedSubject::NamedNode(iri),
+        OwnedObject::BlankNode(id) => OwnedSubject::BlankNode(id),
+        OwnedObject::Triple(triple) => OwnedSubject::Triple(triple),
+        OwnedObject::Literal(_) => return Err(error("expected a subject", value)),
+    };
+    let OwnedObject::NamedNode(predicate) = part("predicate")? else {
+        return Err(error("expected an IRI as predicate", value));
+    };
+    Ok(OwnedTriple {
+        subject,
+        predicate,
+        object: part("object")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::from_sparql_json;
+
+    #[test]
+    fn test_from_sparql_json() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row {
+            id: String,
+            name: String,
+            size: u32,
+            founded: String,
+            description: String,
+            homepage: Option<String>,
+            public: Option<bool>,
+        }
+
+        let input = r#"{
+            "head": {"vars": ["id", "name", "size", "founded", "description", "homepage", "public"]},
+            "results": {"bindings": [
+                {
+                    "id": {"type": "uri", "value": "https://ark.dasch.swiss/ark:/72163/1/081C"},
+                    "name": {"type": "literal", "value": "Hôtel de Musique Bern"},
+                    "size": {"type": "literal", "value": "42", "datatype": "http://www.w3.org/2001/XMLSchema#integer"},
+                    "founded": {"type": "literal", "value": "1767-01-01", "datatype": "http://www.w3.org/2001/XMLSchema#date"},
+                    "description": {"type": "literal", "value": "The Hôtel de Musique", "xml:lang": "en"},
+                    "homepage": {"type": "uri", "value": "https://www.hoteldemusique.ch/"},
+                    "public": {"type": "typed-literal", "value": "true", "datatype": "http://www.w3.org/2001/XMLSchema#boolean"}
+                },
+                {
+                    "id": {"type": "uri", "value": "https://ark.dasch.swiss/ark:/72163/1/0803"},
+                    "name": {"type": "literal", "value": "Letters"},
+                    "size": {"type": "literal", "value": "7", "datatype": "http://www.w3.org/2001/XMLSchema#integer"},
+                    "founded": {"type": "literal", "value": "1900-01-01", "datatype": "http://www.w3.org/2001/XMLSchema#date"},
+                    "description": {"type": "literal", "value": "Briefe", "xml:lang": "de"}
+                }
+            ]}
+        }"#;
+        let rows: Vec<Row> = from_sparql_json(input).unwrap();
+        assert_eq!(
+            rows,
+            [
+                Row {
+                    id: "https://ark.dasch.swiss/ark:/72163/1/081C".to_string(),
+                    name: "Hôtel de Musique Bern".to_string(),
+                    size: 42,
+                    founded: "1767-01-01".to_string(),
+                    description: "The Hôtel de Musique".to_string(),
+                    homepage: Some("https://www.hoteldemusique.ch/".to_string()),
+                    public: Some(true),
+                },
+                Row {
+                    id: "https://ark.dasch.swiss/ark:/72163/1/0803".to_string(),
+                    name: "Letters".to_string(),
+                    size: 7,
+                    founded: "1900-01-01".to_string(),
+                    description: "Briefe".to_string(),
+                    homepage: None,
+                    public: None,
+                },
+            ]
+        );
+
+        // Literals are checked against their datatype as in RDF input.
+        let input = r#"{"head": {"vars": ["size"]}, "results": {"bindings": [
+            {"size": {"type": "literal", "value": "many", "datatype": "http://www.w3.org/2001/XMLSchema#integer"}}
+        ]}}"#;
+        #[derive(Debug, Deserialize)]
+        struct Size {
+            #[allow(dead_code)]
+            size: u32,
+        }
+        assert!(from_sparql_json::<Size>(input).is_err());
+        let error = from_sparql_json::<Size>(r#"{"boolean": true}"#).unwrap_err();
+        assert!(error.to_string().contains("bindings"), "{error}");
+    }
+}
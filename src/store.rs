@@ -0,0 +1,164 @@
+//! Serialize into and deserialize from an [Oxigraph](https://docs.rs/oxigraph) store, without
+//! going through RDF text.
+
+use std::collections::HashSet;
+
+use oxigraph::model::{GraphName, NamedNode, Subject, Term, Triple};
+use oxigraph::store::Store;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::de::Deserializer;
+use crate::error::{Error, Result};
+use crate::graph::Graph;
+use crate::ser::to_triples;
+use crate::structure::{DeserializerConfig, SerializerConfig};
+use crate::triple::OwnedTriple;
+
+/// Serialize the given data structure into the store, into the named `graph` or the default
+/// graph, and return the number of triples it produced. The triples are inserted in one
+/// transaction.
+///
+/// # Errors
+///
+/// Serialization fails if the type cannot be represented as RDF, or if the store cannot be
+/// written to.
+pub fn to_store<T>(
+    value: &T,
+    config: &SerializerConfig,
+    store: &Store,
+    graph: Option<&NamedNode>,
+) -> Result<usize>
+where
+    T: ?Sized + Serialize,
+{
+    let triples = to_triples(value, config)?;
+    let count = triples.len();
+    let graph_name = graph.map_or(GraphName::DefaultGraph, |graph| graph.clone().into());
+    store
+        .extend(
+            triples
+                .into_iter()
+                .map(|triple| Triple::from(triple).in_graph(graph_name.clone())),
+        )
+        .map_err(Error::new)?;
+    Ok(count)
+}
+
+/// Deserialize an instance of type `T` from the subject with the given IRI in the store. The
+/// triples of the subject are read from all graphs, along with those of the subjects it
+/// refers to, transitively, which nested structs are read from.
+///
+/// # Errors
+///
+/// This functions fails with an [`Error::SubjectNotFound`] if the subject has no triples, with
+/// an error if the store cannot be read, or if the data does not match the structure of `T`.
+pub fn from_store<T>(store: &Store, subject: &str, config: &DeserializerConfig) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let root = NamedNode::new(subject).map_err(|err| {
+        Error::Message(format!(
+            "from_store -> invalid subject IRI {subject}: {err}"
+        ))
+    })?;
+    let mut graph = Graph::new();
+    let mut pending = vec![Subject::from(root)];
+    let mut visited = HashSet::new();
+    while let Some(node) = pending.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        for quad in store.quads_for_pattern(Some(node.as_ref()), None, None, None) {
+            let triple = Triple::from(quad.map_err(Error::new)?);
+            match &triple.object {
+                Term::NamedNode(iri) => pending.push(iri.clone().into()),
+                Term::BlankNode(id) => pending.push(id.clone().into()),
+                _ => {}
+            }
+            graph.insert(OwnedTriple::from(triple));
+        }
+    }
+    let deserializer = Deserializer::from_triples(graph)
+        .with_config(config)
+        .with_subject(subject)?;
+    T::deserialize(deserializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use oxigraph::model::{GraphNameRef, NamedNode};
+    use oxigraph::store::Store;
+    use serde::{Deserialize, Serialize};
+
+    use crate::{from_store, to_store, DeserializerConfig, Error, SerializerConfig, SubjectConfig};
+
+    #[test]
+    fn test_store() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Project {
+            id: String,
+            name: String,
+            datasets: Vec<Dataset>,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Dataset {
+            title: String,
+        }
+
+        let config = || {
+            SerializerConfig::builder()
+                .subject(
+                    SubjectConfig::builder("Project")
+                        .rdf_type("https://ns.dasch.swiss/repository#Project")
+                        .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+                        .property("name", "https://ns.dasch.swiss/repository#hasName")
+                        .property("datasets", "https://ns.dasch.swiss/repository#hasDataset")
+                        .build(),
+                )
+                .subject(
+                    SubjectConfig::builder("Dataset")
+                        .rdf_type("https://ns.dasch.swiss/repository#Dataset")
+                        .property("title", "https://ns.dasch.swiss/repository#hasTitle")
+                        .build(),
+                )
+                .build()
+                .unwrap()
+        };
+        let project = Project {
+            id: "081C".to_string(),
+            name: "Hôtel de Musique Bern".to_string(),
+            datasets: vec![Dataset {
+                title: "Events".to_string(),
+            }],
+        };
+
+        let store = Store::new().unwrap();
+        let graph = NamedNode::new("https://example.org/graphs/081C").unwrap();
+        let count = to_store(&project, &config(), &store, Some(&graph)).unwrap();
+        assert_eq!(count, 5);
+        assert_eq!(store.len().unwrap(), 5);
+        assert!(store
+            .quads_for_pattern(None, None, None, Some(GraphNameRef::DefaultGraph))
+            .next()
+            .is_none());
+
+        let de_config = DeserializerConfig::try_from(&config()).unwrap();
+        let round_trip: Project = from_store(
+            &store,
+            "https://ark.dasch.swiss/ark:/72163/1/081C",
+            &de_config,
+        )
+        .unwrap();
+        assert_eq!(round_trip, project);
+
+        let error = from_store::<Project>(
+            &store,
+            "https://ark.dasch.swiss/ark:/72163/1/0803",
+            &de_config,
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::SubjectNotFound(_)), "{error}");
+    }
+}
@@ -1,41 +1,751 @@
-#![allow(unused_variables, unused_imports, dead_code)]
-
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
 
-use rio_api::model::NamedNode;
+use crate::error::{Error, Result};
+use crate::iri::{check_iri, is_absolute_iri};
+use crate::ns::{RDF_TYPE, WELL_KNOWN_PREFIXES};
+use crate::triple::OwnedLiteral;
+use crate::xsd::XsdType;
 
-pub enum Term {
-    Literal(String),
-    Subject(String),
+/// Whether a property has literals or subjects as objects, see [`PropertyConfig::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TermKind {
+    /// Literals, e.g. from a `String` or a number.
+    Literal,
+    /// IRIs or blank nodes, e.g. from a nested struct or a unit struct of an individual.
+    Subject,
 }
 
 /// A subject holds additional information for the serializer
 /// to further configure how a specific rust struct should be serialized.
-#[derive(Debug)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SubjectConfig {
     pub struct_name: String,
     pub rdf_type: String,
     pub identifier_field: String,
     pub identifier_prefix: String,
     pub properties: Vec<PropertyConfig>,
+    /// The field holding the class of an individual instance. When set and not empty, its value
+    /// is used as the object of the `rdf:type` triple instead of `rdf_type`, and the field is
+    /// not serialized as a property. It is deserialized from the IRI of the class.
+    pub type_field: Option<String>,
+    /// The label prefix of the blank nodes generated for this subject, e.g. `addr` for `_:addr0`.
+    /// Subjects are blank nodes when no `identifier_field` is configured.
+    pub blank_node_prefix: Option<String>,
+    /// The names of the `property_groups` of the config whose properties the subject has in
+    /// addition to its own, e.g. `audit` for `createdAt` and `modifiedAt`. They are merged into
+    /// `properties` when the config is built, loaded or serialized with.
+    pub include_properties: Vec<String>,
 }
 
-#[derive(Debug)]
+impl SubjectConfig {
+    /// Start the config of the struct `struct_name`, see [`SubjectBuilder`].
+    pub fn builder(struct_name: impl Into<String>) -> SubjectBuilder {
+        SubjectBuilder {
+            struct_name: struct_name.into(),
+            rdf_type: String::new(),
+            identifier_field: String::new(),
+            identifier_prefix: String::new(),
+            properties: Vec::new(),
+            type_field: None,
+            blank_node_prefix: None,
+            include_properties: Vec::new(),
+        }
+    }
+
+    /// Whether the field is part of the RDF representation of the struct: the identifier, the
+    /// type field or one with a property mapping.
+    pub(crate) fn maps_field(&self, field: &str) -> bool {
+        self.identifier_field == field
+            || self.type_field.as_deref() == Some(field)
+            || self.properties.iter().any(|p| p.struct_field == field)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PropertyConfig {
     pub struct_field: String,
     pub rdf_property: String,
+    /// In addition to the triple itself, describe it with a reified `rdf:Statement`, which is
+    /// linked from the subject through `SerializerConfig::reification_predicate`.
+    pub reify: bool,
+    /// Link the subjects of the field to the struct rather than the other way round, e.g. a
+    /// `Project.datasets` field with `dsp:isPartOf` writes `<dataset> dsp:isPartOf <project>`.
+    /// The deserializer reads the field from the subjects linking to the struct.
+    pub inverse: bool,
+    /// Further predicates the deserializer reads the field from, e.g. the one of an older
+    /// version of the ontology. The serializer only writes the `rdf_property`. A subject with
+    /// different objects for the property and an alias is an error, unless the config
+    /// prefers the primary property.
+    pub aliases: Vec<String>,
+    /// The datatype of the literals of the field, e.g. [`XsdType::Decimal`] for a string
+    /// holding an amount, instead of the one of its Rust type. Language-tagged strings keep
+    /// their language. When deserializing, typed literals of another datatype are reported as
+    /// an [`Error::UnexpectedDatatype`].
+    pub datatype: Option<XsdType>,
+    /// The language tag of the literals of the field, e.g. `en`, which takes precedence over
+    /// the `datatype`. Literals that already have a language, e.g. the values of a language
+    /// map, keep it.
+    pub language: Option<String>,
+    /// The value of the field when the subject has no triple with the property, written as a
+    /// Turtle literal, e.g. `0`, `true` or `"unknown"@en`. Prefixed names use the
+    /// `namespaces` of the config. It takes precedence over `#[serde(default)]` and is only
+    /// read by the deserializer.
+    pub default_literal: Option<String>,
+    /// The least number of objects the property has per subject, e.g. `1` for a required
+    /// field. A field that is not serialized, like an empty `Vec` or `None`, has none.
+    pub min_count: Option<u32>,
+    /// The greatest number of objects the property has per subject.
+    pub max_count: Option<u32>,
+    /// Whether the objects of the property are literals or subjects. When set, the serializer
+    /// reports objects of the other kind, e.g. after the type of the field changed from
+    /// `String` to a struct.
+    pub kind: Option<TermKind>,
+    /// The struct whose subject config applies to the values of the field that are serialized
+    /// as maps rather than structs, e.g. the JSON objects of a `serde_json::Value`. The keys of
+    /// such a map are the field names.
+    pub nested_subject: Option<String>,
+}
+
+impl PropertyConfig {
+    /// Check that `found` objects of the property `predicate` are within the bounds of
+    /// `min_count` and `max_count`.
+    pub(crate) fn check_count(
+        &self,
+        subject: impl FnOnce() -> String,
+        predicate: &str,
+        found: usize,
+    ) -> Result<()> {
+        let expected = match (self.min_count, self.max_count) {
+            (Some(min), _) if found < min as usize => min,
+            (_, Some(max)) if found > max as usize => max,
+            _ => return Ok(()),
+        };
+        Err(Error::CardinalityViolation {
+            subject: subject(),
+            predicate: predicate.to_string(),
+            expected: expected as usize,
+            found,
+        })
+    }
 }
 
 /// Serializer configuration containing mappings / instructions on how to
 /// serialize rust structs into RDF. The config contains one ore more
 /// `Subject`s.
-#[derive(Debug)]
+///
+/// Example:
+/// ```
+/// use serde_rdf::{SerializerConfig, SubjectConfig};
+/// let _config = SerializerConfig::builder()
+///     .subject(
+///         SubjectConfig::builder("Project")
+///             .rdf_type("https://ns.dasch.swiss/repository#Project")
+///             .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+///             .property("name", "https://ns.dasch.swiss/repository#hasName")
+///             .property("description", "https://ns.dasch.swiss/repository#hasDescription")
+///             .property("shortcode", "https://ns.dasch.swiss/repository#hasShortcode")
+///             .property("datasets", "https://ns.dasch.swiss/repository#hasDataset")
+///             .build(),
+///     )
+///     .subject(
+///         SubjectConfig::builder("Dataset")
+///             .rdf_type("https://ns.dasch.swiss/repository#Dataset")
+///             .identifier("id", "https://ark.dasch.swiss/ark:/72163/1/")
+///             .property("title", "https://ns.dasch.swiss/repository#hasTitle")
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// The config can also be loaded from a file, e.g. with
+/// [`from_yaml_str`](SerializerConfig::from_yaml_str), where the subjects are keyed by the
+/// name of their struct and omitted options keep their default:
+/// ```yaml
+/// subjects:
+///   Project:
+///     rdf_type: https://ns.dasch.swiss/repository#Project
+///     identifier_field: id
+///     identifier_prefix: https://ark.dasch.swiss/ark:/72163/1/
+///     properties:
+///       - struct_field: name
+///         rdf_property: https://ns.dasch.swiss/repository#hasName
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SerializerConfig {
     pub base_iri: String,
     pub namespaces: HashMap<String, String>,
     pub subjects: HashMap<String, SubjectConfig>,
+    /// The `identifier_prefix` of the subjects with an `identifier_field` but without a prefix
+    /// of their own, e.g. an ARK prefix shared by all of them. It may be a prefixed name.
+    pub default_identifier_prefix: Option<String>,
+    /// Lists of properties shared by several subjects, keyed by a name that the subjects refer
+    /// to in their `include_properties`.
+    pub property_groups: HashMap<String, Vec<PropertyConfig>>,
+    /// The IRIs of well-known individuals, keyed by the name of the unit struct representing
+    /// them, e.g. `PublicLicense`. Prefixed names are expanded.
+    pub individuals: HashMap<String, String>,
+    /// The predicate used to type subjects. Defaults to `rdf:type`.
+    pub type_predicate: Option<String>,
+    /// The predicate linking a subject to the `rdf:Statement`s of its reified properties.
+    pub reification_predicate: Option<String>,
+    /// Number generated blank nodes from zero for every serialization, so that serializing the
+    /// same value twice produces the same output.
+    pub deterministic: bool,
+    /// The struct whose subject config applies to a map serialized at the top level, e.g. a
+    /// JSON object transcoded with `json_to_rdf`, whose keys are then the field names. Maps
+    /// nested in it need a [`PropertyConfig::nested_subject`].
+    pub root_subject: Option<String>,
+    /// Replace the generated blank nodes with skolem IRIs under this base, e.g.
+    /// `https://example.org/.well-known/genid/`, for stores that handle blank nodes poorly. The
+    /// IRIs are unique to the run unless the config is `deterministic`. It may be a prefixed
+    /// name.
+    pub skolemize: Option<String>,
+    /// Report inconsistencies in the data as errors instead of resolving them silently, e.g. a
+    /// map key that differs from the identifier of the subject it maps to.
+    pub strict: bool,
+    /// Emit the entries of maps ordered by key rather than in iteration order, so that
+    /// `HashMap` fields produce stable output.
+    pub sort_map_entries: bool,
+    /// Write IRIs within the configured namespaces as prefixed names, e.g. `dsp:hasName`, and
+    /// declare the namespaces with `@prefix`.
+    pub compact: bool,
+    /// Report struct fields without a property mapping as an
+    /// [`Error::MissingPropertyConfig`] instead of leaving them out of the RDF representation.
+    pub deny_unmapped_fields: bool,
+    /// Called once for every subject after its own triples, to add triples that are computed at
+    /// serialization time rather than stored in the structs. It is not part of a config loaded
+    /// from a file.
+    #[serde(skip)]
+    pub on_subject: Option<SubjectHook>,
+}
+
+/// A callback receiving the IRI (or blank node label) of a subject and the name of its struct,
+/// and returning additional predicate / literal pairs for that subject. Predicates may be
+/// prefixed names. Clones share the callback.
+#[derive(Clone)]
+pub struct SubjectHook(Arc<SubjectHookFn>);
+
+type SubjectHookFn = dyn Fn(&str, &str) -> Vec<(String, OwnedLiteral)> + Send + Sync;
+
+impl SubjectHook {
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&str, &str) -> Vec<(String, OwnedLiteral)> + Send + Sync + 'static,
+    {
+        SubjectHook(Arc::new(hook))
+    }
+
+    pub(crate) fn call(&self, subject: &str, struct_name: &str) -> Vec<(String, OwnedLiteral)> {
+        (self.0)(subject, struct_name)
+    }
+}
+
+impl fmt::Debug for SubjectHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SubjectHook(..)")
+    }
+}
+
+impl SerializerConfig {
+    /// Start a config, see [`SerializerConfigBuilder`].
+    pub fn builder() -> SerializerConfigBuilder {
+        SerializerConfigBuilder::default()
+    }
+
+    /// A config whose namespaces hold the [well-known prefixes](crate::ns::WELL_KNOWN_PREFIXES),
+    /// e.g. `xsd` and `rdfs`. Namespaces inserted afterwards replace them.
+    pub fn with_well_known_prefixes() -> SerializerConfig {
+        SerializerConfig::builder().well_known_prefixes().config
+    }
+
+    /// Load a config from YAML, or JSON, which YAML includes, and validate it.
+    #[cfg(feature = "yaml")]
+    pub fn from_reader(reader: impl std::io::Read) -> Result<SerializerConfig> {
+        serde_yaml::from_reader(reader)
+            .map_err(Error::new)
+            .and_then(SerializerConfig::loaded)
+    }
+
+    /// Load a config from YAML and validate it.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(s: &str) -> Result<SerializerConfig> {
+        serde_yaml::from_str(s)
+            .map_err(Error::new)
+            .and_then(SerializerConfig::loaded)
+    }
+
+    /// Load a config from TOML and validate it.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(s: &str) -> Result<SerializerConfig> {
+        toml::from_str(s)
+            .map_err(Error::new)
+            .and_then(SerializerConfig::loaded)
+    }
+
+    /// Generate a config from an ontology in Turtle, with a subject per `rdfs:Class` or
+    /// `owl:Class`, named after the local name of the class and identified by the field `id`
+    /// with `base_iri` as prefix. Its properties are those with the class as `rdfs:domain`,
+    /// mapped to the field named after their local name in snake case, e.g. `has_name` for
+    /// `dsp:hasName`. An XSD datatype as `rdfs:range` becomes the `datatype`, and a class the
+    /// `kind` of the property. Relative IRIs are resolved against `base_iri`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the ontology is not valid Turtle or the config does not validate.
+    pub fn from_ontology(ttl: &str, base_iri: &str) -> Result<SerializerConfig> {
+        crate::ontology::config(ttl, base_iri)
+    }
+
+    /// The SHACL shapes of the subjects as Turtle, to validate data against the mapping. Each
+    /// subject becomes a `sh:NodeShape` with its `rdf_type` as `sh:targetClass`, and each of
+    /// its properties a `sh:property` with the `rdf_property` as `sh:path`, or as its
+    /// `sh:inversePath` for an inverse property, and the `datatype`, `kind`, `min_count` and
+    /// `max_count` if set. The struct and field names are kept as
+    /// `sh:name`, and the identifier prefix as a `sh:pattern` matching the start of the IRIs.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a property group or a prefix of the config is unknown.
+    pub fn to_shacl(&self) -> Result<String> {
+        crate::shacl::to_shacl(self)
+    }
+
+    /// Generate a config from SHACL shapes in Turtle, the inverse of
+    /// [`to_shacl`](SerializerConfig::to_shacl). Each `sh:NodeShape` with a `sh:targetClass`
+    /// becomes a subject and each of its `sh:property` shapes with an IRI or a `sh:inversePath`
+    /// as `sh:path` a property, with the `sh:datatype`, `sh:nodeKind`, `sh:minCount` and
+    /// `sh:maxCount`. The struct and field names are taken from `sh:name`, or else from the
+    /// local name of the class, and of the path in snake case. Subjects with `sh:nodeKind sh:IRI` and a
+    /// `sh:pattern` of the form `^prefix` are identified by the field `id` with that prefix.
+    ///
+    /// The other SHACL features, e.g. `sh:closed`, are ignored and returned as warnings.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the shapes are not valid Turtle or the config does not validate.
+    pub fn from_shacl(ttl: &str) -> Result<(SerializerConfig, Vec<String>)> {
+        crate::shacl::from_shacl(ttl)
+    }
+
+    /// Combine two configs, e.g. one of a core crate and one of a plugin adding its own
+    /// subjects. The namespaces, subjects, property groups and individuals of both are kept,
+    /// the other options are those of `self`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if both configs have a different `base_iri`, or a prefix, struct, property group
+    /// or individual with a different definition. Identical duplicates are fine.
+    pub fn merge(mut self, other: SerializerConfig) -> Result<SerializerConfig> {
+        if self.base_iri.is_empty() {
+            self.base_iri = other.base_iri;
+        } else if !other.base_iri.is_empty() && other.base_iri != self.base_iri {
+            return Err(Error::Message(format!(
+                "merge -> conflicting base IRIs: {} and {}",
+                self.base_iri, other.base_iri
+            )));
+        }
+        merge_map(
+            &mut self.namespaces,
+            other.namespaces,
+            "namespaces for prefix",
+        )?;
+        merge_map(
+            &mut self.subjects,
+            other.subjects,
+            "subject configs for struct",
+        )?;
+        merge_map(
+            &mut self.property_groups,
+            other.property_groups,
+            "property groups",
+        )?;
+        merge_map(
+            &mut self.individuals,
+            other.individuals,
+            "individuals for unit struct",
+        )?;
+        Ok(self)
+    }
+
+    /// Take the omitted `struct_name` of a subject from its key, then expand the prefixed
+    /// names and validate the config.
+    #[cfg(any(feature = "yaml", feature = "toml"))]
+    fn loaded(mut self) -> Result<SerializerConfig> {
+        for (name, subject) in &mut self.subjects {
+            if subject.struct_name.is_empty() {
+                subject.struct_name = name.clone();
+            }
+        }
+        self.include_property_groups()?;
+        self.expand_prefixed_names()?;
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Merge the properties of the groups in the `include_properties` of each subject into its
+    /// `properties`, after its own, and clear the list.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a group does not exist or maps a field the subject maps already.
+    pub fn include_property_groups(&mut self) -> Result<()> {
+        for (name, subject) in &mut self.subjects {
+            for group in std::mem::take(&mut subject.include_properties) {
+                let properties = self.property_groups.get(&group).ok_or_else(|| {
+                    Error::Message(format!(
+                        "validate -> unknown property group {group} included by subject: {name}"
+                    ))
+                })?;
+                for property in properties {
+                    if subject.maps_field(&property.struct_field) {
+                        return Err(Error::Message(format!(
+                            "validate -> field {} of subject {name} is also in property group: \
+                             {group}",
+                            property.struct_field
+                        )));
+                    }
+                    subject.properties.push(property.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the prefixed names among the IRIs of the config, e.g. `dsp:Project`, with the
+    /// IRIs they stand for: the `rdf_type`, `identifier_prefix`, and the `rdf_property` and
+    /// `datatype` of the properties of each subject, the `individuals`, the type and
+    /// reification predicates, the `default_identifier_prefix` and the `skolemize` base.
+    /// Values whose prefix is not one of the `namespaces` are kept if they are absolute IRIs,
+    /// e.g. `https://…` or `urn:…`. It is done when the config is built or loaded, and before
+    /// serializing.
+    ///
+    /// Subjects without an `identifier_prefix` of their own get the
+    /// `default_identifier_prefix` first.
+    ///
+    /// # Errors
+    ///
+    /// Fails with an [`Error::UnknownPrefix`] if a prefix is not one of the `namespaces` and
+    /// the value is not an absolute IRI either.
+    pub fn expand_prefixed_names(&mut self) -> Result<()> {
+        self.apply_default_identifier_prefix();
+        let namespaces = &self.namespaces;
+        for subject in self.subjects.values_mut() {
+            expand_in_place(namespaces, &mut subject.rdf_type)?;
+            expand_in_place(namespaces, &mut subject.identifier_prefix)?;
+            for property in &mut subject.properties {
+                expand_in_place(namespaces, &mut property.rdf_property)?;
+                if let Some(iri) = property.datatype.as_mut().and_then(XsdType::custom_iri_mut) {
+                    expand_in_place(namespaces, iri)?;
+                }
+            }
+        }
+        for iri in self.individuals.values_mut() {
+            expand_in_place(namespaces, iri)?;
+        }
+        for iri in [
+            &mut self.type_predicate,
+            &mut self.reification_predicate,
+            &mut self.default_identifier_prefix,
+            &mut self.skolemize,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            expand_in_place(namespaces, iri)?;
+        }
+        Ok(())
+    }
+
+    /// Give the subjects with an `identifier_field` but no `identifier_prefix` the
+    /// `default_identifier_prefix`.
+    fn apply_default_identifier_prefix(&mut self) {
+        let Some(prefix) = &self.default_identifier_prefix else {
+            return;
+        };
+        for subject in self.subjects.values_mut() {
+            if !subject.identifier_field.is_empty() && subject.identifier_prefix.is_empty() {
+                subject.identifier_prefix = prefix.clone();
+            }
+        }
+    }
+
+    /// Check that every subject has an `rdf_type`, that its type and properties are absolute
+    /// IRIs, and that a subject with an `identifier_field` has an `identifier_prefix`, its own
+    /// or the `default_identifier_prefix`. The `skolemize` base has to be an absolute IRI too.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(base) = &self.skolemize {
+            check_iri(base, || "skolemize base".to_string())?;
+        }
+        let mut subjects: Vec<_> = self.subjects.iter().collect();
+        subjects.sort_by_key(|(name, _)| name.as_str());
+        for (name, subject) in subjects {
+            if subject.rdf_type.is_empty() {
+                return Err(Error::Message(format!(
+                    "validate -> no rdf_type configured for subject: {name}"
+                )));
+            }
+            check_iri(&subject.rdf_type, || format!("rdf_type of subject {name}"))?;
+            let default_prefix = self.default_identifier_prefix.as_deref().unwrap_or("");
+            if !subject.identifier_field.is_empty()
+                && subject.identifier_prefix.is_empty()
+                && default_prefix.is_empty()
+            {
+                return Err(Error::Message(format!(
+                    "validate -> no identifier_prefix configured for subject: {name}, and no \
+                     default_identifier_prefix"
+                )));
+            }
+            for property in &subject.properties {
+                check_iri(&property.rdf_property, || {
+                    format!("rdf_property of field {name}.{}", property.struct_field)
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Expand a `prefix:local` name using the configured namespaces. Values with an unknown
+    /// prefix are returned unchanged.
+    pub(crate) fn expand_prefixed_name<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        expand_prefixed_name(&self.namespaces, value)
+    }
+
+    /// Check that the given fields of the struct `struct_name` are all part of its RDF
+    /// representation, returning an [`Error::MissingPropertyConfig`] for each one that is not,
+    /// and an [`Error::InvalidIri`] for each `rdf_property` that is not an absolute IRI.
+    /// Without a subject config for the struct, none of the fields is.
+    pub fn validate_fields(&self, struct_name: &str, fields: &[&str]) -> Vec<Error> {
+        let subject = self.subjects.get(struct_name);
+        let mut errors = Vec::new();
+        for field in fields {
+            if !subject.is_some_and(|subject| subject.maps_field(field)) {
+                errors.push(Error::MissingPropertyConfig {
+                    struct_name: struct_name.to_string(),
+                    field: field.to_string(),
+                });
+            }
+        }
+        for property in subject.map_or(&[][..], |subject| &subject.properties) {
+            if fields.contains(&property.struct_field.as_str()) {
+                let context = || format!("rdf_property of field {}", property.struct_field);
+                errors.extend(check_iri(&property.rdf_property, context).err());
+            }
+        }
+        errors
+    }
 }
 
+/// Deserializer configuration mapping RDF predicates back onto the fields of rust structs. It
+/// mirrors [`SerializerConfig`], so that the same `SubjectConfig`s describe both directions.
+#[derive(Debug, Default, Clone)]
+pub struct DeserializerConfig {
+    pub namespaces: HashMap<String, String>,
+    pub subjects: HashMap<String, SubjectConfig>,
+    /// Remove the `identifier_prefix` of the subject from its IRI before storing it in the
+    /// identifier field. Otherwise the field receives the full IRI.
+    pub strip_identifier_prefix: bool,
+    /// Populate optional identifier fields of blank node subjects with their label, e.g.
+    /// `_:b0`, instead of `None`. Identifier fields of type `String` always receive the label.
+    pub blank_node_labels: bool,
+    /// Report ambiguities in the data as errors instead of resolving them silently, e.g. two
+    /// literals with the same language for a language map, of which otherwise the last is kept.
+    pub strict: bool,
+    /// Report the predicates of a subject that populate none of the fields of its struct as an
+    /// error, like `#[serde(deny_unknown_fields)]`. `rdf:type` is always allowed.
+    pub deny_unknown_predicates: bool,
+    /// Take the first object in document order when a single-valued field has several,
+    /// instead of reporting an [`Error::CardinalityViolation`](crate::Error::CardinalityViolation).
+    pub first_wins: bool,
+    /// Report a field whose number of objects is outside the `min_count` and `max_count` of its
+    /// property as an [`Error::CardinalityViolation`](crate::Error::CardinalityViolation).
+    pub check_counts: bool,
+    /// Read a field from its `rdf_property` when an alias has different objects, instead of
+    /// reporting the conflict as an error.
+    pub prefer_primary_property: bool,
+    /// Accept simple literals where a typed literal is expected, e.g. `"true"` for a `bool`.
+    pub lenient: bool,
+    /// Read simple and `xsd:string` literals into numbers, booleans and durations if their
+    /// lexical form is valid for the target type, e.g. `"42"^^xsd:string` for an integer.
+    /// Otherwise they are reported as an
+    /// [`Error::UnexpectedDatatype`](crate::Error::UnexpectedDatatype).
+    pub coerce_literals: bool,
+    /// Namespaces keyed by prefix, e.g. `xsd`, that Turtle documents may use without declaring
+    /// them. They only apply in `lenient` mode.
+    pub default_prefixes: HashMap<String, String>,
+    /// The languages to pick from, in order of preference, when language-tagged literals are
+    /// read into a plain string, e.g. `["de", "en"]`. If none of them is available, any of the
+    /// literals is taken. Without a priority, several languages are an error.
+    pub language_priority: Vec<String>,
+    /// Match the language tags keying language maps with enum variants as a whole, ignoring
+    /// only case, and report tags that match no variant. Otherwise `en-US` also matches `EN`.
+    pub strict_language_tags: bool,
+    /// The names of the enum variants represented by individual IRIs, keyed by the IRI, e.g.
+    /// `https://ns.example.org/Active` for `Active`. Prefixed names are expanded. Otherwise the
+    /// local name of the IRI, after the last `#` or `/`, is taken as the variant name.
+    pub variants: HashMap<String, String>,
+    /// The names of the enum variants representing classes, keyed by the class IRI, to choose
+    /// the variant from the `rdf:type` of a subject. Prefixed names are expanded. Otherwise the
+    /// `struct_name` of the subject config with that `rdf_type`, or the local name of the class
+    /// is taken as the variant name.
+    pub classes: HashMap<String, String>,
+    /// The key holding the class of a subject that is read as a map, as for enums with
+    /// `#[serde(tag = "type")]`. Defaults to `type`.
+    pub type_tag: Option<String>,
+    /// How deeply structs may be nested, to guard against pathological chains of subjects.
+    /// Defaults to 128.
+    pub max_depth: Option<usize>,
+    /// Read the IRIs under this skolem base, as minted with
+    /// [`SerializerConfig::skolemize`], as blank nodes for identifier fields, which receive
+    /// `None` or the label after the base, as for `blank_node_labels`.
+    pub skolem_base: Option<String>,
+    /// The struct field populated with the IRI of the named graph selected when reading
+    /// quads, e.g. by [`from_trig_str`](crate::from_trig_str). It is left empty otherwise.
+    pub graph_field: Option<String>,
+    /// The predicate typing subjects, as in [`SerializerConfig::type_predicate`]. Prefixed
+    /// names are expanded. Defaults to `rdf:type`.
+    pub type_predicate: Option<String>,
+}
+
+impl DeserializerConfig {
+    /// Expand a `prefix:local` name using the configured namespaces. Values with an unknown
+    /// prefix are returned unchanged.
+    pub(crate) fn expand_prefixed_name<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        expand_prefixed_name(&self.namespaces, value)
+    }
+
+    /// The predicate typing subjects.
+    pub(crate) fn type_predicate(&self) -> Cow<'_, str> {
+        match &self.type_predicate {
+            Some(predicate) => self.expand_prefixed_name(predicate),
+            None => Cow::Borrowed(RDF_TYPE),
+        }
+    }
+}
+
+/// The mapping of a serializer config, set up to reverse its output.
+impl TryFrom<&SerializerConfig> for DeserializerConfig {
+    type Error = Error;
+
+    /// # Errors
+    ///
+    /// Fails if the config includes an unknown property group.
+    fn try_from(config: &SerializerConfig) -> Result<Self> {
+        let mut included = SerializerConfig {
+            subjects: config.subjects.clone(),
+            default_identifier_prefix: config.default_identifier_prefix.clone(),
+            property_groups: config.property_groups.clone(),
+            ..Default::default()
+        };
+        included.include_property_groups()?;
+        included.apply_default_identifier_prefix();
+        Ok(DeserializerConfig {
+            namespaces: config.namespaces.clone(),
+            subjects: included.subjects,
+            strip_identifier_prefix: true,
+            blank_node_labels: false,
+            strict: config.strict,
+            deny_unknown_predicates: false,
+            first_wins: false,
+            check_counts: false,
+            prefer_primary_property: false,
+            lenient: false,
+            coerce_literals: false,
+            default_prefixes: HashMap::new(),
+            language_priority: Vec::new(),
+            strict_language_tags: false,
+            variants: HashMap::new(),
+            classes: HashMap::new(),
+            type_tag: None,
+            max_depth: None,
+            skolem_base: config
+                .skolemize
+                .as_deref()
+                .map(|base| config.expand_prefixed_name(base).into_owned()),
+            graph_field: None,
+            type_predicate: config.type_predicate.clone(),
+        })
+    }
+}
+
+/// Add the entries of `from` to `into`, failing on a key with a different value in both.
+fn merge_map<V: PartialEq>(
+    into: &mut HashMap<String, V>,
+    from: HashMap<String, V>,
+    what: &str,
+) -> Result<()> {
+    for (key, value) in from {
+        match into.get(&key) {
+            Some(existing) if *existing != value => {
+                return Err(Error::Message(format!(
+                    "merge -> conflicting {what}: {key}"
+                )));
+            }
+            Some(_) => {}
+            None => {
+                into.insert(key, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Expand a prefixed name in a config in place, see `try_expand_prefixed_name`.
+fn expand_in_place(namespaces: &HashMap<String, String>, value: &mut String) -> Result<()> {
+    if let Cow::Owned(iri) = try_expand_prefixed_name(namespaces, value)? {
+        *value = iri;
+    }
+    Ok(())
+}
+
+/// Expand a prefixed name in a config like `expand_prefixed_name`, but keep a value with
+/// another prefix only if it is an absolute IRI, e.g. `https://…` or `urn:…`, and fail with an
+/// unknown prefix otherwise. Values without a colon are kept, e.g. an empty
+/// `identifier_prefix`.
+pub(crate) fn try_expand_prefixed_name<'a>(
+    namespaces: &HashMap<String, String>,
+    value: &'a str,
+) -> Result<Cow<'a, str>> {
+    let expanded = expand_prefixed_name(namespaces, value);
+    let Some((prefix, _)) = value.split_once(':') else {
+        return Ok(expanded);
+    };
+    if matches!(expanded, Cow::Owned(_)) || is_absolute_iri(value) {
+        return Ok(expanded);
+    }
+    let mut known: Vec<_> = namespaces.keys().cloned().collect();
+    known.sort();
+    Err(Error::UnknownPrefix {
+        prefix: prefix.to_string(),
+        value: value.to_string(),
+        known,
+    })
+}
+
+/// Expand a prefixed name whose prefix is one of the `namespaces`. Other values are kept.
+fn expand_prefixed_name<'a>(namespaces: &HashMap<String, String>, value: &'a str) -> Cow<'a, str> {
+    let expanded = value.split_once(':').and_then(|(prefix, local)| {
+        namespaces
+            .get(prefix)
+            .map(|namespace| format!("{namespace}{local}"))
+    });
+    match expanded {
+        Some(iri) => Cow::Owned(iri),
+        None => Cow::Borrowed(value),
+    }
+}
+
+/// Builds a [`SubjectConfig`], started by [`SubjectConfig::builder`]. The `rdf_type` is
+/// required, which [`SerializerConfigBuilder::build`] checks.
 #[derive(Debug)]
 pub struct SubjectBuilder {
     struct_name: String,
@@ -43,4 +753,138 @@ pub struct SubjectBuilder {
     identifier_field: String,
     identifier_prefix: String,
     properties: Vec<PropertyConfig>,
+    type_field: Option<String>,
+    blank_node_prefix: Option<String>,
+    include_properties: Vec<String>,
+}
+
+impl SubjectBuilder {
+    /// The class of the instances of the struct.
+    pub fn rdf_type(mut self, iri: impl Into<String>) -> Self {
+        self.rdf_type = iri.into();
+        self
+    }
+
+    /// The field holding the identifier of an instance, which is appended to `prefix` to form
+    /// its IRI. Without one, instances are blank nodes.
+    pub fn identifier(mut self, field: impl Into<String>, prefix: impl Into<String>) -> Self {
+        self.identifier_field = field.into();
+        self.identifier_prefix = prefix.into();
+        self
+    }
+
+    /// The field holding the class of an instance, which is written instead of the `rdf_type`
+    /// unless it is empty.
+    pub fn type_field(mut self, field: impl Into<String>) -> Self {
+        self.type_field = Some(field.into());
+        self
+    }
+
+    /// The label prefix of the blank nodes of instances without an identifier, e.g. `addr` for
+    /// `_:addr0`.
+    pub fn blank_node_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.blank_node_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Add the properties of the property group `name` of the config.
+    pub fn include(mut self, name: impl Into<String>) -> Self {
+        self.include_properties.push(name.into());
+        self
+    }
+
+    /// Map the field to the RDF property `iri`.
+    pub fn property(mut self, field: impl Into<String>, iri: impl Into<String>) -> Self {
+        self.properties.push(PropertyConfig {
+            struct_field: field.into(),
+            rdf_property: iri.into(),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// The config, with the remaining options at their default.
+    pub fn build(self) -> SubjectConfig {
+        SubjectConfig {
+            struct_name: self.struct_name,
+            rdf_type: self.rdf_type,
+            identifier_field: self.identifier_field,
+            identifier_prefix: self.identifier_prefix,
+            properties: self.properties,
+            type_field: self.type_field,
+            blank_node_prefix: self.blank_node_prefix,
+            include_properties: self.include_properties,
+        }
+    }
+}
+
+/// Builds a [`SerializerConfig`], started by [`SerializerConfig::builder`]. Options without a
+/// method keep their default and can be set on the built config.
+#[derive(Debug, Default)]
+pub struct SerializerConfigBuilder {
+    config: SerializerConfig,
+}
+
+impl SerializerConfigBuilder {
+    pub fn base_iri(mut self, iri: impl Into<String>) -> Self {
+        self.config.base_iri = iri.into();
+        self
+    }
+
+    /// Declare the namespace `iri` under the prefix `name`, e.g. `dsp`.
+    pub fn prefix(mut self, name: impl Into<String>, iri: impl Into<String>) -> Self {
+        self.config.namespaces.insert(name.into(), iri.into());
+        self
+    }
+
+    /// Declare the [well-known prefixes](crate::ns::WELL_KNOWN_PREFIXES), except those that
+    /// are declared already.
+    pub fn well_known_prefixes(mut self) -> Self {
+        for (prefix, namespace) in WELL_KNOWN_PREFIXES {
+            self.config
+                .namespaces
+                .entry(prefix.to_string())
+                .or_insert_with(|| namespace.to_string());
+        }
+        self
+    }
+
+    /// The identifier prefix of the subjects that do not set their own.
+    pub fn default_identifier_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config.default_identifier_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Mint skolem IRIs under `base` instead of blank nodes.
+    pub fn skolemize(mut self, base: impl Into<String>) -> Self {
+        self.config.skolemize = Some(base.into());
+        self
+    }
+
+    /// Declare a list of properties that subjects can include by `name`.
+    pub fn property_group(
+        mut self,
+        name: impl Into<String>,
+        properties: Vec<PropertyConfig>,
+    ) -> Self {
+        self.config.property_groups.insert(name.into(), properties);
+        self
+    }
+
+    /// Add the config of a struct, replacing an earlier one of the same struct.
+    pub fn subject(mut self, subject: SubjectConfig) -> Self {
+        self.config
+            .subjects
+            .insert(subject.struct_name.clone(), subject);
+        self
+    }
+
+    /// The config with its prefixed names expanded, checked by
+    /// [`SerializerConfig::validate`].
+    pub fn build(mut self) -> Result<SerializerConfig> {
+        self.config.include_property_groups()?;
+        self.config.expand_prefixed_names()?;
+        self.config.validate()?;
+        Ok(self.config)
+    }
 }
@@ -0,0 +1,165 @@
+use rio_api::model::{
+    BlankNode as RioBlankNode, Literal as RioLiteral, NamedNode as RioNamedNode,
+    Subject as RioSubject, Term as RioTerm, Triple,
+};
+
+/// An owned [RDF triple](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-triple).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedTriple {
+    pub subject: OwnedSubject,
+    /// The IRI of the predicate.
+    pub predicate: String,
+    pub object: OwnedObject,
+}
+
+/// The subject of an [`OwnedTriple`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OwnedSubject {
+    /// An [IRI](https://www.w3.org/TR/rdf11-concepts/#dfn-iri).
+    NamedNode(String),
+    /// A [blank node](https://www.w3.org/TR/rdf11-concepts/#dfn-blank-node) label.
+    BlankNode(String),
+    /// An [RDF-star quoted triple](https://w3c.github.io/rdf-star/cg-spec/2021-12-17.html#dfn-quoted).
+    Triple(Box<OwnedTriple>),
+}
+
+/// The object of an [`OwnedTriple`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OwnedObject {
+    /// An [IRI](https://www.w3.org/TR/rdf11-concepts/#dfn-iri).
+    NamedNode(String),
+    /// A [blank node](https://www.w3.org/TR/rdf11-concepts/#dfn-blank-node) label.
+    BlankNode(String),
+    Literal(OwnedLiteral),
+    /// An [RDF-star quoted triple](https://w3c.github.io/rdf-star/cg-spec/2021-12-17.html#dfn-quoted).
+    Triple(Box<OwnedTriple>),
+}
+
+/// An owned [RDF literal](https://www.w3.org/TR/rdf11-concepts/#dfn-literal).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OwnedLiteral {
+    /// A [simple literal](https://www.w3.org/TR/rdf11-concepts/#dfn-simple-literal) without datatype or language form.
+    Simple { value: String },
+    /// A [language-tagged string](https://www.w3.org/TR/rdf11-concepts/#dfn-language-tagged-string)
+    LanguageTaggedString { value: String, language: String },
+    /// A literal with an explicit datatype IRI
+    Typed { value: String, datatype: String },
+}
+
+impl OwnedTriple {
+    /// Call `f` with a borrowed rio view of this triple, e.g. to hand it to a rio formatter.
+    pub(crate) fn with_rio<R>(&self, f: &mut dyn FnMut(&Triple<'_>) -> R) -> R {
+        self.subject.with_rio(&mut |subject| {
+            self.object.with_rio(&mut |object| {
+                f(&Triple {
+                    subject,
+                    predicate: RioNamedNode {
+                        iri: self.predicate.as_str(),
+                    },
+                    object,
+                })
+            })
+        })
+    }
+}
+
+impl OwnedSubject {
+    pub(crate) fn with_rio<R>(&self, f: &mut dyn FnMut(RioSubject<'_>) -> R) -> R {
+        match self {
+            OwnedSubject::NamedNode(iri) => f(RioNamedNode { iri }.into()),
+            OwnedSubject::BlankNode(id) => f(RioBlankNode { id }.into()),
+            OwnedSubject::Triple(triple) => {
+                triple.with_rio(&mut |triple| f(RioSubject::Triple(triple)))
+            }
+        }
+    }
+}
+
+impl OwnedObject {
+    pub(crate) fn with_rio<R>(&self, f: &mut dyn FnMut(RioTerm<'_>) -> R) -> R {
+        match self {
+            OwnedObject::NamedNode(iri) => f(RioNamedNode { iri }.into()),
+            OwnedObject::BlankNode(id) => f(RioBlankNode { id }.into()),
+            OwnedObject::Literal(literal) => f(literal.as_rio().into()),
+            OwnedObject::Triple(triple) => {
+                triple.with_rio(&mut |triple| f(RioTerm::Triple(triple)))
+            }
+        }
+    }
+}
+
+impl OwnedLiteral {
+    /// The [lexical form](https://www.w3.org/TR/rdf11-concepts/#dfn-lexical-form) of the
+    /// literal.
+    pub(crate) fn lexical_form(&self) -> &str {
+        match self {
+            OwnedLiteral::Simple { value }
+            | OwnedLiteral::LanguageTaggedString { value, .. }
+            | OwnedLiteral::Typed { value, .. } => value,
+        }
+    }
+
+    pub(crate) fn as_rio(&self) -> RioLiteral<'_> {
+        match self {
+            OwnedLiteral::Simple { value } => RioLiteral::Simple { value },
+            OwnedLiteral::LanguageTaggedString { value, language } => {
+                RioLiteral::LanguageTaggedString { value, language }
+            }
+            OwnedLiteral::Typed { value, datatype } => RioLiteral::Typed {
+                value,
+                datatype: RioNamedNode { iri: datatype },
+            },
+        }
+    }
+}
+
+impl From<&Triple<'_>> for OwnedTriple {
+    fn from(triple: &Triple<'_>) -> Self {
+        OwnedTriple {
+            subject: triple.subject.into(),
+            predicate: triple.predicate.iri.to_owned(),
+            object: triple.object.into(),
+        }
+    }
+}
+
+impl From<RioSubject<'_>> for OwnedSubject {
+    fn from(subject: RioSubject<'_>) -> Self {
+        match subject {
+            RioSubject::NamedNode(node) => OwnedSubject::NamedNode(node.iri.to_owned()),
+            RioSubject::BlankNode(node) => OwnedSubject::BlankNode(node.id.to_owned()),
+            RioSubject::Triple(triple) => OwnedSubject::Triple(Box::new(triple.into())),
+        }
+    }
+}
+
+impl From<RioTerm<'_>> for OwnedObject {
+    fn from(term: RioTerm<'_>) -> Self {
+        match term {
+            RioTerm::NamedNode(node) => OwnedObject::NamedNode(node.iri.to_owned()),
+            RioTerm::BlankNode(node) => OwnedObject::BlankNode(node.id.to_owned()),
+            RioTerm::Literal(literal) => OwnedObject::Literal(literal.into()),
+            RioTerm::Triple(triple) => OwnedObject::Triple(Box::new(triple.into())),
+        }
+    }
+}
+
+impl From<RioLiteral<'_>> for OwnedLiteral {
+    fn from(literal: RioLiteral<'_>) -> Self {
+        match literal {
+            RioLiteral::Simple { value } => OwnedLiteral::Simple {
+                value: value.to_owned(),
+            },
+            RioLiteral::LanguageTaggedString { value, language } => {
+                OwnedLiteral::LanguageTaggedString {
+                    value: value.to_owned(),
+                    language: language.to_owned(),
+                }
+            }
+            RioLiteral::Typed { value, datatype } => OwnedLiteral::Typed {
+                value: value.to_owned(),
+                datatype: datatype.iri.to_owned(),
+            },
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+use rio_api::model::{BlankNode as RioBlankNode, Literal as RioLiteral, NamedNode as RioNamedNode};
+
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+
+/// Turtle output that writes IRIs as prefixed names wherever a registered namespace allows it.
+/// Terms are escaped by the `Display` implementations of rio.
+pub(crate) struct Compact<'a> {
+    triples: &'a [OwnedTriple],
+    /// The usable namespaces, ordered by prefix.
+    prefixes: Vec<(&'a str, &'a str)>,
+    /// Whether the namespaces are declared with `@prefix` before the triples.
+    declare_prefixes: bool,
+}
+
+impl<'a> Compact<'a> {
+    pub(crate) fn new(triples: &'a [OwnedTriple], namespaces: &'a HashMap<String, String>) -> Self {
+        let mut prefixes: Vec<_> = namespaces
+            .iter()
+            .filter(|(prefix, _)| is_pn_prefix(prefix))
+            .map(|(prefix, namespace)| (prefix.as_str(), namespace.as_str()))
+            .collect();
+        prefixes.sort_unstable();
+        Compact {
+            triples,
+            prefixes,
+            declare_prefixes: true,
+        }
+    }
+
+    /// Leave out the `@prefix` declarations, e.g. for the part of a document that follows
+    /// them.
+    #[cfg(any(feature = "async", feature = "rayon"))]
+    pub(crate) fn without_prefixes(self) -> Self {
+        Compact {
+            declare_prefixes: false,
+            ..self
+        }
+    }
+
+    /// Write a single triple, e.g. as soon as it is complete, after the `@prefix` declarations
+    /// of an empty document.
+    pub(crate) fn write_triple(
+        &self,
+        writer: &mut dyn io::Write,
+        triple: &OwnedTriple,
+    ) -> io::Result<()> {
+        struct Statement<'c, 'a>(&'c Compact<'a>, &'c OwnedTriple);
+
+        impl fmt::Display for Statement<'_, '_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt_triple(f, self.1)?;
+                f.write_str(" .\n")
+            }
+        }
+
+        write!(writer, "{}", Statement(self, triple))
+    }
+
+    fn fmt_iri(&self, f: &mut fmt::Formatter<'_>, iri: &str) -> fmt::Result {
+        // The longest matching namespace leaves the shortest local part.
+        let compacted = self
+            .prefixes
+            .iter()
+            .filter_map(|(prefix, namespace)| Some((prefix, iri.strip_prefix(namespace)?)))
+            .filter(|(_, local)| is_pn_local(local))
+            .min_by_key(|(_, local)| local.len());
+        match compacted {
+            Some((prefix, local)) => write!(f, "{prefix}:{local}"),
+            None => write!(f, "{}", RioNamedNode { iri }),
+        }
+    }
+
+    fn fmt_triple(&self, f: &mut fmt::Formatter<'_>, triple: &OwnedTriple) -> fmt::Result {
+        match &triple.subject {
+            OwnedSubject::NamedNode(iri) => self.fmt_iri(f, iri)?,
+            OwnedSubject::BlankNode(id) => write!(f, "{}", RioBlankNode { id })?,
+            OwnedSubject::Triple(quoted) => self.fmt_quoted(f, quoted)?,
+        }
+        f.write_str(" ")?;
+        self.fmt_iri(f, &triple.predicate)?;
+        f.write_str(" ")?;
+        match &triple.object {
+            OwnedObject::NamedNode(iri) => self.fmt_iri(f, iri),
+            OwnedObject::BlankNode(id) => write!(f, "{}", RioBlankNode { id }),
+            OwnedObject::Literal(OwnedLiteral::Typed { value, datatype }) => {
+                write!(f, "{}^^", RioLiteral::Simple { value })?;
+                self.fmt_iri(f, datatype)
+            }
+            OwnedObject::Literal(OwnedLiteral::Simple { value }) => {
+                write!(f, "{}", RioLiteral::Simple { value })
+            }
+            OwnedObject::Literal(OwnedLiteral::LanguageTaggedString { value, language }) => {
+                write!(
+                    f,
+                    "{}",
+                    RioLiteral::LanguageTaggedString { value, language }
+                )
+            }
+            OwnedObject::Triple(quoted) => self.fmt_quoted(f, quoted),
+        }
+    }
+
+    fn fmt_quoted(&self, f: &mut fmt::Formatter<'_>, triple: &OwnedTriple) -> fmt::Result {
+        f.write_str("<< ")?;
+        self.fmt_triple(f, triple)?;
+        f.write_str(" >>")
+    }
+}
+
+impl fmt::Display for Compact<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.declare_prefixes {
+            for (prefix, namespace) in &self.prefixes {
+                writeln!(f, "@prefix {prefix}: {} .", RioNamedNode { iri: namespace })?;
+            }
+        }
+        for triple in self.triples {
+            self.fmt_triple(f, triple)?;
+            f.write_str(" .\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// [PN_CHARS_BASE](https://www.w3.org/TR/turtle/#grammar-production-PN_CHARS_BASE)
+fn is_pn_chars_base(c: char) -> bool {
+    matches!(c,
+        'A'..='Z'
+        | 'a'..='z'
+        | '\u{00C0}'..='\u{00D6}'
+        | '\u{00D8}'..='\u{00F6}'
+        | '\u{00F8}'..='\u{02FF}'
+        | '\u{0370}'..='\u{037D}'
+        | '\u{037F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}')
+}
+
+/// [PN_CHARS_U](https://www.w3.org/TR/turtle/#grammar-production-PN_CHARS_U)
+fn is_pn_chars_u(c: char) -> bool {
+    c == '_' || is_pn_chars_base(c)
+}
+
+/// [PN_CHARS](https://www.w3.org/TR/turtle/#grammar-production-PN_CHARS)
+fn is_pn_chars(c: char) -> bool {
+    is_pn_chars_u(c)
+        || matches!(c, '-' | '0'..='9' | '\u{00B7}' | '\u{0300}'..='\u{036F}' | '\u{203F}'..='\u{2040}')
+}
+
+/// Whether `prefix` is a valid [PN_PREFIX](https://www.w3.org/TR/turtle/#grammar-production-PN_PREFIX)
+/// or empty.
+fn is_pn_prefix(prefix: &str) -> bool {
+    let mut chars = prefix.chars();
+    match chars.next() {
+        None => true,
+        Some(first) => {
+            is_pn_chars_base(first)
+                && chars.all(|c| is_pn_chars(c) || c == '.')
+                && !prefix.ends_with('.')
+        }
+    }
+}
+
+/// Whether `local` can be written as a [PN_LOCAL](https://www.w3.org/TR/turtle/#grammar-production-PN_LOCAL)
+/// without escapes. Percent-encoded octets are allowed, characters that would need a backslash
+/// escape are not.
+fn is_pn_local(local: &str) -> bool {
+    let chars: Vec<char> = local.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '%' {
+            match chars.get(i + 1..i + 3) {
+                Some(hex) if hex.iter().all(char::is_ascii_hexdigit) => i += 3,
+                _ => return false,
+            }
+            continue;
+        }
+        let valid = if i == 0 {
+            is_pn_chars_u(c) || c == ':' || c.is_ascii_digit()
+        } else if i == chars.len() - 1 {
+            is_pn_chars(c) || c == ':'
+        } else {
+            is_pn_chars(c) || c == ':' || c == '.'
+        };
+        if !valid {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
@@ -0,0 +1,504 @@
+//! A dynamic model of RDF terms and subjects, for data without a matching Rust type.
+//!
+//! [`Term`], [`Literal`] and [`Node`] can be deserialized from any input and serialized with any
+//! [`SerializerConfig`](crate::SerializerConfig), keeping datatypes and language tags exactly as
+//! they are. In other serde formats they are written in N-Triples syntax.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use rio_api::model::NamedNode;
+use rio_api::parser::TriplesParser;
+use rio_turtle::{NTriplesParser, TurtleError};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+
+use crate::error::{Error, Result};
+use crate::triple::{OwnedLiteral, OwnedObject, OwnedSubject, OwnedTriple};
+
+/// The name under which [`Term`] is recognized by the serializer and deserializer.
+pub(crate) const TERM: &str = "$serde_rdf::private::Term";
+/// The name under which [`Literal`] is recognized by the serializer and deserializer.
+pub(crate) const LITERAL: &str = "$serde_rdf::private::Literal";
+/// The name under which [`Node`] is recognized by the serializer and deserializer.
+pub(crate) const NODE: &str = "$serde_rdf::private::Node";
+
+/// An IRI, blank node or literal.
+///
+/// Example:
+/// ```
+/// use serde_rdf::value::Term;
+/// let size: Term = "\"42\"^^<http://www.w3.org/2001/XMLSchema#integer>".parse().unwrap();
+/// assert_eq!(
+///     size,
+///     Term::Literal {
+///         value: "42".to_string(),
+///         datatype: Some("http://www.w3.org/2001/XMLSchema#integer".to_string()),
+///         language: None,
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Term {
+    /// An [IRI](https://www.w3.org/TR/rdf11-concepts/#dfn-iri).
+    Iri(String),
+    /// A [blank node](https://www.w3.org/TR/rdf11-concepts/#dfn-blank-node) label.
+    BlankNode(String),
+    /// A [literal](https://www.w3.org/TR/rdf11-concepts/#dfn-literal). A literal without
+    /// datatype and language is a simple literal, the language takes precedence over the
+    /// datatype.
+    Literal {
+        /// The [lexical form](https://www.w3.org/TR/rdf11-concepts/#dfn-lexical-form).
+        value: String,
+        /// The [datatype IRI](https://www.w3.org/TR/rdf11-concepts/#dfn-datatype-iri).
+        datatype: Option<String>,
+        /// The [language tag](https://www.w3.org/TR/rdf11-concepts/#dfn-language-tag).
+        language: Option<String>,
+    },
+}
+
+impl Term {
+    pub(crate) fn from_object(object: &OwnedObject) -> Result<Term> {
+        Ok(match object {
+            OwnedObject::NamedNode(iri) => Term::Iri(iri.clone()),
+            OwnedObject::BlankNode(id) => Term::BlankNode(id.clone()),
+            OwnedObject::Literal(OwnedLiteral::Simple { value }) => Term::Literal {
+                value: value.clone(),
+                datatype: None,
+                language: None,
+            },
+            OwnedObject::Literal(OwnedLiteral::LanguageTaggedString { value, language }) => {
+                Term::Literal {
+                    value: value.clone(),
+                    datatype: None,
+                    language: Some(language.clone()),
+                }
+            }
+            OwnedObject::Literal(OwnedLiteral::Typed { value, datatype }) => Term::Literal {
+                value: value.clone(),
+                datatype: Some(datatype.clone()),
+                language: None,
+            },
+            OwnedObject::Triple(_) => {
+                return Err(Error::new(
+                    "quoted triples cannot be represented as a value::Term",
+                ))
+            }
+        })
+    }
+
+    pub(crate) fn to_object(&self) -> OwnedObject {
+        match self {
+            Term::Iri(iri) => OwnedObject::NamedNode(iri.clone()),
+            Term::BlankNode(id) => OwnedObject::BlankNode(id.clone()),
+            Term::Literal {
+                value,
+                datatype,
+                language,
+            } => OwnedObject::Literal(literal(value, datatype.as_deref(), language.as_deref())),
+        }
+    }
+
+    fn to_subject(&self) -> Result<OwnedSubject> {
+        match self {
+            Term::Iri(iri) => Ok(OwnedSubject::NamedNode(iri.clone())),
+            Term::BlankNode(id) => Ok(OwnedSubject::BlankNode(id.clone())),
+            Term::Literal { value, .. } => Err(Error::Message(format!(
+                "a literal cannot identify a node: {value:?}"
+            ))),
+        }
+    }
+}
+
+/// The literal with the given lexical form, of which the language takes precedence over the
+/// datatype.
+pub(crate) fn literal(value: &str, datatype: Option<&str>, language: Option<&str>) -> OwnedLiteral {
+    let value = value.to_owned();
+    match (datatype, language) {
+        (_, Some(language)) => OwnedLiteral::LanguageTaggedString {
+            value,
+            language: language.to_owned(),
+        },
+        (Some(datatype), None) => OwnedLiteral::Typed {
+            value,
+            datatype: datatype.to_owned(),
+        },
+        (None, None) => OwnedLiteral::Simple { value },
+    }
+}
+
+/// The term in N-Triples syntax, e.g. `<https://example.org/p>` or `"Bern"@de`.
+impl Display for Term {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_object().with_rio(&mut |term| write!(f, "{term}"))
+    }
+}
+
+impl FromStr for Term {
+    type Err = Error;
+
+    fn from_str(term: &str) -> Result<Self> {
+        let triples = parse(&format!("<urn:term> <urn:term> {term} .\n"))?;
+        match triples.as_slice() {
+            [triple] => Term::from_object(&triple.object),
+            _ => Err(Error::Message(format!("invalid N-Triples term: {term:?}"))),
+        }
+    }
+}
+
+/// A literal as it is in the data, for fields whose datatype has no Rust counterpart, e.g.
+/// `geo:wktLiteral`. The lexical form is neither checked nor normalized.
+///
+/// Example:
+/// ```
+/// use serde_rdf::value::Literal;
+/// let location = Literal::typed(
+///     "POINT(7.4474 46.948)",
+///     "http://www.opengis.net/ont/geosparql#wktLiteral",
+/// );
+/// assert_eq!(
+///     location.to_string(),
+///     r#""POINT(7.4474 46.948)"^^<http://www.opengis.net/ont/geosparql#wktLiteral>"#
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Literal {
+    /// The [lexical form](https://www.w3.org/TR/rdf11-concepts/#dfn-lexical-form).
+    pub value: String,
+    /// The [datatype IRI](https://www.w3.org/TR/rdf11-concepts/#dfn-datatype-iri).
+    pub datatype: Option<String>,
+    /// The [language tag](https://www.w3.org/TR/rdf11-concepts/#dfn-language-tag), which takes
+    /// precedence over the datatype.
+    pub language: Option<String>,
+}
+
+impl Literal {
+    /// A literal with the given datatype IRI.
+    pub fn typed(value: impl Into<String>, datatype: impl Into<String>) -> Self {
+        Literal {
+            value: value.into(),
+            datatype: Some(datatype.into()),
+            language: None,
+        }
+    }
+
+    /// A language-tagged string.
+    pub fn language_tagged(value: impl Into<String>, language: impl Into<String>) -> Self {
+        Literal {
+            value: value.into(),
+            datatype: None,
+            language: Some(language.into()),
+        }
+    }
+}
+
+impl From<Literal> for Term {
+    fn from(literal: Literal) -> Self {
+        Term::Literal {
+            value: literal.value,
+            datatype: literal.datatype,
+            language: literal.language,
+        }
+    }
+}
+
+/// IRIs and blank nodes are returned as the error.
+impl TryFrom<Term> for Literal {
+    type Error = Term;
+
+    fn try_from(term: Term) -> Result<Self, Term> {
+        match term {
+            Term::Literal {
+                value,
+                datatype,
+                language,
+            } => Ok(Literal {
+                value,
+                datatype,
+                language,
+            }),
+            term => Err(term),
+        }
+    }
+}
+
+/// The literal in N-Triples syntax, e.g. `"Bern"@de`.
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let literal = literal(
+            &self.value,
+            self.datatype.as_deref(),
+            self.language.as_deref(),
+        );
+        write!(f, "{}", literal.as_rio())
+    }
+}
+
+impl FromStr for Literal {
+    type Err = Error;
+
+    fn from_str(literal: &str) -> Result<Self> {
+        Literal::try_from(literal.parse::<Term>()?)
+            .map_err(|term| Error::Message(format!("expected a literal, found: {term}")))
+    }
+}
+
+/// A subject with all its statements.
+///
+/// Example:
+/// ```
+/// use serde_rdf::value::{Node, Term};
+/// let input = r#"
+///     @prefix dsp: <https://ns.dasch.swiss/repository#> .
+///     <https://ark.dasch.swiss/ark:/72163/1/081C> dsp:hasName "Hôtel de Musique Bern"@de .
+/// "#;
+/// let node: Node = serde_rdf::from_str(input).unwrap();
+/// assert_eq!(node.id, Term::Iri("https://ark.dasch.swiss/ark:/72163/1/081C".to_string()));
+/// assert_eq!(
+///     node.properties["https://ns.dasch.swiss/repository#hasName"][0].to_string(),
+///     r#""Hôtel de Musique Bern"@de"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    /// The IRI or blank node of the subject.
+    pub id: Term,
+    /// The objects of every predicate IRI, in the order of the statements.
+    pub properties: BTreeMap<String, Vec<Term>>,
+}
+
+impl Node {
+    pub(crate) fn from_statements(
+        subject: &OwnedSubject,
+        statements: &[(String, OwnedObject)],
+    ) -> Result<Node> {
+        let id = match subject {
+            OwnedSubject::NamedNode(iri) => Term::Iri(iri.clone()),
+            OwnedSubject::BlankNode(id) => Term::BlankNode(id.clone()),
+            OwnedSubject::Triple(_) => {
+                return Err(Error::new(
+                    "quoted triples cannot be represented as a value::Node",
+                ))
+            }
+        };
+        let mut properties: BTreeMap<String, Vec<Term>> = BTreeMap::new();
+        for (predicate, object) in statements {
+            properties
+                .entry(predicate.clone())
+                .or_default()
+                .push(Term::from_object(object)?);
+        }
+        Ok(Node { id, properties })
+    }
+
+    /// The statements of the node.
+    pub(crate) fn triples(&self) -> Result<Vec<OwnedTriple>> {
+        let subject = self.id.to_subject()?;
+        Ok(self
+            .properties
+            .iter()
+            .flat_map(|(predicate, objects)| {
+                objects.iter().map(|object| OwnedTriple {
+                    subject: subject.clone(),
+                    predicate: predicate.clone(),
+                    object: object.to_object(),
+                })
+            })
+            .collect())
+    }
+}
+
+/// The statements of the node as an N-Triples document.
+impl Display for Node {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (predicate, objects) in &self.properties {
+            let predicate = NamedNode { iri: predicate };
+            for object in objects {
+                writeln!(f, "{} {predicate} {object} .", self.id)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Node {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let triples = parse(input)?;
+        let subject = &triples
+            .first()
+            .ok_or_else(|| Error::new("a value::Node needs at least one statement"))?
+            .subject;
+        if let Some(other) = triples.iter().find(|triple| triple.subject != *subject) {
+            return Err(Error::Message(format!(
+                "expected the statements of a single subject, found: {:?} and {:?}",
+                subject, other.subject
+            )));
+        }
+        let statements: Vec<_> = triples
+            .iter()
+            .map(|triple| (triple.predicate.clone(), triple.object.clone()))
+            .collect();
+        Node::from_statements(subject, &statements)
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<OwnedTriple>> {
+    let mut triples = Vec::new();
+    NTriplesParser::new(input.as_bytes()).parse_all(&mut |triple| -> std::result::Result<
+        (),
+        TurtleError,
+    > {
+        triples.push(OwnedTriple::from(&triple));
+        Ok(())
+    })?;
+    Ok(triples)
+}
+
+impl Serialize for Term {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(TERM, &self.to_string())
+    }
+}
+
+impl Serialize for Literal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(LITERAL, &self.to_string())
+    }
+}
+
+impl Serialize for Node {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if let Term::Literal { value, .. } = &self.id {
+            return Err(ser::Error::custom(format!(
+                "a literal cannot identify a node: {value:?}"
+            )));
+        }
+        serializer.serialize_newtype_struct(NODE, &self.to_string())
+    }
+}
+
+/// Reads a value from its N-Triples syntax, either directly or wrapped in a newtype struct.
+struct NTriplesVisitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for NTriplesVisitor<T>
+where
+    T: FromStr<Err = Error>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("RDF in N-Triples syntax")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        value.parse().map_err(E::custom)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Term {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(TERM, NTriplesVisitor(std::marker::PhantomData))
+    }
+}
+
+impl<'de> Deserialize<'de> for Literal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(LITERAL, NTriplesVisitor(std::marker::PhantomData))
+    }
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(NODE, NTriplesVisitor(std::marker::PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Node, Term};
+    use crate::{from_str, to_string, SerializerConfig};
+
+    #[test]
+    fn test_value_model() {
+        const DSP: &str = "https://ns.dasch.swiss/repository#";
+
+        let input = r#"
+            @prefix dsp: <https://ns.dasch.swiss/repository#> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+            <https://ark.dasch.swiss/ark:/72163/1/081C> dsp:hasSize "42"^^xsd:integer ;
+                dsp:hasDescription "Das Hôtel de Musique"@de, "The Hôtel de Musique"@en ;
+                dsp:hasDataset <https://ark.dasch.swiss/ark:/72163/1/081C/d1> .
+        "#;
+        let node: Node = from_str(input).unwrap();
+        assert_eq!(
+            node.id,
+            Term::Iri("https://ark.dasch.swiss/ark:/72163/1/081C".to_string())
+        );
+        assert_eq!(
+            node.properties[&format!("{DSP}hasSize")],
+            [Term::Literal {
+                value: "42".to_string(),
+                datatype: Some("http://www.w3.org/2001/XMLSchema#integer".to_string()),
+                language: None,
+            }]
+        );
+        assert_eq!(
+            node.properties[&format!("{DSP}hasDescription")][1],
+            Term::Literal {
+                value: "The Hôtel de Musique".to_string(),
+                datatype: None,
+                language: Some("en".to_string()),
+            }
+        );
+        assert_eq!(
+            node.properties[&format!("{DSP}hasDataset")],
+            [Term::Iri(
+                "https://ark.dasch.swiss/ark:/72163/1/081C/d1".to_string()
+            )]
+        );
+
+        let output = to_string(&node, &SerializerConfig::default()).unwrap();
+        assert_eq!(from_str::<Node>(&output).unwrap(), node);
+
+        // Terms are written and parsed in N-Triples syntax.
+        for terms in node.properties.values() {
+            for term in terms {
+                assert_eq!(&term.to_string().parse::<Term>().unwrap(), term);
+            }
+        }
+        let term: Term = from_str(input).unwrap();
+        assert_eq!(term, node.id);
+    }
+}
@@ -0,0 +1,226 @@
+//! Modules for `#[serde(with = "...")]` on fields whose type is read from the datatype of a
+//! literal as well as its lexical form.
+//!
+//! The deserializer recognizes the fields by the newtype struct the modules wrap them in and
+//! checks their literals before handing the lexical form to the field type. Other serde formats
+//! see the field type as it is.
+
+use std::fmt::{self, Formatter};
+use std::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, Visitor};
+
+/// The name under which fields of [`chrono`] are recognized by the deserializer.
+#[cfg(feature = "chrono")]
+pub(crate) const CHRONO: &str = "$serde_rdf::private::Chrono";
+/// The name under which fields of [`decimal`] are recognized by the deserializer.
+#[cfg(feature = "decimal")]
+pub(crate) const DECIMAL: &str = "$serde_rdf::private::Decimal";
+/// The name under which fields of [`duration`] are recognized by the deserializer.
+pub(crate) const DURATION: &str = "$serde_rdf::private::Duration";
+/// The name under which fields of [`url`] are recognized by the deserializer.
+#[cfg(feature = "url")]
+pub(crate) const URL: &str = "$serde_rdf::private::Url";
+
+/// The names of the newtype structs of the modules.
+pub(crate) const NAMES: &[&str] = &[
+    #[cfg(feature = "chrono")]
+    CHRONO,
+    #[cfg(feature = "decimal")]
+    DECIMAL,
+    DURATION,
+    #[cfg(feature = "url")]
+    URL,
+];
+
+/// Deserialize `T` wrapped in the newtype struct `name`.
+fn deserialize_as<'de, D, T>(deserializer: D, name: &'static str) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    deserializer.deserialize_newtype_struct(name, Newtype(PhantomData))
+}
+
+/// Hands the content of a newtype struct to `T`.
+struct Newtype<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for Newtype<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("a newtype struct")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Read `chrono` types such as `DateTime<FixedOffset>`, `NaiveDateTime` and `NaiveDate`,
+/// checking that `xsd:dateTime` and `xsd:date` literals are valid for chrono first, so that the
+/// error names the predicate.
+///
+/// Example:
+/// ```
+/// use chrono::NaiveDate;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Event {
+///     #[serde(with = "serde_rdf::with::chrono")]
+///     day: NaiveDate,
+/// }
+///
+/// let input = r#"<https://example.org/e1> <https://example.org/ns#day>
+///     "2023-05-01"^^<http://www.w3.org/2001/XMLSchema#date> ."#;
+/// let event: Event = serde_rdf::from_str(input).unwrap();
+/// assert_eq!(event.day, NaiveDate::from_ymd_opt(2023, 5, 1).unwrap());
+/// ```
+#[cfg(feature = "chrono")]
+pub mod chrono {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_as(deserializer, super::CHRONO)
+    }
+}
+
+/// Read [`rust_decimal::Decimal`] exactly from `xsd:decimal` and integer literals, keeping the
+/// scale of the lexical form, e.g. `0.10`, rather than through a float.
+///
+/// Example:
+/// ```
+/// use rust_decimal::Decimal;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Invoice {
+///     #[serde(with = "serde_rdf::with::decimal")]
+///     amount: Decimal,
+/// }
+///
+/// let input = r#"<https://example.org/i1> <https://example.org/ns#amount>
+///     "0.10"^^<http://www.w3.org/2001/XMLSchema#decimal> ."#;
+/// let invoice: Invoice = serde_rdf::from_str(input).unwrap();
+/// assert_eq!(invoice.amount.to_string(), "0.10");
+/// ```
+#[cfg(feature = "decimal")]
+pub mod decimal {
+    use rust_decimal::Decimal;
+    use serde::{Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(decimal: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Serialize::serialize(decimal, serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_as(deserializer, super::DECIMAL)
+    }
+}
+
+/// Read [`std::time::Duration`] from `xsd:duration` and `xsd:dayTimeDuration` literals
+/// without years, months or a sign, and write it as the lexical form of an `xsd:duration`.
+///
+/// Example:
+/// ```
+/// use std::time::Duration;
+///
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Dataset {
+///     #[serde(with = "serde_rdf::with::duration")]
+///     window: Duration,
+/// }
+///
+/// let input = r#"<https://example.org/d> <https://example.org/ns#window>
+///     "PT1H30M"^^<http://www.w3.org/2001/XMLSchema#duration> ."#;
+/// let dataset: Dataset = serde_rdf::from_str(input).unwrap();
+/// assert_eq!(dataset.window, Duration::from_secs(5400));
+/// ```
+pub mod duration {
+    use std::time::Duration;
+
+    use serde::de::{self, Deserializer};
+    use serde::ser::Serializer;
+
+    use crate::duration::XsdDuration;
+
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&XsdDuration::from(*duration))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let duration: XsdDuration = super::deserialize_as(deserializer, super::DURATION)?;
+        Duration::try_from(duration).map_err(de::Error::custom)
+    }
+}
+
+/// Read [`url::Url`](::url::Url) only from IRIs and `xsd:anyURI` literals, rather than from any
+/// string.
+///
+/// Example:
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Link {
+///     #[serde(with = "serde_rdf::with::url")]
+///     homepage: url::Url,
+/// }
+///
+/// let input = r#"<https://example.org/d1> <https://example.org/ns#hasHomepage>
+///     <https://example.org/projects/081C> ."#;
+/// let link: Link = serde_rdf::from_str(input).unwrap();
+/// assert_eq!(link.homepage.as_str(), "https://example.org/projects/081C");
+/// ```
+#[cfg(feature = "url")]
+pub mod url {
+    use ::url::Url;
+    use serde::{Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(url: &Url, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        url.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Url, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_as(deserializer, super::URL)
+    }
+}
@@ -0,0 +1,177 @@
+//! The datatypes a property can be configured with.
+
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::error::{Error, Result};
+use crate::ns::{
+    RDF_LANG_STRING, XSD, XSD_ANY_URI, XSD_BASE64_BINARY, XSD_BOOLEAN, XSD_DATE, XSD_DATE_TIME,
+    XSD_DECIMAL, XSD_DOUBLE, XSD_INTEGER, XSD_STRING,
+};
+
+/// The datatype of the literals of a property, see
+/// [`PropertyConfig::datatype`](crate::PropertyConfig::datatype).
+///
+/// It is parsed from its local name, e.g. `integer`, its prefixed name, e.g. `xsd:integer`,
+/// or its IRI. Other prefixed names and IRIs are kept as a `Custom` datatype, e.g. one of an
+/// ontology or an XSD datatype without a variant, such as `xsd:gYear`.
+///
+/// Example:
+/// ```
+/// use serde_rdf::XsdType;
+/// let datatype: XsdType = "dateTime".parse().unwrap();
+/// assert_eq!(datatype, XsdType::DateTime);
+/// assert_eq!(datatype.as_iri(), "http://www.w3.org/2001/XMLSchema#dateTime");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum XsdType {
+    String,
+    Boolean,
+    Integer,
+    Decimal,
+    Double,
+    Date,
+    DateTime,
+    AnyUri,
+    Base64Binary,
+    /// `rdf:langString`, the datatype of language-tagged strings.
+    LangString,
+    /// Any other datatype, given as an IRI or a prefixed name.
+    Custom(String),
+}
+
+/// The datatypes with a variant, by local name.
+const XSD_TYPES: &[(&str, XsdType)] = &[
+    ("string", XsdType::String),
+    ("boolean", XsdType::Boolean),
+    ("integer", XsdType::Integer),
+    ("decimal", XsdType::Decimal),
+    ("double", XsdType::Double),
+    ("date", XsdType::Date),
+    ("dateTime", XsdType::DateTime),
+    ("anyURI", XsdType::AnyUri),
+    ("base64Binary", XsdType::Base64Binary),
+];
+
+impl XsdType {
+    /// The IRI of the datatype. It is the prefixed name for a `Custom` datatype given as one,
+    /// until the config is expanded with
+    /// [`SerializerConfig::expand_prefixed_names`](crate::SerializerConfig::expand_prefixed_names).
+    pub fn as_iri(&self) -> &str {
+        match self {
+            XsdType::Custom(iri) => iri,
+            _ => self.static_iri().unwrap_or_default(),
+        }
+    }
+
+    /// The IRI of the datatype, borrowed unless it is `Custom`.
+    pub(crate) fn to_iri(&self) -> Cow<'static, str> {
+        match self.static_iri() {
+            Some(iri) => Cow::Borrowed(iri),
+            None => Cow::Owned(self.as_iri().to_owned()),
+        }
+    }
+
+    /// The IRI of a datatype other than `Custom`, one of the constants of the `ns` module.
+    fn static_iri(&self) -> Option<&'static str> {
+        Some(match self {
+            XsdType::String => XSD_STRING,
+            XsdType::Boolean => XSD_BOOLEAN,
+            XsdType::Integer => XSD_INTEGER,
+            XsdType::Decimal => XSD_DECIMAL,
+            XsdType::Double => XSD_DOUBLE,
+            XsdType::Date => XSD_DATE,
+            XsdType::DateTime => XSD_DATE_TIME,
+            XsdType::AnyUri => XSD_ANY_URI,
+            XsdType::Base64Binary => XSD_BASE64_BINARY,
+            XsdType::LangString => RDF_LANG_STRING,
+            XsdType::Custom(_) => return None,
+        })
+    }
+
+    /// The IRI of a `Custom` datatype, to expand it in place.
+    pub(crate) fn custom_iri_mut(&mut self) -> Option<&mut String> {
+        match self {
+            XsdType::Custom(iri) => Some(iri),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for XsdType {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        if ["langString", "rdf:langString", RDF_LANG_STRING].contains(&value) {
+            return Ok(XsdType::LangString);
+        }
+        let local = value
+            .strip_prefix("xsd:")
+            .or_else(|| value.strip_prefix(XSD))
+            .unwrap_or(value);
+        if let Some((_, datatype)) = XSD_TYPES.iter().find(|(name, _)| *name == local) {
+            return Ok(datatype.clone());
+        }
+        // A local name without a prefix has to be one of the variants, which catches typos.
+        if !value.contains(':') {
+            return Err(Error::Message(format!("unknown XSD datatype: {value}")));
+        }
+        Ok(XsdType::Custom(value.to_string()))
+    }
+}
+
+/// The local name of a datatype with a variant, the IRI or prefixed name of others.
+impl Display for XsdType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            XsdType::LangString => f.write_str("langString"),
+            XsdType::Custom(iri) => f.write_str(iri),
+            datatype => {
+                let name = XSD_TYPES
+                    .iter()
+                    .find(|(_, d)| d == datatype)
+                    .map_or("", |(name, _)| name);
+                f.write_str(name)
+            }
+        }
+    }
+}
+
+impl Serialize for XsdType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for XsdType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct XsdTypeVisitor;
+
+        impl Visitor<'_> for XsdTypeVisitor {
+            type Value = XsdType;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("a datatype name or IRI")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<XsdType, E>
+            where
+                E: de::Error,
+            {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(XsdTypeVisitor)
+    }
+}
@@ -0,0 +1,145 @@
+use assert_cmd::Command;
+
+const CONFIG: &str = "tests/fixtures/mapping.yaml";
+
+fn serde_rdf() -> Command {
+    Command::cargo_bin("serde-rdf").unwrap()
+}
+
+fn stdout(command: &mut Command) -> String {
+    let output = command.assert().success().get_output().stdout.clone();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn test_serialize() {
+    let turtle = stdout(serde_rdf().args([
+        "serialize",
+        "--config",
+        CONFIG,
+        "--input",
+        "tests/fixtures/record.json",
+        "--format",
+        "turtle",
+    ]));
+    assert!(
+        turtle.contains("<https://ark.dasch.swiss/ark:/72163/1/081C>"),
+        "{turtle}"
+    );
+    assert!(turtle.contains("Hôtel de Musique Bern"), "{turtle}");
+
+    let ntriples = stdout(serde_rdf().args([
+        "serialize",
+        "--config",
+        CONFIG,
+        "--input",
+        "tests/fixtures/record.json",
+        "--format",
+        "application/n-triples",
+    ]));
+    assert!(ntriples.contains(
+        "<https://ark.dasch.swiss/ark:/72163/1/081C> \
+         <https://ns.dasch.swiss/repository#hasSize> \
+         \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> ."
+    ));
+
+    // The record is read from stdin without `--input`.
+    let record = std::fs::read_to_string("tests/fixtures/record.json").unwrap();
+    let output = stdout(
+        serde_rdf()
+            .args(["serialize", "--config", CONFIG])
+            .write_stdin(record),
+    );
+    assert_eq!(output, turtle);
+}
+
+#[test]
+fn test_deserialize() {
+    let json = stdout(serde_rdf().args([
+        "deserialize",
+        "--config",
+        CONFIG,
+        "--input",
+        "tests/fixtures/data.ttl",
+        "--subject",
+        "https://ark.dasch.swiss/ark:/72163/1/0803",
+    ]));
+    let record: serde_json::Value = serde_json::from_str(&json).unwrap();
+    // The identifier prefix of the config is stripped, so that the record serializes back.
+    assert_eq!(record["id"], "0803");
+    assert_eq!(record["name"], "Incunabula");
+    assert_eq!(record["shortcode"], "0803");
+    assert_eq!(record["size"], 7);
+}
+
+#[test]
+fn test_round_trip() {
+    let ntriples = stdout(serde_rdf().args([
+        "serialize",
+        "--config",
+        CONFIG,
+        "--input",
+        "tests/fixtures/record.json",
+        "--format",
+        "nt",
+    ]));
+    let json = stdout(
+        serde_rdf()
+            .args(["deserialize", "--config", CONFIG, "--format", "ntriples"])
+            .write_stdin(ntriples),
+    );
+    let record: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(record["name"], "Hôtel de Musique Bern");
+    assert_eq!(record["size"], 42);
+}
+
+#[test]
+fn test_errors() {
+    let output = serde_rdf()
+        .args([
+            "serialize",
+            "--config",
+            CONFIG,
+            "--input",
+            "tests/fixtures/record.json",
+            "--struct",
+            "Dataset",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .get_output()
+        .stderr
+        .clone();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "error: no subject config found for struct: Dataset\n"
+    );
+
+    let output = serde_rdf()
+        .args([
+            "deserialize",
+            "--config",
+            CONFIG,
+            "--input",
+            "tests/fixtures/data.ttl",
+            "--format",
+            "yaml",
+        ])
+        .assert()
+        .failure()
+        .get_output()
+        .stderr
+        .clone();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "error: unknown RDF format: yaml\n"
+    );
+
+    serde_rdf()
+        .args(["serialize", "--config"])
+        .assert()
+        .failure()
+        .code(2);
+    serde_rdf().arg("convert").assert().failure().code(2);
+}